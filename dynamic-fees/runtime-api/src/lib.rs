@@ -0,0 +1,32 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the dynamic fees pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query fees of an asset.
+    pub trait DynamicFeesApi<AssetId, Fee>
+    where
+        AssetId: Codec,
+        Fee: Codec,
+    {
+        /// Returns the asset fee and protocol fee of `asset_id` in the current block, without updating them.
+        fn current_fees(asset_id: AssetId) -> (Fee, Fee);
+    }
+}
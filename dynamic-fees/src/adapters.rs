@@ -0,0 +1,114 @@
+// This file is part of pallet-dynamic-fees.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of the pallet's traits on top of other components.
+
+use crate::traits::{Volume, VolumeProvider};
+use crate::{Balance, Config};
+use frame_support::traits::Get;
+use hydradx_traits::oracle::{AggregatedEntry, AggregatedOracle, OraclePeriod, Source};
+use sp_runtime::{
+    traits::{CheckedDiv, Saturating},
+    FixedPointNumber, FixedU128,
+};
+use sp_std::marker::PhantomData;
+
+/// Volume of an asset in the pool with the counter asset, as reported by the oracle.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct OracleVolume {
+    pub amount_in: Balance,
+    pub amount_out: Balance,
+}
+
+impl Volume<Balance> for OracleVolume {
+    fn amount_in(&self) -> Balance {
+        self.amount_in
+    }
+
+    fn amount_out(&self) -> Balance {
+        self.amount_out
+    }
+}
+
+/// `VolumeProvider` implementation reading the volume and liquidity of an asset from an `AggregatedOracle`.
+///
+/// The values are taken from the oracle entry of `OracleSource` for the asset and `CounterAsset`, aggregated over
+/// `Period`. Nothing is provided for `CounterAsset` itself or if there is no oracle entry for the asset.
+///
+/// The price change is the difference between the `LastBlock` price and the price aggregated over `Period`, relative
+/// to the latter.
+pub struct OracleVolumeProvider<T, Oracle, Price, OracleSource, CounterAsset, Period>(
+    PhantomData<(T, Oracle, Price, OracleSource, CounterAsset, Period)>,
+);
+
+impl<T, Oracle, Price, OracleSource, CounterAsset, Period>
+    OracleVolumeProvider<T, Oracle, Price, OracleSource, CounterAsset, Period>
+where
+    T: Config,
+    Oracle: AggregatedOracle<T::AssetId, Balance, T::BlockNumber, Price>,
+    Price: Into<(Balance, Balance)>,
+    OracleSource: Get<Source>,
+    CounterAsset: Get<T::AssetId>,
+    Period: Get<OraclePeriod>,
+{
+    fn oracle_entry(
+        asset_id: T::AssetId,
+        period: OraclePeriod,
+    ) -> Option<AggregatedEntry<Balance, T::BlockNumber, Price>> {
+        Oracle::get_entry(asset_id, CounterAsset::get(), period, OracleSource::get()).ok()
+    }
+}
+
+impl<T, Oracle, Price, OracleSource, CounterAsset, Period> VolumeProvider<T::AssetId, Balance>
+    for OracleVolumeProvider<T, Oracle, Price, OracleSource, CounterAsset, Period>
+where
+    T: Config,
+    Oracle: AggregatedOracle<T::AssetId, Balance, T::BlockNumber, Price>,
+    Price: Into<(Balance, Balance)>,
+    OracleSource: Get<Source>,
+    CounterAsset: Get<T::AssetId>,
+    Period: Get<OraclePeriod>,
+{
+    type Volume = OracleVolume;
+
+    fn asset_volume(asset_id: T::AssetId) -> Option<Self::Volume> {
+        Self::oracle_entry(asset_id, Period::get()).map(|entry| OracleVolume {
+            amount_in: entry.volume.a_in,
+            amount_out: entry.volume.a_out,
+        })
+    }
+
+    fn asset_liquidity(asset_id: T::AssetId) -> Option<Balance> {
+        Self::oracle_entry(asset_id, Period::get()).map(|entry| entry.liquidity.a)
+    }
+
+    fn asset_price_change(asset_id: T::AssetId) -> Option<FixedU128> {
+        let to_price = |entry: AggregatedEntry<Balance, T::BlockNumber, Price>| {
+            let (n, d) = entry.price.into();
+            FixedU128::checked_from_rational(n, d)
+        };
+        let last_price = to_price(Self::oracle_entry(asset_id, OraclePeriod::LastBlock)?)?;
+        let period_price = to_price(Self::oracle_entry(asset_id, Period::get())?)?;
+
+        let change = if last_price > period_price {
+            last_price.saturating_sub(period_price)
+        } else {
+            period_price.saturating_sub(last_price)
+        };
+        change.checked_div(&period_price)
+    }
+}
@@ -0,0 +1,91 @@
+// This file is part of pallet-dynamic-fees.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use crate::types::AssetFeeConfig;
+use frame_benchmarking::benchmarks;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+fn fee_params<T: Config>() -> FeeParams<T::Fee> {
+    FeeParams {
+        min_fee: T::Fee::from_percent(1),
+        max_fee: T::Fee::from_percent(40),
+        decay: FixedU128::saturating_from_rational(1, 10),
+        amplification: FixedU128::saturating_from_integer(2),
+    }
+}
+
+benchmarks! {
+    where_clause { where
+        T::AssetId: From<u32>,
+    }
+
+    set_asset_fee_config {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        let config = AssetFeeConfig {
+            asset_fee_params: fee_params::<T>(),
+            protocol_fee_params: fee_params::<T>(),
+        };
+    }: {
+        assert_ok!(crate::Pallet::<T>::set_asset_fee_config(origin, 1u32.into(), config));
+    }
+    verify {
+        assert_eq!(crate::Pallet::<T>::asset_fee_config(T::AssetId::from(1u32)), Some(config));
+    }
+
+    remove_asset_fee_config {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        let config = AssetFeeConfig {
+            asset_fee_params: fee_params::<T>(),
+            protocol_fee_params: fee_params::<T>(),
+        };
+        crate::Pallet::<T>::set_asset_fee_config(origin, 1u32.into(), config)?;
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+    }: {
+        assert_ok!(crate::Pallet::<T>::remove_asset_fee_config(origin, 1u32.into()));
+    }
+    verify {
+        assert!(crate::Pallet::<T>::asset_fee_config(T::AssetId::from(1u32)).is_none());
+    }
+
+    reset_asset_fee {
+        AssetFee::<T>::insert(T::AssetId::from(1u32), FeeEntry {
+            asset_fee: T::Fee::from_percent(2),
+            protocol_fee: T::Fee::from_percent(2),
+            timestamp: 1u32.into(),
+        });
+    }: {
+        assert_ok!(crate::Pallet::<T>::reset_asset_fee(RawOrigin::Root.into(), 1u32.into()));
+    }
+    verify {
+        assert!(crate::Pallet::<T>::current_fees(T::AssetId::from(1u32)).is_none());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pallet;
+    use crate::tests::mock::*;
+    use frame_benchmarking::impl_benchmark_test_suite;
+
+    impl_benchmark_test_suite!(Pallet, super::ExtBuilder::default().build(), super::Test);
+}
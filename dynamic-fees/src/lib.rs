@@ -34,12 +34,16 @@
 //! * **Asset fee amplification:** The amplification parameter for asset fee.
 //! * **Protocol fee amplification:** The amplification parameter for protocol fee.
 //! * **Minimum and maximum fee:** The minimum and maximum fee value for asset or protocol fee.
+//! * **Volatility fee:** Optional asset fee component driven by the price change of an asset over the oracle period.
 //!
 //! ### Storage
 //!
 //! The module stores last calculated fees as tuple of `(Fee, Fee, Block number)` where the first item is asset fee,
 //! the second one is protocol fee and the third one is block number indicating when the two fees were updated.
 //!
+//! Fee parameters of an asset can be overridden by `AuthorityOrigin`. Assets without an override use
+//! `AssetFeeParameters` and `ProtocolFeeParameters` from the pallet's configuration.
+//!
 //! ## Interface
 //!
 //! ### Update and retrieve fee
@@ -49,26 +53,51 @@
 //!
 //! On first retrieve call in a block, the asset fee as well as the protocol are updated and new fees are returned.
 //!
+//! If `VolatilityFeeParameters` are set and the oracle provides the price change of the asset, the volatility fee is
+//! added to the retrieved asset fee. The result is clamped to the minimum and maximum asset fee. Only the volume based
+//! asset fee is stored, so the volatility fee does not accumulate over blocks.
+//!
+//! `FeesUpdated` event is emitted when the fees of an asset change.
+//!
+//! ### Fee preview
+//!
+//! `calculate_current_fees` returns the fees of an asset in the current block without updating them. It is exposed to
+//! clients by `DynamicFeesApi` runtime API.
+//!
+//! ### Dispatchable functions
+//!
+//! * `set_asset_fee_config` - sets fee parameters of an asset.
+//! * `remove_asset_fee_config` - removes fee parameters of an asset, falling back to the configured defaults.
+//! * `reset_asset_fee` - removes the last calculated fees of an asset (root only).
+//!
 //! ### Prerequisites
 //!
 //! An oracle which provides volume in and out of an asset and liquidity.
+//!
+//! [`adapters::OracleVolumeProvider`] can be used to provide these values from an `AggregatedOracle`, such as the
+//! EMA oracle.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::Get;
+use frame_support::{ensure, pallet_prelude::DispatchResult, traits::Get};
 use orml_traits::GetByKey;
-use sp_runtime::traits::{BlockNumberProvider, Saturating};
-use sp_runtime::{FixedPointOperand, PerThing, SaturatedConversion};
+use sp_runtime::traits::{BlockNumberProvider, Saturating, Zero};
+use sp_runtime::{FixedPointNumber, FixedPointOperand, FixedU128, PerThing, SaturatedConversion};
 
+pub mod adapters;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 #[cfg(test)]
 mod tests;
 pub mod traits;
 pub mod types;
+pub mod weights;
 
 pub use pallet::*;
 
 use crate::traits::{Volume, VolumeProvider};
-use crate::types::{FeeEntry, FeeParams};
+use crate::types::{FeeEntry, FeeParams, VolatilityFeeParams};
+pub use crate::weights::WeightInfo;
 use hydra_dx_math::dynamic_fees::types::OracleEntry;
 use hydra_dx_math::dynamic_fees::{recalculate_asset_fee, recalculate_protocol_fee};
 
@@ -78,9 +107,12 @@ type Balance = u128;
 pub mod pallet {
     use super::*;
     use crate::traits::VolumeProvider;
-    use crate::types::FeeEntry;
+    use crate::types::{AssetFeeConfig, FeeEntry};
     use frame_support::pallet_prelude::*;
-    use frame_system::pallet_prelude::BlockNumberFor;
+    use frame_system::{
+        ensure_root,
+        pallet_prelude::{BlockNumberFor, OriginFor},
+    };
     use sp_runtime::traits::{BlockNumberProvider, Zero};
 
     #[pallet::pallet]
@@ -94,6 +126,12 @@ pub mod pallet {
     pub type AssetFee<T: Config> =
         StorageMap<_, Twox64Concat, T::AssetId, FeeEntry<T::Fee, T::BlockNumber>, OptionQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn asset_fee_config)]
+    /// Fee parameters of an asset overriding `AssetFeeParameters` and `ProtocolFeeParameters`.
+    pub type AssetFeeConfiguration<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, AssetFeeConfig<T::Fee>, OptionQuery>;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -115,16 +153,137 @@ pub mod pallet {
 
         #[pallet::constant]
         type ProtocolFeeParameters: Get<FeeParams<Self::Fee>>;
+
+        /// Parameters of the volatility fee component of the asset fee. The component is disabled if `None`.
+        #[pallet::constant]
+        type VolatilityFeeParameters: Get<Option<VolatilityFeeParams<Self::Fee>>>;
+
+        /// Origin which can set and remove fee parameters of an asset.
+        type AuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::event]
-    pub enum Event<T: Config> {}
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Fee parameters of an asset have been set.
+        AssetFeeConfigSet {
+            asset_id: T::AssetId,
+            config: AssetFeeConfig<T::Fee>,
+        },
+        /// Fee parameters of an asset have been removed.
+        AssetFeeConfigRemoved { asset_id: T::AssetId },
+        /// Fees of an asset have been updated.
+        FeesUpdated {
+            asset_id: T::AssetId,
+            asset_fee: T::Fee,
+            protocol_fee: T::Fee,
+        },
+        /// Fees of an asset have been reset.
+        AssetFeeReset { asset_id: T::AssetId },
+    }
 
     #[pallet::error]
-    pub enum Error<T> {}
+    pub enum Error<T> {
+        /// Minimum fee is higher than maximum fee.
+        InvalidMinMaxFee,
+        /// Fee amplification is zero.
+        ZeroAmplification,
+    }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Set fee parameters of an asset.
+        ///
+        /// The parameters are used instead of `AssetFeeParameters` and `ProtocolFeeParameters` when fees of the asset
+        /// are updated.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `asset_id`: asset id
+        /// - `config`: asset fee and protocol fee parameters
+        ///
+        /// Emits `AssetFeeConfigSet` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_asset_fee_config())]
+        pub fn set_asset_fee_config(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            config: AssetFeeConfig<T::Fee>,
+        ) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            Self::ensure_valid_fee_params(&config.asset_fee_params)?;
+            Self::ensure_valid_fee_params(&config.protocol_fee_params)?;
+
+            AssetFeeConfiguration::<T>::insert(asset_id, config);
+
+            Self::deposit_event(Event::AssetFeeConfigSet { asset_id, config });
+
+            Ok(())
+        }
+
+        /// Remove fee parameters of an asset.
+        ///
+        /// Fees of the asset are updated using `AssetFeeParameters` and `ProtocolFeeParameters` afterwards.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `asset_id`: asset id
+        ///
+        /// Emits `AssetFeeConfigRemoved` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_asset_fee_config())]
+        pub fn remove_asset_fee_config(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            AssetFeeConfiguration::<T>::remove(asset_id);
+
+            Self::deposit_event(Event::AssetFeeConfigRemoved { asset_id });
+
+            Ok(())
+        }
+
+        /// Reset fees of an asset.
+        ///
+        /// Removes the last calculated fees of the asset. Fees of the asset start from the minimum asset and protocol
+        /// fee afterwards.
+        ///
+        /// Can only be called by root.
+        ///
+        /// Parameters:
+        /// - `origin`: root
+        /// - `asset_id`: asset id
+        ///
+        /// Emits `AssetFeeReset` event when successful, followed by `FeesUpdated` event with the minimum fees if the
+        /// asset had fees.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::reset_asset_fee())]
+        pub fn reset_asset_fee(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let previous_fee_entry = AssetFee::<T>::take(asset_id);
+
+            Self::deposit_event(Event::AssetFeeReset { asset_id });
+
+            if previous_fee_entry.is_some() {
+                let (asset_fee_params, protocol_fee_params) = Self::fee_params(asset_id);
+                Self::deposit_event(Event::FeesUpdated {
+                    asset_id,
+                    asset_fee: asset_fee_params.min_fee,
+                    protocol_fee: protocol_fee_params.min_fee,
+                });
+            }
+
+            Ok(())
+        }
+    }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
@@ -151,15 +310,106 @@ pub mod pallet {
     }
 }
 
+impl<T: Config> Pallet<T> {
+    fn ensure_valid_fee_params(params: &FeeParams<T::Fee>) -> DispatchResult {
+        ensure!(params.min_fee <= params.max_fee, Error::<T>::InvalidMinMaxFee);
+        ensure!(!params.amplification.is_zero(), Error::<T>::ZeroAmplification);
+        Ok(())
+    }
+
+    /// Returns the asset fee and protocol fee parameters of an asset - the asset's override if set, the configured
+    /// defaults otherwise.
+    fn fee_params(asset_id: T::AssetId) -> (FeeParams<T::Fee>, FeeParams<T::Fee>) {
+        Self::asset_fee_config(asset_id).map_or_else(
+            || (T::AssetFeeParameters::get(), T::ProtocolFeeParameters::get()),
+            |config| (config.asset_fee_params, config.protocol_fee_params),
+        )
+    }
+
+    /// Returns the volatility fee component of the asset fee, if enabled and the price change of the asset is
+    /// available.
+    fn volatility_fee(asset_id: T::AssetId) -> Option<T::Fee> {
+        let params = T::VolatilityFeeParameters::get()?;
+        let price_change = T::Oracle::asset_price_change(asset_id)?;
+
+        let fee = params.amplification.saturating_mul(price_change);
+        let fee = T::Fee::from_rational(fee.into_inner(), FixedU128::DIV);
+
+        Some(fee.min(params.max_fee))
+    }
+
+    /// Returns the asset fee increased by the volatility fee, if enabled, and clamped to the minimum and maximum
+    /// asset fee.
+    fn with_volatility_fee(asset_id: T::AssetId, asset_fee: T::Fee) -> T::Fee {
+        match Self::volatility_fee(asset_id) {
+            Some(volatility_fee) => {
+                let (asset_fee_params, _) = Self::fee_params(asset_id);
+                asset_fee
+                    .saturating_add(volatility_fee)
+                    .clamp(asset_fee_params.min_fee, asset_fee_params.max_fee)
+            }
+            None => asset_fee,
+        }
+    }
+}
+
 impl<T: Config> Pallet<T>
 where
     <T::Fee as PerThing>::Inner: FixedPointOperand,
 {
     fn update_fee(asset_id: T::AssetId) -> (T::Fee, T::Fee) {
+        let (current_fee_entry, new_fee_entry) = Self::calculate_fees(asset_id);
+
+        let Some(fee_entry) = new_fee_entry else {
+            return (
+                Self::with_volatility_fee(asset_id, current_fee_entry.asset_fee),
+                current_fee_entry.protocol_fee,
+            );
+        };
+
+        AssetFee::<T>::insert(asset_id, fee_entry);
+
+        let asset_fee = Self::with_volatility_fee(asset_id, fee_entry.asset_fee);
+        if fee_entry.asset_fee != current_fee_entry.asset_fee
+            || fee_entry.protocol_fee != current_fee_entry.protocol_fee
+        {
+            Self::deposit_event(Event::FeesUpdated {
+                asset_id,
+                asset_fee,
+                protocol_fee: fee_entry.protocol_fee,
+            });
+        }
+
+        (asset_fee, fee_entry.protocol_fee)
+    }
+
+    /// Returns the asset fee and protocol fee of an asset in the current block without updating them.
+    ///
+    /// The fees are the same as returned by `UpdateAndRetrieveFees` in the current block.
+    pub fn calculate_current_fees(asset_id: T::AssetId) -> (T::Fee, T::Fee) {
+        let (current_fee_entry, new_fee_entry) = Self::calculate_fees(asset_id);
+        let fee_entry = new_fee_entry.unwrap_or(current_fee_entry);
+
+        (
+            Self::with_volatility_fee(asset_id, fee_entry.asset_fee),
+            fee_entry.protocol_fee,
+        )
+    }
+
+    /// Calculates fees of an asset in the current block.
+    ///
+    /// Returns the current fee entry of the asset and the new fee entry, if the fees need to be updated. The entries
+    /// hold the volume based fees only - the volatility fee is added when the fees are retrieved, so that it does
+    /// not accumulate over blocks.
+    fn calculate_fees(
+        asset_id: T::AssetId,
+    ) -> (
+        FeeEntry<T::Fee, T::BlockNumber>,
+        Option<FeeEntry<T::Fee, T::BlockNumber>>,
+    ) {
         let block_number = T::BlockNumberProvider::current_block_number();
 
-        let asset_fee_params = T::AssetFeeParameters::get();
-        let protocol_fee_params = T::ProtocolFeeParameters::get();
+        let (asset_fee_params, protocol_fee_params) = Self::fee_params(asset_id);
 
         let current_fee_entry = Self::current_fees(asset_id).unwrap_or(FeeEntry {
             asset_fee: asset_fee_params.min_fee,
@@ -169,7 +419,7 @@ where
 
         // Update only if it has not yet been updated this block
         if block_number == current_fee_entry.timestamp {
-            return (current_fee_entry.asset_fee, current_fee_entry.protocol_fee);
+            return (current_fee_entry, None);
         }
 
         let delta_blocks: u128 = block_number
@@ -177,10 +427,10 @@ where
             .saturated_into();
 
         let Some(volume) = T::Oracle::asset_volume(asset_id) else {
-            return (current_fee_entry.asset_fee, current_fee_entry.protocol_fee);
+            return (current_fee_entry, None);
         };
         let Some(liquidity) = T::Oracle::asset_liquidity(asset_id) else {
-            return (current_fee_entry.asset_fee, current_fee_entry.protocol_fee);
+            return (current_fee_entry, None);
         };
 
         let asset_fee = recalculate_asset_fee(
@@ -204,15 +454,12 @@ where
             protocol_fee_params.into(),
         );
 
-        AssetFee::<T>::insert(
-            asset_id,
-            FeeEntry {
-                asset_fee,
-                protocol_fee,
-                timestamp: block_number,
-            },
-        );
-        (asset_fee, protocol_fee)
+        let new_fee_entry = FeeEntry {
+            asset_fee,
+            protocol_fee,
+            timestamp: block_number,
+        };
+        (current_fee_entry, Some(new_fee_entry))
     }
 }
 
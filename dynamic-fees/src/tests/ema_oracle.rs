@@ -0,0 +1,155 @@
+use crate::tests::ema_oracle_mock::*;
+use crate::traits::VolumeProvider;
+use sp_runtime::traits::{One, Zero};
+use sp_runtime::FixedU128;
+
+#[test]
+fn fees_should_be_updated_from_oracle_when_asset_is_bought_from_pool() {
+    ExtBuilder::default()
+        .with_initial_fees(HDX, Fee::from_percent(2), Fee::from_percent(20))
+        .build()
+        .execute_with(|| {
+            trade(HDX, LRNA, ONE, ONE);
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(4), Fee::from_percent(18)));
+        });
+}
+
+#[test]
+fn fees_should_be_updated_from_oracle_when_asset_is_sold_to_pool() {
+    ExtBuilder::default()
+        .with_initial_fees(HDX, Fee::from_percent(20), Fee::from_percent(2))
+        .build()
+        .execute_with(|| {
+            trade(HDX, LRNA, 2 * ONE, 2 * ONE);
+            trade(LRNA, HDX, ONE, ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(18), Fee::from_percent(4)));
+        });
+}
+
+#[test]
+fn fees_should_only_react_to_trades_with_counter_asset() {
+    ExtBuilder::default()
+        .with_initial_fees(HDX, Fee::from_percent(2), Fee::from_percent(20))
+        .build()
+        .execute_with(|| {
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            trade(DOT, HDX, 10 * ONE, 10 * ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(6), Fee::from_percent(16)));
+        });
+}
+
+#[test]
+fn fees_should_not_change_when_asset_has_no_oracle_entry() {
+    ExtBuilder::default()
+        .with_initial_fees(DOT, Fee::from_percent(2), Fee::from_percent(20))
+        .build()
+        .execute_with(|| {
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(DOT);
+
+            assert_eq!(fee, (Fee::from_percent(2), Fee::from_percent(20)));
+        });
+}
+
+#[test]
+fn fees_should_not_change_for_counter_asset() {
+    ExtBuilder::default()
+        .with_initial_fees(LRNA, Fee::from_percent(2), Fee::from_percent(20))
+        .build()
+        .execute_with(|| {
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(LRNA);
+
+            assert_eq!(fee, (Fee::from_percent(2), Fee::from_percent(20)));
+        });
+}
+
+#[test]
+fn fees_should_not_change_when_there_was_no_trade_in_last_block() {
+    ExtBuilder::default()
+        .with_initial_fees(HDX, Fee::from_percent(2), Fee::from_percent(20))
+        .build()
+        .execute_with(|| {
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            next_block();
+            next_block();
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(2), Fee::from_percent(20)));
+        });
+}
+
+#[test]
+fn fees_should_not_include_volatility_fee_when_price_is_stable() {
+    ExtBuilder::default()
+        .with_initial_fees(HDX, Fee::from_percent(2), Fee::from_percent(20))
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .build()
+        .execute_with(|| {
+            trade(HDX, LRNA, ONE, ONE);
+            trade(LRNA, HDX, 2 * ONE, 2 * ONE);
+            next_block();
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(4), Fee::from_percent(18)));
+        });
+}
+
+#[test]
+fn price_change_should_be_zero_when_price_is_stable() {
+    ExtBuilder::default().build().execute_with(|| {
+        trade(HDX, LRNA, ONE, ONE);
+        next_block();
+        trade(HDX, LRNA, ONE, ONE);
+        next_block();
+
+        assert_eq!(
+            TenMinutesVolumeProvider::asset_price_change(HDX),
+            Some(FixedU128::zero())
+        );
+    });
+}
+
+#[test]
+fn price_change_should_be_relative_to_period_price_when_price_moved() {
+    ExtBuilder::default().build().execute_with(|| {
+        trade(HDX, LRNA, ONE, ONE);
+        next_block();
+        trade_with_liquidity(HDX, LRNA, ONE, ONE, LIQUIDITY, 2 * LIQUIDITY);
+        next_block();
+
+        let change = TenMinutesVolumeProvider::asset_price_change(HDX).unwrap();
+
+        assert!(change > FixedU128::zero());
+        assert!(change < FixedU128::one());
+    });
+}
+
+#[test]
+fn price_change_should_not_be_provided_when_asset_has_no_oracle_entry() {
+    ExtBuilder::default().build().execute_with(|| {
+        trade(HDX, LRNA, ONE, ONE);
+        next_block();
+
+        assert_eq!(TenMinutesVolumeProvider::asset_price_change(DOT), None);
+    });
+}
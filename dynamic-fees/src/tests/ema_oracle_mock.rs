@@ -0,0 +1,220 @@
+// This file is part of warehouse
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment wiring the pallet to the EMA oracle via `OracleVolumeProvider`.
+
+use crate::adapters::OracleVolumeProvider;
+use crate::types::{FeeEntry, FeeParams, VolatilityFeeParams};
+use crate::{Config, UpdateAndRetrieveFees};
+
+use frame_support::{
+    assert_ok, bounded_vec, construct_runtime, parameter_types,
+    traits::{ConstU32, ConstU64, Hooks},
+};
+use frame_system::EnsureRoot;
+use hydradx_traits::{OnTradeHandler, OraclePeriod, Source};
+use orml_traits::GetByKey;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup, One, Zero},
+    FixedU128, Perquintill,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type Balance = u128;
+pub type AssetId = u32;
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+
+pub const HDX: AssetId = 0;
+pub const LRNA: AssetId = 1;
+pub const DOT: AssetId = 2;
+
+pub const ONE: Balance = 1_000_000_000_000;
+pub const LIQUIDITY: Balance = 50 * ONE;
+
+pub const SOURCE: Source = *b"dummysrc";
+
+pub(crate) type Fee = Perquintill;
+
+thread_local! {
+    static VOLATILITY_FEE_PARAMS: RefCell<Option<VolatilityFeeParams<Fee>>> = RefCell::new(None);
+}
+
+construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EmaOracle: pallet_ema_oracle::{Pallet, Call, Storage, Event<T>},
+        DynamicFees: crate::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+pallet_ema_oracle::impl_test_config!(Test, bounded_vec![OraclePeriod::LastBlock, OraclePeriod::TenMinutes]);
+
+parameter_types! {
+    pub FeeParameters: FeeParams<Fee> = FeeParams {
+        min_fee: Fee::from_percent(1),
+        max_fee: Fee::from_percent(40),
+        decay: FixedU128::zero(),
+        amplification: FixedU128::one(),
+    };
+    pub const OracleSource: Source = SOURCE;
+    pub const CounterAsset: AssetId = LRNA;
+    pub const VolumePeriod: OraclePeriod = OraclePeriod::LastBlock;
+    pub const TenMinutesPeriod: OraclePeriod = OraclePeriod::TenMinutes;
+    pub VolatilityFeeParameters: Option<VolatilityFeeParams<Fee>> = VOLATILITY_FEE_PARAMS.with(|v| *v.borrow());
+}
+
+/// Adapter comparing the last block price with the price aggregated over ten minutes.
+pub(crate) type TenMinutesVolumeProvider =
+    OracleVolumeProvider<Test, EmaOracle, pallet_ema_oracle::Price, OracleSource, CounterAsset, TenMinutesPeriod>;
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Fee = Fee;
+    type AssetId = AssetId;
+    type BlockNumberProvider = System;
+    type Oracle =
+        OracleVolumeProvider<Test, EmaOracle, pallet_ema_oracle::Price, OracleSource, CounterAsset, VolumePeriod>;
+    type AssetFeeParameters = FeeParameters;
+    type ProtocolFeeParameters = FeeParameters;
+    type VolatilityFeeParameters = VolatilityFeeParameters;
+    type AuthorityOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = ();
+}
+
+pub struct ExtBuilder {
+    initial_fees: Vec<(AssetId, Fee, Fee)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        VOLATILITY_FEE_PARAMS.with(|v| {
+            *v.borrow_mut() = None;
+        });
+
+        Self { initial_fees: vec![] }
+    }
+}
+
+impl ExtBuilder {
+    pub fn with_initial_fees(mut self, asset_id: AssetId, asset_fee: Fee, protocol_fee: Fee) -> Self {
+        self.initial_fees.push((asset_id, asset_fee, protocol_fee));
+        self
+    }
+
+    pub fn with_volatility_fee_params(self, amplification: FixedU128, max_fee: Fee) -> Self {
+        VOLATILITY_FEE_PARAMS.with(|v| {
+            *v.borrow_mut() = Some(VolatilityFeeParams { amplification, max_fee });
+        });
+
+        self
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut r: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into();
+        r.execute_with(|| {
+            System::set_block_number(1);
+            for (asset_id, asset_fee, protocol_fee) in self.initial_fees {
+                crate::AssetFee::<Test>::insert(
+                    asset_id,
+                    FeeEntry {
+                        asset_fee,
+                        protocol_fee,
+                        timestamp: 1,
+                    },
+                );
+            }
+        });
+
+        r
+    }
+}
+
+/// Records a trade of `amount_in` of `asset_in` for `amount_out` of `asset_out` in the current block. Both assets
+/// have `LIQUIDITY` in the pool after the trade.
+pub(crate) fn trade(asset_in: AssetId, asset_out: AssetId, amount_in: Balance, amount_out: Balance) {
+    trade_with_liquidity(asset_in, asset_out, amount_in, amount_out, LIQUIDITY, LIQUIDITY);
+}
+
+/// Same as `trade`, but with the given pool liquidity after the trade, which determines the oracle price.
+pub(crate) fn trade_with_liquidity(
+    asset_in: AssetId,
+    asset_out: AssetId,
+    amount_in: Balance,
+    amount_out: Balance,
+    liquidity_in: Balance,
+    liquidity_out: Balance,
+) {
+    assert_ok!(pallet_ema_oracle::OnActivityHandler::<Test>::on_trade(
+        SOURCE,
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        liquidity_in,
+        liquidity_out,
+    ));
+}
+
+/// Updates the oracle with the trades of the current block and moves to the next block.
+pub(crate) fn next_block() {
+    EmaOracle::on_finalize(System::block_number());
+    System::set_block_number(System::block_number() + 1);
+}
+
+pub(crate) fn retrieve_fee_entry(asset_id: AssetId) -> (Fee, Fee) {
+    <UpdateAndRetrieveFees<Test> as GetByKey<AssetId, (Fee, Fee)>>::get(&asset_id)
+}
@@ -0,0 +1,244 @@
+use crate::tests::mock::*;
+use crate::tests::oracle::SingleValueOracle;
+use crate::types::{AssetFeeConfig, FeeParams};
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::{One, Zero};
+use sp_runtime::{DispatchError, FixedU128};
+
+fn fee_params(min_fee: Fee, max_fee: Fee) -> FeeParams<Fee> {
+    FeeParams {
+        min_fee,
+        max_fee,
+        decay: FixedU128::zero(),
+        amplification: FixedU128::one(),
+    }
+}
+
+fn asset_fee_config() -> AssetFeeConfig<Fee> {
+    AssetFeeConfig {
+        asset_fee_params: fee_params(Fee::from_percent(1), Fee::from_percent(3)),
+        protocol_fee_params: fee_params(Fee::from_percent(1), Fee::from_percent(3)),
+    }
+}
+
+#[test]
+fn set_asset_fee_config_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(DynamicFees::set_asset_fee_config(
+            RuntimeOrigin::root(),
+            HDX,
+            asset_fee_config()
+        ));
+
+        assert_eq!(DynamicFees::asset_fee_config(HDX), Some(asset_fee_config()));
+        System::assert_last_event(
+            Event::AssetFeeConfigSet {
+                asset_id: HDX,
+                config: asset_fee_config(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn set_asset_fee_config_should_fail_when_origin_is_not_authority() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            DynamicFees::set_asset_fee_config(RuntimeOrigin::signed(1), HDX, asset_fee_config()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_asset_fee_config_should_fail_when_min_fee_is_higher_than_max_fee() {
+    ExtBuilder::default().build().execute_with(|| {
+        let mut config = asset_fee_config();
+        config.protocol_fee_params = fee_params(Fee::from_percent(4), Fee::from_percent(3));
+
+        assert_noop!(
+            DynamicFees::set_asset_fee_config(RuntimeOrigin::root(), HDX, config),
+            Error::<Test>::InvalidMinMaxFee
+        );
+    });
+}
+
+#[test]
+fn set_asset_fee_config_should_fail_when_amplification_is_zero() {
+    ExtBuilder::default().build().execute_with(|| {
+        let mut config = asset_fee_config();
+        config.asset_fee_params.amplification = FixedU128::zero();
+
+        assert_noop!(
+            DynamicFees::set_asset_fee_config(RuntimeOrigin::root(), HDX, config),
+            Error::<Test>::ZeroAmplification
+        );
+    });
+}
+
+#[test]
+fn remove_asset_fee_config_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(DynamicFees::set_asset_fee_config(
+            RuntimeOrigin::root(),
+            HDX,
+            asset_fee_config()
+        ));
+
+        assert_ok!(DynamicFees::remove_asset_fee_config(RuntimeOrigin::root(), HDX));
+
+        assert_eq!(DynamicFees::asset_fee_config(HDX), None);
+        System::assert_last_event(Event::AssetFeeConfigRemoved { asset_id: HDX }.into());
+    });
+}
+
+#[test]
+fn asset_fee_should_use_asset_fee_config_when_set() {
+    let initial_fee = Fee::from_percent(2);
+
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_initial_fees(initial_fee, Fee::zero(), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .build()
+        .execute_with(|| {
+            assert_ok!(DynamicFees::set_asset_fee_config(
+                RuntimeOrigin::root(),
+                HDX,
+                asset_fee_config()
+            ));
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(3));
+        });
+}
+
+#[test]
+fn asset_fee_should_use_default_params_when_asset_fee_config_is_removed() {
+    let initial_fee = Fee::from_percent(2);
+
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_initial_fees(initial_fee, Fee::zero(), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .build()
+        .execute_with(|| {
+            assert_ok!(DynamicFees::set_asset_fee_config(
+                RuntimeOrigin::root(),
+                HDX,
+                asset_fee_config()
+            ));
+            assert_ok!(DynamicFees::remove_asset_fee_config(RuntimeOrigin::root(), HDX));
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(4));
+        });
+}
+
+#[test]
+fn reset_asset_fee_should_work() {
+    ExtBuilder::default()
+        .with_initial_fees(Fee::from_percent(2), Fee::from_percent(20), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_protocol_fee_params(
+            Fee::from_percent(2),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            assert_ok!(DynamicFees::reset_asset_fee(RuntimeOrigin::root(), HDX));
+
+            assert_eq!(DynamicFees::current_fees(HDX), None);
+            System::assert_has_event(Event::AssetFeeReset { asset_id: HDX }.into());
+            System::assert_last_event(
+                Event::FeesUpdated {
+                    asset_id: HDX,
+                    asset_fee: Fee::from_percent(1),
+                    protocol_fee: Fee::from_percent(2),
+                }
+                .into(),
+            );
+        });
+}
+
+#[test]
+fn reset_asset_fee_should_not_emit_fees_updated_event_when_asset_has_no_fees() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(DynamicFees::reset_asset_fee(RuntimeOrigin::root(), HDX));
+
+        assert_eq!(System::events().len(), 1);
+        System::assert_last_event(Event::AssetFeeReset { asset_id: HDX }.into());
+    });
+}
+
+#[test]
+fn reset_asset_fee_should_fail_when_origin_is_not_root() {
+    ExtBuilder::default()
+        .with_initial_fees(Fee::from_percent(2), Fee::from_percent(20), 0)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                DynamicFees::reset_asset_fee(RuntimeOrigin::signed(1), HDX),
+                DispatchError::BadOrigin
+            );
+        });
+}
+
+#[test]
+fn fees_should_start_from_min_fees_when_asset_fee_is_reset() {
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_initial_fees(Fee::from_percent(20), Fee::from_percent(20), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_protocol_fee_params(
+            Fee::from_percent(2),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            assert_ok!(DynamicFees::reset_asset_fee(RuntimeOrigin::root(), HDX));
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee, (Fee::from_percent(1), Fee::from_percent(2)));
+        });
+}
@@ -0,0 +1,148 @@
+use crate::tests::mock::*;
+use crate::tests::oracle::SingleValueOracle;
+use crate::types::FeeEntry;
+use crate::Event;
+use sp_runtime::traits::{One, Zero};
+use sp_runtime::FixedU128;
+
+fn ext_builder(oracle: SingleValueOracle) -> ExtBuilder {
+    ExtBuilder::default()
+        .with_oracle(oracle)
+        .with_initial_fees(Fee::from_percent(2), Fee::from_percent(20), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_protocol_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+}
+
+#[test]
+fn calculate_current_fees_should_return_updated_fees_when_fees_were_not_updated_in_current_block() {
+    ext_builder(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fees = DynamicFees::calculate_current_fees(HDX);
+
+            assert_eq!(fees, (Fee::from_percent(4), Fee::from_percent(18)));
+        });
+}
+
+#[test]
+fn calculate_current_fees_should_not_update_fees() {
+    ext_builder(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            DynamicFees::calculate_current_fees(HDX);
+
+            assert_eq!(
+                DynamicFees::current_fees(HDX),
+                Some(FeeEntry {
+                    asset_fee: Fee::from_percent(2),
+                    protocol_fee: Fee::from_percent(20),
+                    timestamp: 0,
+                })
+            );
+            assert!(System::events().is_empty());
+        });
+}
+
+#[test]
+fn calculate_current_fees_should_return_same_fees_as_update() {
+    ext_builder(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let preview = DynamicFees::calculate_current_fees(HDX);
+            let fees = retrieve_fee_entry(HDX);
+
+            assert_eq!(preview, fees);
+            assert_eq!(DynamicFees::calculate_current_fees(HDX), fees);
+        });
+}
+
+#[test]
+fn calculate_current_fees_should_return_min_fees_when_asset_has_no_fees() {
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_protocol_fee_params(
+            Fee::from_percent(2),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fees = DynamicFees::calculate_current_fees(HDX);
+
+            assert_eq!(fees, (Fee::from_percent(1), Fee::from_percent(2)));
+        });
+}
+
+#[test]
+fn update_should_emit_event_when_fees_changed() {
+    ext_builder(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            retrieve_fee_entry(HDX);
+
+            System::assert_last_event(
+                Event::FeesUpdated {
+                    asset_id: HDX,
+                    asset_fee: Fee::from_percent(4),
+                    protocol_fee: Fee::from_percent(18),
+                }
+                .into(),
+            );
+        });
+}
+
+#[test]
+fn update_should_not_emit_event_when_fees_did_not_change() {
+    ext_builder(SingleValueOracle::new(ONE, ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fees = retrieve_fee_entry(HDX);
+
+            assert_eq!(fees, (Fee::from_percent(2), Fee::from_percent(20)));
+            assert_eq!(DynamicFees::current_fees(HDX).map(|entry| entry.timestamp), Some(1));
+            assert!(System::events().is_empty());
+        });
+}
+
+#[test]
+fn update_should_emit_event_only_once_per_block() {
+    ext_builder(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            retrieve_fee_entry(HDX);
+            retrieve_fee_entry(HDX);
+
+            assert_eq!(System::events().len(), 1);
+        });
+}
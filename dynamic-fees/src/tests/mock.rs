@@ -26,6 +26,7 @@ use frame_support::{
     construct_runtime, parameter_types,
     traits::{ConstU32, ConstU64},
 };
+use frame_system::EnsureRoot;
 use orml_traits::GetByKey;
 pub use orml_traits::MultiCurrency;
 use sp_core::H256;
@@ -38,7 +39,7 @@ use sp_runtime::{
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 use crate::tests::oracle::Oracle;
-use crate::types::{FeeEntry, FeeParams};
+use crate::types::{FeeEntry, FeeParams, VolatilityFeeParams};
 use sp_runtime::traits::{One, Zero};
 
 pub type Balance = u128;
@@ -56,6 +57,8 @@ thread_local! {
     pub static BLOCK: RefCell<usize> = RefCell::new(0);
     pub static ASSET_FEE_PARAMS: RefCell<FeeParams<Fee>> = RefCell::new(fee_params_default());
     pub static PROTOCOL_FEE_PARAMS: RefCell<FeeParams<Fee>> = RefCell::new(fee_params_default());
+    pub static VOLATILITY_FEE_PARAMS: RefCell<Option<VolatilityFeeParams<Fee>>> = RefCell::new(None);
+    pub static PRICE_CHANGE: RefCell<Option<FixedU128>> = RefCell::new(None);
 }
 
 fn fee_params_default() -> FeeParams<Fee> {
@@ -108,6 +111,7 @@ impl frame_system::Config for Test {
 parameter_types! {
     pub AssetFeeParams: FeeParams<Fee>= ASSET_FEE_PARAMS.with(|v| *v.borrow());
     pub ProtocolFeeParams: FeeParams<Fee>= PROTOCOL_FEE_PARAMS.with(|v| *v.borrow());
+    pub VolatilityFeeParameters: Option<VolatilityFeeParams<Fee>> = VOLATILITY_FEE_PARAMS.with(|v| *v.borrow());
 }
 
 impl Config for Test {
@@ -118,6 +122,9 @@ impl Config for Test {
     type Oracle = OracleProvider;
     type AssetFeeParameters = AssetFeeParams;
     type ProtocolFeeParameters = ProtocolFeeParams;
+    type VolatilityFeeParameters = VolatilityFeeParameters;
+    type AuthorityOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = ();
 }
 
 pub struct ExtBuilder {
@@ -129,6 +136,12 @@ impl Default for ExtBuilder {
         ORACLE.with(|v| {
             *v.borrow_mut() = Box::new(Oracle::new());
         });
+        VOLATILITY_FEE_PARAMS.with(|v| {
+            *v.borrow_mut() = None;
+        });
+        PRICE_CHANGE.with(|v| {
+            *v.borrow_mut() = None;
+        });
 
         Self { initial_fee: None }
     }
@@ -167,6 +180,22 @@ impl ExtBuilder {
         self
     }
 
+    pub fn with_volatility_fee_params(self, amplification: FixedU128, max_fee: Fee) -> Self {
+        VOLATILITY_FEE_PARAMS.with(|v| {
+            *v.borrow_mut() = Some(VolatilityFeeParams { amplification, max_fee });
+        });
+
+        self
+    }
+
+    pub fn with_price_change(self, price_change: FixedU128) -> Self {
+        PRICE_CHANGE.with(|v| {
+            *v.borrow_mut() = Some(price_change);
+        });
+
+        self
+    }
+
     pub fn with_oracle(self, oracle: impl CustomOracle + 'static) -> Self {
         ORACLE.with(|v| {
             *v.borrow_mut() = Box::new(oracle);
@@ -215,6 +244,10 @@ impl VolumeProvider<AssetId, Balance> for OracleProvider {
         let liquidity = ORACLE.with(|v| v.borrow().liquidity(asset_id, BLOCK.with(|v| *v.borrow())));
         Some(liquidity)
     }
+
+    fn asset_price_change(_asset_id: AssetId) -> Option<FixedU128> {
+        PRICE_CHANGE.with(|v| *v.borrow())
+    }
 }
 
 #[derive(Default, Clone, Debug)]
@@ -1,7 +1,12 @@
 mod decay;
+mod ema_oracle;
+mod ema_oracle_mock;
+mod fee_config;
+mod fee_preview;
 mod fees;
 mod limits;
-mod mock;
+pub(crate) mod mock;
 mod oracle;
 mod oracle_fees;
 mod property;
+mod volatility;
@@ -0,0 +1,168 @@
+use crate::tests::mock::*;
+use crate::tests::oracle::SingleValueOracle;
+use sp_runtime::traits::{One, Zero};
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+fn ext_builder() -> ExtBuilder {
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_initial_fees(Fee::from_percent(2), Fee::from_percent(20), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_protocol_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(40),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+}
+
+#[test]
+fn asset_fee_should_include_volatility_fee_when_price_changed() {
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(9));
+        });
+}
+
+#[test]
+fn volatility_fee_should_be_amplified() {
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::from(2), Fee::from_percent(20))
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(14));
+        });
+}
+
+#[test]
+fn volatility_fee_should_not_exceed_its_max_fee() {
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .with_price_change(FixedU128::saturating_from_rational(50, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(14));
+        });
+}
+
+#[test]
+fn asset_fee_should_not_exceed_max_limit_when_volatility_fee_is_added() {
+    ExtBuilder::default()
+        .with_oracle(SingleValueOracle::new(ONE, 2 * ONE, 50 * ONE))
+        .with_initial_fees(Fee::from_percent(2), Fee::from_percent(20), 0)
+        .with_asset_fee_params(
+            Fee::from_percent(1),
+            Fee::from_percent(6),
+            FixedU128::zero(),
+            FixedU128::one(),
+        )
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(6));
+        });
+}
+
+#[test]
+fn volatility_fee_should_not_be_applied_when_params_are_not_set() {
+    ext_builder()
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(4));
+        });
+}
+
+#[test]
+fn volatility_fee_should_not_be_applied_when_price_change_is_not_available() {
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.0, Fee::from_percent(4));
+        });
+}
+
+#[test]
+fn protocol_fee_should_not_include_volatility_fee() {
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            let fee = retrieve_fee_entry(HDX);
+
+            assert_eq!(fee.1, Fee::from_percent(18));
+        });
+}
+
+#[test]
+fn volatility_fee_should_not_compound_over_blocks() {
+    let fees_without_volatility: Vec<Fee> = ext_builder().build().execute_with(|| {
+        (1..=10)
+            .map(|block| {
+                System::set_block_number(block);
+                retrieve_fee_entry(HDX).0
+            })
+            .collect()
+    });
+
+    ext_builder()
+        .with_volatility_fee_params(FixedU128::one(), Fee::from_percent(10))
+        .with_price_change(FixedU128::saturating_from_rational(5, 100))
+        .build()
+        .execute_with(|| {
+            for (block, fee_without_volatility) in (1..=10).zip(fees_without_volatility) {
+                System::set_block_number(block);
+
+                let fee = retrieve_fee_entry(HDX);
+
+                assert_eq!(
+                    fee.0,
+                    fee_without_volatility
+                        .saturating_add(Fee::from_percent(5))
+                        .min(Fee::from_percent(40))
+                );
+                assert_eq!(
+                    DynamicFees::current_fees(HDX).unwrap().asset_fee,
+                    fee_without_volatility
+                );
+            }
+        });
+}
@@ -1,3 +1,5 @@
+use sp_runtime::FixedU128;
+
 pub trait Volume<Balance> {
     fn amount_in(&self) -> Balance;
     fn amount_out(&self) -> Balance;
@@ -9,4 +11,11 @@ pub trait VolumeProvider<AssetId, Balance> {
     fn asset_volume(asset_id: AssetId) -> Option<Self::Volume>;
 
     fn asset_liquidity(asset_id: AssetId) -> Option<Balance>;
+
+    /// Relative change of the asset price over the oracle period, e.g. `0.05` for a change of 5%.
+    ///
+    /// Used by the volatility fee component. No volatility fee is applied if `None` is returned.
+    fn asset_price_change(_asset_id: AssetId) -> Option<FixedU128> {
+        None
+    }
 }
@@ -7,7 +7,7 @@ use hydra_dx_math::dynamic_fees::types::FeeParams as MathFeeParams;
 
 use scale_info::TypeInfo;
 
-#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct FeeParams<Fee> {
     pub min_fee: Fee,
@@ -16,6 +16,25 @@ pub struct FeeParams<Fee> {
     pub amplification: FixedU128,
 }
 
+/// Parameters of the asset fee component driven by the price volatility of an asset.
+///
+/// The component is the relative price change over the oracle period multiplied by `amplification`, capped at
+/// `max_fee`.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VolatilityFeeParams<Fee> {
+    pub amplification: FixedU128,
+    pub max_fee: Fee,
+}
+
+/// Fee parameters of an asset which override `AssetFeeParameters` and `ProtocolFeeParameters`.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssetFeeConfig<Fee> {
+    pub asset_fee_params: FeeParams<Fee>,
+    pub protocol_fee_params: FeeParams<Fee>,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct FeeEntry<Fee, Block> {
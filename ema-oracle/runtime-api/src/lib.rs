@@ -0,0 +1,52 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the EMA oracle pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use hydradx_traits::AggregatedEntry;
+use pallet_ema_oracle::{AssetId, Balance, OracleError, OraclePeriod, Price, Source};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query EMA oracles.
+    pub trait EmaOracleApi<BlockNumber>
+    where
+        BlockNumber: Codec,
+    {
+        /// Returns the entry of the oracle for the given source, assets and period, updated to the
+        /// parent block.
+        fn get_entry(
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            period: OraclePeriod,
+        ) -> Result<AggregatedEntry<Balance, BlockNumber, Price>, OracleError>;
+
+        /// Returns the updated entries of all supported periods for the given source and assets.
+        fn get_entries(
+            source: Source,
+            asset_a: AssetId,
+            asset_b: AssetId,
+        ) -> Vec<(OraclePeriod, AggregatedEntry<Balance, BlockNumber, Price>)>;
+
+        /// Returns the asset pairs tracked by oracles of the given source.
+        fn tracked_pairs(source: Source) -> Vec<(AssetId, AssetId)>;
+    }
+}
@@ -22,8 +22,9 @@ use super::*;
 pub const HDX: AssetId = 1_000;
 pub const DOT: AssetId = 2_000;
 
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::{assert_ok, traits::Hooks};
+use frame_system::RawOrigin;
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -44,6 +45,7 @@ benchmarks! {
     on_finalize_insert_one_token {
         let block_num: T::BlockNumber = 5u32.into();
         let prev_block = block_num.saturating_sub(One::one());
+        whitelist_oracles::<T>([(HDX, DOT)]);
 
         frame_system::Pallet::<T>::set_block_number(prev_block);
         EmaOracle::<T>::on_initialize(prev_block);
@@ -59,6 +61,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
 
@@ -67,7 +70,7 @@ benchmarks! {
     }: { EmaOracle::<T>::on_finalize(block_num); }
     verify {
         assert!(Accumulator::<T>::get().is_empty());
-        assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LastBlock)).unwrap(), (entry, block_num));
+        assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).unwrap(), (entry, block_num));
     }
 
     #[extra]
@@ -75,6 +78,7 @@ benchmarks! {
         let initial_data_block: T::BlockNumber = 5u32.into();
         // higher update time difference might make exponentiation more expensive
         let block_num = initial_data_block.saturating_add(1_000_000u32.into());
+        whitelist_oracles::<T>([(HDX, DOT)]);
 
         frame_system::Pallet::<T>::set_block_number(initial_data_block);
         EmaOracle::<T>::on_initialize(initial_data_block);
@@ -91,6 +95,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
 
@@ -99,11 +104,12 @@ benchmarks! {
     }: { EmaOracle::<T>::on_finalize(block_num); }
     verify {
         assert!(Accumulator::<T>::get().is_empty());
-        assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LastBlock)).unwrap(), (entry, initial_data_block));
+        assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).unwrap(), (entry, initial_data_block));
     }
 
     on_finalize_multiple_tokens {
         let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+        whitelist_oracles::<T>((0..b).map(|i| (i * 1_000, i * 1_000 + 500)));
 
         let initial_data_block: T::BlockNumber = 5u32.into();
         let block_num = initial_data_block.saturating_add(1_000_000u32.into());
@@ -132,18 +138,20 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
 
         for i in 0 .. b {
             let asset_a = i * 1_000;
             let asset_b = asset_a + 500;
-            assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(asset_a, asset_b), LastBlock)).unwrap(), (entry.clone(), initial_data_block));
+            assert_eq!(Oracles::<T>::get((SOURCE, ordered_pair(asset_a, asset_b), LAST_BLOCK_PERIOD_LENGTH)).unwrap(), (entry.clone(), initial_data_block));
         }
     }
 
     on_trade_multiple_tokens {
         let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+        whitelist_oracles::<T>((0..=b).map(|i| (i * 1_000, i * 1_000 + 500)));
 
         let initial_data_block: T::BlockNumber = 5u32.into();
         let block_num = initial_data_block.saturating_add(1_000_000u32.into());
@@ -167,6 +175,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_in, liquidity_asset_out)),
             volume: Volume::from_a_in_b_out(amount_in, amount_out),
             liquidity: Liquidity::new(liquidity_asset_in, liquidity_asset_out),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
         for i in 0 .. b {
@@ -194,6 +203,7 @@ benchmarks! {
 
     on_liquidity_changed_multiple_tokens {
         let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+        whitelist_oracles::<T>((0..=b).map(|i| (i * 1_000, i * 1_000 + 500)));
 
         let initial_data_block: T::BlockNumber = 5u32.into();
         let block_num = initial_data_block.saturating_add(1_000_000u32.into());
@@ -217,6 +227,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_a, liquidity_asset_b)),
             volume: Volume::from_a_in_b_out(amount_a, amount_b),
             liquidity: Liquidity::new(liquidity_asset_a, liquidity_asset_b),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
         for i in 0 .. b {
@@ -241,6 +252,7 @@ benchmarks! {
             price: Price::from((liquidity_asset_a, liquidity_asset_b)),
             volume: Volume::default(),
             liquidity: Liquidity::new(liquidity_asset_a, liquidity_asset_b),
+            volatility: Volatility::zero(),
             timestamp: block_num,
         };
         entries.push(((SOURCE, ordered_pair(asset_a, asset_b)), liquidity_entry));
@@ -259,6 +271,7 @@ benchmarks! {
         let (liquidity_asset_in, liquidity_asset_out) = (1_000_000_000_000_000, 2_000_000_000_000_000);
         let asset_a = 1_000;
         let asset_b = asset_a + 500;
+        whitelist_oracles::<T>([(asset_a, asset_b)]);
         assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, asset_a, asset_b, amount_in, amount_out, liquidity_asset_in, liquidity_asset_out));
         EmaOracle::<T>::on_finalize(initial_data_block);
 
@@ -280,5 +293,146 @@ benchmarks! {
         }));
     }
 
+    add_oracle {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+    }: _<T::RuntimeOrigin>(origin, SOURCE, (HDX, DOT))
+    verify {
+        assert!(WhitelistedAssets::<T>::get().contains(&(SOURCE, ordered_pair(HDX, DOT))));
+    }
+
+    remove_oracle {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        WhitelistedAssets::<T>::mutate(|list| list.try_insert((SOURCE, ordered_pair(HDX, DOT))).unwrap());
+
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, HDX, DOT, 1_000_000_000_000, 2_000_000_000_000, 1_000_000_000_000_000, 2_000_000_000_000_000));
+        EmaOracle::<T>::on_finalize(block_num);
+        assert!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).is_some());
+    }: _<T::RuntimeOrigin>(origin, SOURCE, (HDX, DOT))
+    verify {
+        assert!(!WhitelistedAssets::<T>::get().contains(&(SOURCE, ordered_pair(HDX, DOT))));
+        assert!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).is_none());
+    }
+
+    set_price_path {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        let path = price_path_of_max_length();
+    }: _<T::RuntimeOrigin>(origin, HDX, DOT, path.clone())
+    verify {
+        assert_eq!(PricePaths::<T>::get((HDX, DOT)), Some(path));
+    }
+
+    remove_price_path {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        PricePaths::<T>::insert((HDX, DOT), price_path_of_max_length());
+    }: _<T::RuntimeOrigin>(origin, (HDX, DOT))
+    verify {
+        assert!(PricePaths::<T>::get((HDX, DOT)).is_none());
+    }
+
+    prune {
+        whitelist_oracles::<T>([(HDX, DOT)]);
+
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+        assert_ok!(OnActivityHandler::<T>::on_trade(SOURCE, HDX, DOT, 1_000_000_000_000, 2_000_000_000_000, 1_000_000_000_000_000, 2_000_000_000_000_000));
+        EmaOracle::<T>::on_finalize(block_num);
+        assert!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).is_some());
+
+        let inactive_block = block_num + T::PruneAfter::get() + 2u32.into();
+        frame_system::Pallet::<T>::set_block_number(inactive_block);
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), SOURCE, (HDX, DOT))
+    verify {
+        assert!(Oracles::<T>::get((SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).is_none());
+    }
+
+    add_feeder {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        fill_feeders::<T>(T::MaxFeeders::get() - 1);
+        let feeder: T::AccountId = whitelisted_caller();
+    }: _<T::RuntimeOrigin>(origin, feeder.clone())
+    verify {
+        assert!(Feeders::<T>::get().contains(&feeder));
+    }
+
+    remove_feeder {
+        let origin = T::AuthorityOrigin::try_successful_origin().unwrap();
+        fill_feeders::<T>(T::MaxFeeders::get() - 1);
+        let feeder: T::AccountId = whitelisted_caller();
+        Feeders::<T>::mutate(|feeders| feeders.try_insert(feeder.clone()).unwrap());
+    }: _<T::RuntimeOrigin>(origin, feeder.clone())
+    verify {
+        assert!(!Feeders::<T>::get().contains(&feeder));
+    }
+
+    submit_price {
+        let source = T::FeedSource::get();
+        WhitelistedAssets::<T>::mutate(|list| list.try_insert((source, ordered_pair(HDX, DOT))).unwrap());
+
+        let block_num: T::BlockNumber = 5u32.into();
+        frame_system::Pallet::<T>::set_block_number(block_num);
+        EmaOracle::<T>::on_initialize(block_num);
+
+        // all other feeders already submitted in this block
+        let price = Price::new(1_000_000_000_000, 2_000_000_000_000);
+        let liquidity = Liquidity::new(1_000_000_000_000_000, 2_000_000_000_000_000);
+        for feeder in fill_feeders::<T>(T::MaxFeeders::get() - 1) {
+            assert_ok!(EmaOracle::<T>::submit_price(RawOrigin::Signed(feeder).into(), HDX, DOT, price, liquidity));
+        }
+        // prices were submitted for the maximum number of other pairs in this block
+        let submissions = FeedSubmissions::<T>::get().get(&ordered_pair(HDX, DOT)).cloned().unwrap_or_default();
+        FeedSubmissions::<T>::mutate(|all| {
+            for i in 1..T::MaxFeedPairs::get() {
+                all.try_insert((DOT + i, DOT + i + 1), submissions.clone()).unwrap();
+            }
+        });
+        let caller: T::AccountId = whitelisted_caller();
+        Feeders::<T>::mutate(|feeders| feeders.try_insert(caller.clone()).unwrap());
+    }: _(RawOrigin::Signed(caller.clone()), HDX, DOT, price, liquidity)
+    verify {
+        assert_eq!(LastFeedSubmission::<T>::get(&caller, ordered_pair(HDX, DOT)), Some(block_num));
+        assert!(Accumulator::<T>::get().contains_key(&(source, ordered_pair(HDX, DOT))));
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
 }
+
+/// Return a price path from `HDX` to `DOT` with the maximum number of hops.
+fn price_path_of_max_length() -> PricePath {
+    let hops: Vec<(Source, AssetId)> = (1..MAX_PRICE_PATH_LENGTH)
+        .map(|i| (SOURCE, HDX + i))
+        .chain(sp_std::iter::once((SOURCE, DOT)))
+        .collect();
+    PricePath::truncate_from(hops)
+}
+
+/// Approve `count` feeder accounts and return them.
+fn fill_feeders<T: Config>(count: u32) -> Vec<T::AccountId> {
+    let feeders: Vec<T::AccountId> = (0..count).map(|i| account("feeder", i, 0)).collect();
+    Feeders::<T>::mutate(|list| {
+        for feeder in feeders.iter() {
+            list.try_insert(feeder.clone())
+                .expect("benchmarked feeders should fit into the feeder list");
+        }
+    });
+    feeders
+}
+
+/// Add the oracles used in the benchmarks to `WhitelistedAssets` unless they are contained in
+/// `OracleWhitelist`. `WhitelistedAssets` holds at most `MaxWhitelistedEntries` oracles.
+fn whitelist_oracles<T: Config>(pairs: impl IntoIterator<Item = (AssetId, AssetId)>) {
+    for (asset_a, asset_b) in pairs {
+        let assets = ordered_pair(asset_a, asset_b);
+        if EmaOracle::<T>::is_whitelisted(SOURCE, assets) {
+            continue;
+        }
+        WhitelistedAssets::<T>::mutate(|list| {
+            list.try_insert((SOURCE, assets))
+                .expect("benchmarked oracles should fit into the whitelist");
+        });
+    }
+}
@@ -29,7 +29,7 @@
 //! pallets (e.g. xyk pallet).
 //!
 //! It is meant to be used by other pallets via the `AggregatedOracle` and `AggregatedPriceOracle`
-//! traits.
+//! traits. Clients can query the oracles via the `EmaOracleApi` runtime API.
 //!
 //! When integrating with this pallet take care to use the `on_trade_weight`,
 //! `on_liquidity_changed_weight` and `get_entry_weight` into account when calculating the weight
@@ -43,6 +43,11 @@
 //!   Implicitly determines the oracle period.
 //! - *Period*: The window over which an oracle is averaged. Certain smoothing factors correspond to
 //!   an oracle period. E.g. ten minutes oracle period ≈ 0.0198
+//! - *Period length*: The number of blocks in a period, configured via `PeriodLengths`. The
+//!   smoothing factor is derived from it as `2 / (length + 1)`. `Custom` periods allow for any
+//!   length.
+//! - *Volatility*: The exponentially smoothed variance of the relative price changes per block.
+//!   Exposed via the `VolatilityOracle` trait.
 //! - *Source*: The source of the data. E.g. xyk pallet.
 //!
 //! ### Implementation
@@ -53,9 +58,49 @@
 //! this aggregation is NOT based on EMA, yet, it just sums the volume and replaces price and
 //! liquidity with the most recent value.
 //!
+//! Only data of whitelisted oracles is recorded. An oracle is whitelisted if it is contained in
+//! `OracleWhitelist` or was added to `WhitelistedAssets` by `AuthorityOrigin` via `add_oracle`.
+//! `remove_oracle` removes an oracle from `WhitelistedAssets` and deletes its entries. At most
+//! `MaxWhitelistedEntries` oracles can be added.
+//!
+//! Prices of asset pairs without a direct oracle can be derived via price paths set by
+//! `AuthorityOrigin` via `set_price_path`. `PathPriceOracle` composes the prices of the oracles
+//! along the path and reports the oldest `oracle_age` on the path. It fails if any oracle on the
+//! path is missing or was not updated within `MaxPricePathAge` blocks.
+//!
+//! Oracles of pairs that were not updated for more than `PruneAfter` blocks are inactive. Their
+//! entries are removed by an `on_idle` sweep or by anyone via `prune`, which is signalled to
+//! `OnOracleRemoved`. The oracle is initialised again by the next trade or liquidity change.
+//!
+//! Prices of assets that are not traded on-chain can be submitted by feeders approved by
+//! `AuthorityOrigin` via `add_feeder`. Submissions via `submit_price` are recorded under
+//! `FeedSource` like any other incoming data, using the median of the prices and liquidity
+//! submitted by the feeders for the pair in the current block. A feeder can submit for the same
+//! pair at most once every `FeedRateLimit` blocks, but may replace its submission within the same
+//! block. Prices can be submitted for at most `MaxFeedPairs` pairs per block. Expired rate limits
+//! are removed by the `on_idle` sweep.
+//!
+//! If the accumulator is full, entries of oracles in `WhitelistedAssets` and entries with higher
+//! volume relative to the liquidity of the pair evict entries with lower priority. Evictions are
+//! signalled by the `AccumulatorEntryEvicted` event. If the incoming entry does not have a higher
+//! priority than any of the accumulated entries, it is rejected with `TooManyUniqueEntries`.
+//!
+//! `MultiSourcePriceOracle` combines the prices of an asset pair from several sources into a
+//! single price, either as liquidity-weighted mean or as median. It is requested via a synthetic
+//! source, so it can replace the pallet in existing consumers.
+//!
+//! `OraclePriceProvider` implements `NativePriceOracle` and `SpotPriceProvider` via the oracle
+//! prices of a configured period, falling back through a list of sources and ignoring oracles
+//! that were not updated recently. It can replace spot prices for fee payment, which are
+//! manipulable within a block.
+//!
+//! Sources can limit the relative price change per block via `MaxPriceChange`. Before the
+//! accumulated entries are integrated, their prices are clamped toward the price of the
+//! `LastBlock` oracle and a `PriceClamped` event is emitted.
+//!
 //! At the end of the block, all the entries are merged into permanent storage via the exponential
 //! moving average logic defined in the math package this pallet depens on. There is one oracle
-//! entry for each combination of `(source, asset_pair, period)` in storage.
+//! entry for each combination of `(source, asset_pair, period_length)` in storage.
 //!
 //! Oracle values are accessed lazily. This means that the storage does not contain the most recent
 //! value, but the value calculated the last time it was updated via trade or liquidity change. On a
@@ -67,14 +112,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::pallet_prelude::*;
+use frame_support::sp_runtime::traits::Convert;
 use frame_support::sp_runtime::traits::{BlockNumberProvider, One, Zero};
+use frame_support::traits::Contains;
+use frame_support::{BoundedBTreeMap, BoundedBTreeSet};
 use hydradx_traits::{
-    AggregatedEntry, AggregatedOracle, AggregatedPriceOracle, Liquidity, OnCreatePoolHandler,
-    OnLiquidityChangedHandler, OnTradeHandler,
+    pools::SpotPriceProvider,
+    AggregatedEntry, AggregatedOracle, AggregatedPriceOracle, Liquidity, NativePriceOracle, OnCreatePoolHandler,
+    OnLiquidityChangedHandler, OnOracleRemovedHandler, OnTradeHandler,
     OraclePeriod::{self, *},
-    Volume,
+    VolatilityOracle, Volume,
 };
-use sp_arithmetic::traits::Saturating;
+use sp_arithmetic::{traits::Saturating, FixedPointNumber, FixedU128, Permill};
+use sp_core::U256;
+use sp_std::cmp::Ordering;
 use sp_std::marker::PhantomData;
 use sp_std::prelude::*;
 
@@ -90,12 +141,25 @@ use weights::WeightInfo;
 
 mod benchmarking;
 
+pub mod migration;
+
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
 /// Maximum number of unique oracle entries expected in one block. Empirically determined by running
 /// `trades_estimation.py` and rounding up from 212 to 300. Not necessarily representative for all
 /// chains, configure `MaxUniqueEntries` according to your chain.
 pub const MAX_UNIQUE_ENTRIES: u32 = 300;
+/// The maximum number of custom periods that could have corresponding oracles.
+pub const MAX_CUSTOM_PERIODS: u32 = 5;
 /// The maximum number of periods that could have corresponding oracles.
-pub const MAX_PERIODS: u32 = OraclePeriod::all_periods().len() as u32;
+pub const MAX_PERIODS: u32 = OraclePeriod::all_periods().len() as u32 + MAX_CUSTOM_PERIODS;
+/// The maximum number of hops of a price path.
+pub const MAX_PRICE_PATH_LENGTH: u32 = 4;
+
+/// Path of oracle hops used to derive the price of an asset pair. Each hop consists of the data
+/// source of the oracle and the asset the hop leads to.
+pub type PricePath = BoundedVec<(Source, AssetId), ConstU32<MAX_PRICE_PATH_LENGTH>>;
 
 const LOG_TARGET: &str = "runtime::ema-oracle";
 
@@ -106,10 +170,12 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::BoundedBTreeMap;
-    use frame_system::pallet_prelude::BlockNumberFor;
+    use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -125,19 +191,124 @@ pub mod pallet {
         /// The periods supported by the pallet. I.e. which oracles to track.
         type SupportedPeriods: Get<BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>>>;
 
+        /// Conversion of oracle periods into their length in blocks, which determines the smoothing
+        /// factor of the oracles. `LastBlock` is always one block long.
+        type PeriodLengths: Convert<OraclePeriod, PeriodLength>;
+
         /// Maximum number of unique oracle entries expected in one block.
         #[pallet::constant]
         type MaxUniqueEntries: Get<u32>;
+
+        /// Maximum number of oracles in `WhitelistedAssets`.
+        #[pallet::constant]
+        type MaxWhitelistedEntries: Get<u32>;
+
+        /// Oracles which are always tracked, in addition to the ones in `WhitelistedAssets`.
+        type OracleWhitelist: Contains<(Source, AssetId, AssetId)>;
+
+        /// Origin that can add and remove oracles from `WhitelistedAssets` and manage price paths.
+        type AuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of blocks since the last update of an oracle for it to be used as a hop
+        /// of a price path.
+        #[pallet::constant]
+        type MaxPricePathAge: Get<Self::BlockNumber>;
+
+        /// Maximum relative change of the price per block for oracles of a source. Incoming prices
+        /// are clamped toward the price of the `LastBlock` oracle. `None` disables the clamp.
+        type MaxPriceChange: Convert<Source, Option<Permill>>;
+
+        /// Number of blocks without update after which the oracles of a pair are inactive and can
+        /// be pruned.
+        #[pallet::constant]
+        type PruneAfter: Get<Self::BlockNumber>;
+
+        /// Handler called when the oracles of a pair are removed.
+        type OnOracleRemoved: OnOracleRemovedHandler<AssetId>;
+
+        /// Source under which the prices submitted by feeders are recorded.
+        #[pallet::constant]
+        type FeedSource: Get<Source>;
+
+        /// Maximum number of feeders.
+        #[pallet::constant]
+        type MaxFeeders: Get<u32>;
+
+        /// Minimum number of blocks between two submissions of a feeder for the same asset pair.
+        /// Repeated submissions within the same block are not limited.
+        #[pallet::constant]
+        type FeedRateLimit: Get<Self::BlockNumber>;
+
+        /// Maximum number of asset pairs feeders can submit prices for in one block.
+        #[pallet::constant]
+        type MaxFeedPairs: Get<u32>;
     }
 
     #[pallet::error]
     pub enum Error<T> {
         TooManyUniqueEntries,
         OnTradeValueZero,
+        /// The oracle is not in `WhitelistedAssets` or has no entries.
+        OracleNotFound,
+        /// `WhitelistedAssets` already contains `MaxWhitelistedEntries` oracles.
+        TooManyWhitelistedOracles,
+        /// The oracle was updated within the last `PruneAfter` blocks.
+        OracleNotInactive,
+        /// The price path does not lead from the first to the second asset.
+        InvalidPricePath,
+        /// No price path is configured for the asset pair.
+        PricePathNotFound,
+        /// Maximum number of feeders has been reached.
+        TooManyFeeders,
+        /// The account is not a feeder.
+        FeederNotFound,
+        /// Feeders already submitted prices for `MaxFeedPairs` asset pairs in this block.
+        TooManyFeedPairs,
+        /// The feeder submitted a price for the asset pair in one of the previous `FeedRateLimit`
+        /// blocks.
+        FeedRateLimited,
+        /// The submitted price or liquidity is zero.
+        InvalidFeedPrice,
     }
 
     #[pallet::event]
-    pub enum Event<T: Config> {}
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Oracle was added to the whitelist.
+        AddedToWhitelist { source: Source, assets: (AssetId, AssetId) },
+        /// Oracle was removed from the whitelist and its entries were deleted.
+        RemovedFromWhitelist { source: Source, assets: (AssetId, AssetId) },
+        /// Data of the oracle accumulated in the current block was evicted from the full
+        /// accumulator in favour of an entry with higher priority.
+        AccumulatorEntryEvicted { source: Source, assets: (AssetId, AssetId) },
+        /// Price path of the asset pair was set.
+        PricePathSet {
+            assets: (AssetId, AssetId),
+            path: PricePath,
+        },
+        /// Price path of the asset pair was removed.
+        PricePathRemoved { assets: (AssetId, AssetId) },
+        /// Incoming price of the oracle exceeded the maximum price change and was clamped.
+        PriceClamped {
+            source: Source,
+            assets: (AssetId, AssetId),
+            incoming_price: Price,
+            clamped_price: Price,
+        },
+        /// Entries of the inactive oracle were removed.
+        OraclePruned { source: Source, assets: (AssetId, AssetId) },
+        /// Account was approved as feeder.
+        FeederAdded { feeder: T::AccountId },
+        /// Account is no longer a feeder.
+        FeederRemoved { feeder: T::AccountId },
+        /// Feeder submitted the price and liquidity of the asset pair.
+        PriceSubmitted {
+            feeder: T::AccountId,
+            assets: (AssetId, AssetId),
+            price: Price,
+            liquidity: Liquidity<Balance>,
+        },
+    }
 
     /// Accumulator for oracle data in current block that will be recorded at the end of the block.
     #[pallet::storage]
@@ -148,7 +319,8 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    /// Orace storage keyed by data source, involved asset ids and the period length of the oracle.
+    /// Orace storage keyed by data source, involved asset ids and the period length of the oracle
+    /// in blocks. Periods of the same length share the oracle.
     ///
     /// Stores the data entry as well as the block number when the oracle was first initialized.
     #[pallet::storage]
@@ -158,12 +330,69 @@ pub mod pallet {
         (
             NMapKey<Twox64Concat, Source>,
             NMapKey<Twox64Concat, (AssetId, AssetId)>,
-            NMapKey<Twox64Concat, OraclePeriod>,
+            NMapKey<Twox64Concat, PeriodLength>,
         ),
         (OracleEntry<T::BlockNumber>, T::BlockNumber),
         OptionQuery,
     >;
 
+    /// Oracles tracked in addition to `OracleWhitelist`, keyed by data source and ordered asset pair.
+    #[pallet::storage]
+    #[pallet::getter(fn whitelisted_assets)]
+    pub type WhitelistedAssets<T: Config> =
+        StorageValue<_, BoundedBTreeSet<(Source, (AssetId, AssetId)), T::MaxWhitelistedEntries>, ValueQuery>;
+
+    /// Paths used to derive the price of asset pairs without a direct oracle, keyed by ordered
+    /// asset pair. The path leads from the first to the second asset of the pair.
+    #[pallet::storage]
+    #[pallet::getter(fn price_path)]
+    pub type PricePaths<T: Config> = StorageMap<_, Twox64Concat, (AssetId, AssetId), PricePath, OptionQuery>;
+
+    /// Last oracle checked for inactivity by the `on_idle` sweep, which continues after it. `None`
+    /// if the sweep starts from the beginning of `Oracles`.
+    #[pallet::storage]
+    #[pallet::getter(fn prune_cursor)]
+    pub type PruneCursor<T: Config> = StorageValue<_, (Source, (AssetId, AssetId)), OptionQuery>;
+
+    /// Accounts approved to submit prices under `FeedSource`.
+    #[pallet::storage]
+    #[pallet::getter(fn feeders)]
+    pub type Feeders<T: Config> = StorageValue<_, BoundedBTreeSet<T::AccountId, T::MaxFeeders>, ValueQuery>;
+
+    /// Prices and liquidity submitted by feeders in the current block, keyed by ordered asset
+    /// pair. Cleared in `on_finalize`.
+    #[pallet::storage]
+    #[pallet::getter(fn feed_submissions)]
+    pub type FeedSubmissions<T: Config> = StorageValue<
+        _,
+        BoundedBTreeMap<
+            (AssetId, AssetId),
+            BoundedVec<(T::AccountId, Price, Liquidity<Balance>), T::MaxFeeders>,
+            T::MaxFeedPairs,
+        >,
+        ValueQuery,
+    >;
+
+    /// Block of the last submission of a feeder for an ordered asset pair. Entries older than
+    /// `FeedRateLimit` blocks no longer limit the feeder and are removed by the `on_idle` sweep.
+    #[pallet::storage]
+    #[pallet::getter(fn last_feed_submission)]
+    pub type LastFeedSubmission<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        (AssetId, AssetId),
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// Last entry of `LastFeedSubmission` checked for expiry by the `on_idle` sweep, which
+    /// continues after it. `None` if the sweep starts from the beginning of `LastFeedSubmission`.
+    #[pallet::storage]
+    #[pallet::getter(fn feed_prune_cursor)]
+    pub type FeedPruneCursor<T: Config> = StorageValue<_, (T::AccountId, (AssetId, AssetId)), OptionQuery>;
+
     #[pallet::genesis_config]
     #[derive(Default)]
     pub struct GenesisConfig {
@@ -179,6 +408,7 @@ pub mod pallet {
                         price,
                         volume: Volume::default(),
                         liquidity,
+                        volatility: Volatility::zero(),
                         timestamp: T::BlockNumber::zero(),
                     };
                     if ordered_pair(asset_a, asset_b) == (asset_a, asset_b) {
@@ -188,9 +418,14 @@ pub mod pallet {
                     }
                 };
 
-                for period in T::SupportedPeriods::get() {
+                for period in Pallet::<T>::supported_period_lengths() {
                     Pallet::<T>::update_oracle(source, ordered_pair(asset_a, asset_b), period, entry.clone());
                 }
+
+                WhitelistedAssets::<T>::mutate(|list| {
+                    list.try_insert((source, ordered_pair(asset_a, asset_b)))
+                        .expect("Genesis oracles should fit into the whitelist.");
+                });
             }
         }
     }
@@ -198,12 +433,22 @@ pub mod pallet {
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-            T::WeightInfo::on_finalize_no_entry()
+            // clearing the feed submissions in `on_finalize`
+            T::WeightInfo::on_finalize_no_entry().saturating_add(T::DbWeight::get().writes(1))
         }
 
         fn on_finalize(_n: BlockNumberFor<T>) {
             // update oracles based on data accumulated during the block
             Self::update_oracles_from_accumulator();
+            // the submissions of the block are part of the accumulated data
+            FeedSubmissions::<T>::kill();
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used_weight = Self::prune_inactive_oracles(remaining_weight);
+            used_weight.saturating_add(Self::prune_expired_feed_submissions(
+                remaining_weight.saturating_sub(used_weight),
+            ))
         }
 
         fn integrity_test() {
@@ -215,30 +460,393 @@ pub mod pallet {
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Add an oracle to the whitelist.
+        ///
+        /// Data of whitelisted oracles is recorded even if they are not in `OracleWhitelist`.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `source`: data source of the oracle
+        /// - `assets`: asset pair of the oracle, in any order
+        ///
+        /// Emits `AddedToWhitelist` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_oracle())]
+        pub fn add_oracle(origin: OriginFor<T>, source: Source, assets: (AssetId, AssetId)) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            let assets = ordered_pair(assets.0, assets.1);
+            WhitelistedAssets::<T>::try_mutate(|list| -> DispatchResult {
+                list.try_insert((source, assets))
+                    .map_err(|_| Error::<T>::TooManyWhitelistedOracles)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AddedToWhitelist { source, assets });
+
+            Ok(())
+        }
+
+        /// Remove an oracle from the whitelist and delete its entries for all supported periods.
+        ///
+        /// Data of the oracle is recorded again if it is in `OracleWhitelist`.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `source`: data source of the oracle
+        /// - `assets`: asset pair of the oracle, in any order
+        ///
+        /// Emits `RemovedFromWhitelist` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_oracle(
+            Pallet::<T>::supported_period_lengths().len() as u32
+        ))]
+        pub fn remove_oracle(origin: OriginFor<T>, source: Source, assets: (AssetId, AssetId)) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            let assets = ordered_pair(assets.0, assets.1);
+            WhitelistedAssets::<T>::try_mutate(|list| -> DispatchResult {
+                ensure!(list.remove(&(source, assets)), Error::<T>::OracleNotFound);
+                Ok(())
+            })?;
+
+            Accumulator::<T>::mutate(|accumulator| {
+                accumulator.remove(&(source, assets));
+            });
+            Self::remove_oracle_entries(source, assets);
+
+            Self::deposit_event(Event::RemovedFromWhitelist { source, assets });
+
+            Ok(())
+        }
+
+        /// Set the path used to derive the price of an asset pair. Replaces any existing path.
+        ///
+        /// Each hop of the path consists of the data source of the oracle and the asset the hop
+        /// leads to, starting from `asset_a`. The last hop has to lead to `asset_b`.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `asset_a`: asset the path starts from
+        /// - `asset_b`: asset the path leads to
+        /// - `path`: hops from `asset_a` to `asset_b`
+        ///
+        /// Emits `PricePathSet` event when successful.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_price_path())]
+        pub fn set_price_path(
+            origin: OriginFor<T>,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            path: PricePath,
+        ) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                Self::is_valid_price_path(asset_a, asset_b, &path),
+                Error::<T>::InvalidPricePath
+            );
+
+            let assets = ordered_pair(asset_a, asset_b);
+            let path = if assets == (asset_a, asset_b) {
+                path
+            } else {
+                reversed_price_path(asset_a, &path)
+            };
+            PricePaths::<T>::insert(assets, path.clone());
+
+            Self::deposit_event(Event::PricePathSet { assets, path });
+
+            Ok(())
+        }
+
+        /// Remove the price path of an asset pair.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `assets`: asset pair of the path, in any order
+        ///
+        /// Emits `PricePathRemoved` event when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_price_path())]
+        pub fn remove_price_path(origin: OriginFor<T>, assets: (AssetId, AssetId)) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            let assets = ordered_pair(assets.0, assets.1);
+            ensure!(PricePaths::<T>::contains_key(assets), Error::<T>::PricePathNotFound);
+            PricePaths::<T>::remove(assets);
+
+            Self::deposit_event(Event::PricePathRemoved { assets });
+
+            Ok(())
+        }
+
+        /// Remove the entries of an inactive oracle for all supported periods.
+        ///
+        /// An oracle is inactive if it was not updated for more than `PruneAfter` blocks. It is
+        /// initialised again by the next trade or liquidity change of the pair.
+        ///
+        /// Can be called by anyone.
+        ///
+        /// Parameters:
+        /// - `origin`: signed origin
+        /// - `source`: data source of the oracle
+        /// - `assets`: asset pair of the oracle, in any order
+        ///
+        /// Emits `OraclePruned` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::prune(
+            Pallet::<T>::supported_period_lengths().len() as u32
+        ))]
+        pub fn prune(origin: OriginFor<T>, source: Source, assets: (AssetId, AssetId)) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let assets = ordered_pair(assets.0, assets.1);
+            let (last_block, _) =
+                Self::oracle((source, assets, LAST_BLOCK_PERIOD_LENGTH)).ok_or(Error::<T>::OracleNotFound)?;
+            ensure!(
+                Self::is_inactive(source, assets, &last_block, &Self::accumulator()),
+                Error::<T>::OracleNotInactive
+            );
+
+            Self::prune_oracle(source, assets);
+
+            Ok(())
+        }
+
+        /// Approve an account to submit prices under `FeedSource`.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `feeder`: account to approve
+        ///
+        /// Emits `FeederAdded` event when successful.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_feeder())]
+        pub fn add_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                feeders
+                    .try_insert(feeder.clone())
+                    .map_err(|_| Error::<T>::TooManyFeeders)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::FeederAdded { feeder });
+
+            Ok(())
+        }
+
+        /// Revoke the approval of a feeder. Prices it submitted in the current block are kept.
+        ///
+        /// Can only be called by `AuthorityOrigin`.
+        ///
+        /// Parameters:
+        /// - `origin`: `AuthorityOrigin`
+        /// - `feeder`: account to remove
+        ///
+        /// Emits `FeederRemoved` event when successful.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_feeder())]
+        pub fn remove_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                ensure!(feeders.remove(&feeder), Error::<T>::FeederNotFound);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::FeederRemoved { feeder });
+
+            Ok(())
+        }
+
+        /// Submit the price and liquidity of an asset pair as feeder.
+        ///
+        /// The oracle of the pair is updated with the median of the prices and liquidity
+        /// submitted by all feeders in the current block. A repeated submission of a feeder in
+        /// the same block replaces its previous one and is not subject to `FeedRateLimit`.
+        ///
+        /// The oracle of the pair under `FeedSource` has to be whitelisted.
+        ///
+        /// Parameters:
+        /// - `origin`: signed origin of a feeder
+        /// - `asset_a`: first asset of the pair
+        /// - `asset_b`: second asset of the pair
+        /// - `price`: amount of `asset_a` per unit of `asset_b`
+        /// - `liquidity`: liquidity of `asset_a` and `asset_b`
+        ///
+        /// Emits `PriceSubmitted` event when successful.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_price()
+            .saturating_add(fractional_on_finalize_weight::<T>(T::MaxUniqueEntries::get())))]
+        pub fn submit_price(
+            origin: OriginFor<T>,
+            asset_a: AssetId,
+            asset_b: AssetId,
+            price: Price,
+            liquidity: Liquidity<Balance>,
+        ) -> DispatchResult {
+            let feeder = ensure_signed(origin)?;
+            ensure!(Self::feeders().contains(&feeder), Error::<T>::FeederNotFound);
+
+            let (n, d): (Balance, Balance) = price.into();
+            ensure!(
+                !n.is_zero() && !d.is_zero() && !liquidity.a.is_zero() && !liquidity.b.is_zero(),
+                Error::<T>::InvalidFeedPrice
+            );
+
+            let source = T::FeedSource::get();
+            let assets = ordered_pair(asset_a, asset_b);
+            ensure!(Self::is_whitelisted(source, assets), Error::<T>::OracleNotFound);
+
+            let now = T::BlockNumberProvider::current_block_number();
+            if let Some(last) = Self::last_feed_submission(&feeder, assets) {
+                ensure!(
+                    last == now || now.saturating_sub(last) >= T::FeedRateLimit::get(),
+                    Error::<T>::FeedRateLimited
+                );
+            }
+
+            let entry = {
+                let e = OracleEntry {
+                    price,
+                    volume: Volume::default(),
+                    liquidity,
+                    volatility: Volatility::zero(),
+                    timestamp: now,
+                };
+                if assets == (asset_a, asset_b) {
+                    e
+                } else {
+                    e.inverted()
+                }
+            };
+
+            let mut feed_submissions = Self::feed_submissions();
+            let mut submissions = feed_submissions.get(&assets).cloned().unwrap_or_default();
+            submissions.retain(|(account, _, _)| *account != feeder);
+            submissions
+                .try_push((feeder.clone(), entry.price, entry.liquidity))
+                .map_err(|_| Error::<T>::TooManyFeeders)?;
+
+            let median_entry = OracleEntry {
+                price: median(submissions.iter().map(|(_, price, _)| *price).collect())
+                    .ok_or(Error::<T>::InvalidFeedPrice)?,
+                liquidity: Liquidity::new(
+                    median_balance(submissions.iter().map(|(_, _, liquidity)| liquidity.a).collect()),
+                    median_balance(submissions.iter().map(|(_, _, liquidity)| liquidity.b).collect()),
+                ),
+                ..entry
+            };
+            feed_submissions
+                .try_insert(assets, submissions)
+                .map_err(|_| Error::<T>::TooManyFeedPairs)?;
+            Self::on_entry(source, assets, median_entry).map_err(|_| Error::<T>::TooManyUniqueEntries)?;
+
+            FeedSubmissions::<T>::put(feed_submissions);
+            LastFeedSubmission::<T>::insert(&feeder, assets, now);
+
+            Self::deposit_event(Event::PriceSubmitted {
+                feeder,
+                assets,
+                price: entry.price,
+                liquidity: entry.liquidity,
+            });
+
+            Ok(())
+        }
+    }
 }
 
 impl<T: Config> Pallet<T> {
     /// Insert or update data in the accumulator from received entry. Aggregates volume and
     /// takes the most recent data for the rest.
+    ///
+    /// Entries of oracles that are neither in `OracleWhitelist` nor in `WhitelistedAssets` are
+    /// ignored.
+    ///
+    /// If the accumulator is full, the entry with the lowest priority is evicted in favour of the
+    /// received entry if the received entry has a higher priority. Otherwise an error is returned.
+    /// See [`Self::entry_priority`].
     pub(crate) fn on_entry(
         src: Source,
         assets: (AssetId, AssetId),
         oracle_entry: OracleEntry<T::BlockNumber>,
     ) -> Result<(), ()> {
+        if !Self::is_whitelisted(src, assets) {
+            return Ok(());
+        }
+
         Accumulator::<T>::mutate(|accumulator| {
             if let Some(entry) = accumulator.get_mut(&(src, assets)) {
                 entry.accumulate_volume_and_update_from(&oracle_entry);
-                Ok(())
-            } else {
-                accumulator
+                return Ok(());
+            }
+            if (accumulator.len() as u32) < T::MaxUniqueEntries::get() {
+                return accumulator
                     .try_insert((src, assets), oracle_entry)
                     .map(|_| ())
-                    .map_err(|_| ())
+                    .map_err(|_| ());
             }
+
+            let whitelist = Self::whitelisted_assets();
+            let priority = Self::entry_priority(&whitelist, src, assets, &oracle_entry);
+            let (lowest_priority, evicted) = accumulator
+                .iter()
+                .map(|(&(key_src, key_assets), entry)| {
+                    (
+                        Self::entry_priority(&whitelist, key_src, key_assets, entry),
+                        (key_src, key_assets),
+                    )
+                })
+                .min_by_key(|(entry_priority, _)| *entry_priority)
+                .ok_or(())?;
+            if lowest_priority >= priority {
+                return Err(());
+            }
+
+            accumulator.remove(&evicted);
+            accumulator.try_insert((src, assets), oracle_entry).map_err(|_| ())?;
+
+            Self::deposit_event(Event::AccumulatorEntryEvicted {
+                source: evicted.0,
+                assets: evicted.1,
+            });
+            Ok(())
         })
     }
 
+    /// Return the priority of an accumulator entry. Entries with higher priority can evict entries
+    /// with lower priority from a full accumulator.
+    ///
+    /// Oracles in `WhitelistedAssets` have priority over the rest. Entries in the same group are
+    /// ordered by the volume of asset `a` traded in the current block relative to the liquidity of
+    /// asset `a`. The ratio does not depend on the decimals of the assets, so entries of different
+    /// pairs are comparable. Entries of liquidity changes without trades have the lowest priority.
+    fn entry_priority(
+        whitelist: &BoundedBTreeSet<(Source, (AssetId, AssetId)), T::MaxWhitelistedEntries>,
+        src: Source,
+        assets: (AssetId, AssetId),
+        entry: &OracleEntry<T::BlockNumber>,
+    ) -> (bool, FixedU128) {
+        let volume = entry.volume.a_in.saturating_add(entry.volume.a_out);
+        let relative_volume = FixedU128::checked_from_rational(volume, entry.liquidity.a).unwrap_or_default();
+        (whitelist.contains(&(src, assets)), relative_volume)
+    }
+
     /// Insert or update data in the accumulator from received entry. Aggregates volume and
     /// takes the most recent data for the rest.
     pub(crate) fn on_trade(
@@ -265,13 +873,39 @@ impl<T: Config> Pallet<T> {
             .map_err(|_| (weight, Error::<T>::TooManyUniqueEntries.into()))
     }
 
+    /// Return the length of the given oracle period in blocks.
+    pub fn period_length(period: OraclePeriod) -> PeriodLength {
+        match period {
+            LastBlock => LAST_BLOCK_PERIOD_LENGTH,
+            period => T::PeriodLengths::convert(period).max(LAST_BLOCK_PERIOD_LENGTH),
+        }
+    }
+
+    /// Return the distinct lengths of the supported periods in ascending order. Always includes
+    /// the `LastBlock` period.
+    pub(crate) fn supported_period_lengths() -> Vec<PeriodLength> {
+        let mut lengths: Vec<PeriodLength> = T::SupportedPeriods::get()
+            .into_iter()
+            .map(Self::period_length)
+            .chain(sp_std::iter::once(LAST_BLOCK_PERIOD_LENGTH))
+            .collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        lengths
+    }
+
+    /// Return whether data of the oracle for the given `source` and ordered `assets` is recorded.
+    pub(crate) fn is_whitelisted(src: Source, assets: (AssetId, AssetId)) -> bool {
+        T::OracleWhitelist::contains(&(src, assets.0, assets.1)) || Self::whitelisted_assets().contains(&(src, assets))
+    }
+
     /// Return the current value of the `LastBlock` oracle for the given `source` and `assets`.
     pub(crate) fn last_block_oracle(
         source: Source,
         assets: (AssetId, AssetId),
         block: T::BlockNumber,
     ) -> Option<(OracleEntry<T::BlockNumber>, T::BlockNumber)> {
-        Self::oracle((source, assets, LastBlock)).map(|(mut last_block, init)| {
+        Self::oracle((source, assets, LAST_BLOCK_PERIOD_LENGTH)).map(|(mut last_block, init)| {
             // update the `LastBlock` oracle to the last block if it hasn't been updated for a while
             // price and liquidity stay constant, volume becomes zero
             if last_block.timestamp != block {
@@ -281,24 +915,229 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    /// Remove the entries of the oracle for all supported periods and notify `OnOracleRemoved`.
+    fn remove_oracle_entries(source: Source, assets: (AssetId, AssetId)) {
+        for period in Self::supported_period_lengths() {
+            Oracles::<T>::remove((source, assets, period));
+        }
+        T::OnOracleRemoved::on_oracle_removed(source, assets.0, assets.1);
+    }
+
+    /// Remove the entries of the inactive oracle and emit `OraclePruned`.
+    fn prune_oracle(source: Source, assets: (AssetId, AssetId)) {
+        Self::remove_oracle_entries(source, assets);
+        Self::deposit_event(Event::OraclePruned { source, assets });
+    }
+
+    /// Return whether the oracle was not updated for more than `PruneAfter` blocks, given its
+    /// `LastBlock` entry. Oracles with data in the accumulator are about to be updated and are
+    /// never inactive.
+    fn is_inactive(
+        source: Source,
+        assets: (AssetId, AssetId),
+        last_block: &OracleEntry<T::BlockNumber>,
+        accumulator: &BoundedBTreeMap<(Source, (AssetId, AssetId)), OracleEntry<T::BlockNumber>, T::MaxUniqueEntries>,
+    ) -> bool {
+        let parent = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
+        parent.saturating_sub(last_block.timestamp) > T::PruneAfter::get()
+            && !accumulator.contains_key(&(source, assets))
+    }
+
+    /// Prune inactive oracles, continuing after the oracle stored in `PruneCursor`. Stops as soon
+    /// as the next step would not fit into `remaining_weight`. Restarts from the beginning of
+    /// `Oracles` once all oracles were checked.
+    fn prune_inactive_oracles(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let prune_weight = T::WeightInfo::prune(Self::supported_period_lengths().len() as u32);
+
+        // cursor and accumulator read, cursor write
+        let mut used_weight = db_weight.reads_writes(2, 1);
+        if remaining_weight.any_lt(used_weight) {
+            return Weight::zero();
+        }
+
+        let initial_cursor = Self::prune_cursor();
+        let accumulator = Self::accumulator();
+        let iter = match initial_cursor {
+            Some((source, assets)) => {
+                Oracles::<T>::iter_from(Oracles::<T>::hashed_key_for((source, assets, LAST_BLOCK_PERIOD_LENGTH)))
+            }
+            None => Oracles::<T>::iter(),
+        };
+
+        let mut cursor = initial_cursor;
+        let mut inactive = Vec::new();
+        let mut exhausted = true;
+        for ((source, assets, period), (entry, _)) in iter {
+            let next_weight = used_weight.saturating_add(db_weight.reads(1));
+            if remaining_weight.any_lt(next_weight) {
+                exhausted = false;
+                break;
+            }
+            used_weight = next_weight;
+            // every oracle has exactly one `LastBlock` entry, which determines its activity
+            if period != LAST_BLOCK_PERIOD_LENGTH {
+                continue;
+            }
+            if Self::is_inactive(source, assets, &entry, &accumulator) {
+                let next_weight = used_weight.saturating_add(prune_weight);
+                if remaining_weight.any_lt(next_weight) {
+                    exhausted = false;
+                    break;
+                }
+                used_weight = next_weight;
+                inactive.push((source, assets));
+            }
+            cursor = Some((source, assets));
+        }
+
+        // entries are removed after iterating to not modify `Oracles` while iterating over it
+        for (source, assets) in inactive {
+            Self::prune_oracle(source, assets);
+        }
+
+        let cursor = if exhausted { None } else { cursor };
+        if cursor != initial_cursor {
+            PruneCursor::<T>::set(cursor);
+        }
+
+        used_weight
+    }
+
+    /// Remove entries of `LastFeedSubmission` that no longer limit the feeder, continuing after
+    /// the entry stored in `FeedPruneCursor`. Stops as soon as `remaining_weight` would be
+    /// exceeded.
+    ///
+    /// Returns the weight used.
+    fn prune_expired_feed_submissions(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+
+        // cursor read and write
+        let mut used_weight = db_weight.reads_writes(1, 1);
+        if remaining_weight.any_lt(used_weight) {
+            return Weight::zero();
+        }
+
+        let now = T::BlockNumberProvider::current_block_number();
+        let initial_cursor = Self::feed_prune_cursor();
+        let iter = match &initial_cursor {
+            Some((feeder, assets)) => {
+                LastFeedSubmission::<T>::iter_from(LastFeedSubmission::<T>::hashed_key_for(feeder, assets))
+            }
+            None => LastFeedSubmission::<T>::iter(),
+        };
+
+        let mut cursor = initial_cursor.clone();
+        let mut expired = Vec::new();
+        let mut exhausted = true;
+        for (feeder, assets, last) in iter {
+            let is_expired = now.saturating_sub(last) >= T::FeedRateLimit::get();
+            let next_weight = if is_expired {
+                used_weight.saturating_add(db_weight.reads_writes(1, 1))
+            } else {
+                used_weight.saturating_add(db_weight.reads(1))
+            };
+            if remaining_weight.any_lt(next_weight) {
+                exhausted = false;
+                break;
+            }
+            used_weight = next_weight;
+            if is_expired {
+                expired.push((feeder.clone(), assets));
+            }
+            cursor = Some((feeder, assets));
+        }
+
+        // entries are removed after iterating to not modify `LastFeedSubmission` while iterating over it
+        for (feeder, assets) in expired {
+            LastFeedSubmission::<T>::remove(&feeder, assets);
+        }
+
+        let cursor = if exhausted { None } else { cursor };
+        if cursor != initial_cursor {
+            FeedPruneCursor::<T>::set(cursor);
+        }
+
+        used_weight
+    }
+
     /// Update oracles based on data accumulated during the block.
     fn update_oracles_from_accumulator() {
-        for ((src, assets), oracle_entry) in Accumulator::<T>::take().into_iter() {
+        for ((src, assets), mut oracle_entry) in Accumulator::<T>::take().into_iter() {
+            Self::clamp_price_change(src, assets, &mut oracle_entry);
+            Self::set_incoming_volatility(src, assets, &mut oracle_entry);
             // First we update the non-immediate oracles with the value of the `LastBlock` oracle.
-            for period in T::SupportedPeriods::get().into_iter().filter(|p| *p != LastBlock) {
+            for period in Self::supported_period_lengths()
+                .into_iter()
+                .filter(|p| *p != LAST_BLOCK_PERIOD_LENGTH)
+            {
                 Self::update_oracle(src, assets, period, oracle_entry.clone());
             }
             // As we use (the old value of) the `LastBlock` entry to update the other oracles it
             // gets updated last.
-            Self::update_oracle(src, assets, LastBlock, oracle_entry.clone());
+            Self::update_oracle(src, assets, LAST_BLOCK_PERIOD_LENGTH, oracle_entry.clone());
         }
     }
 
-    /// Update the oracle of the given source, assets and period with `oracle_entry`.
+    /// Clamp the price of `incoming_entry` to the `MaxPriceChange` of the source relative to the
+    /// price of the `LastBlock` oracle. Emits `PriceClamped` if the price was clamped.
+    fn clamp_price_change(src: Source, assets: (AssetId, AssetId), incoming_entry: &mut OracleEntry<T::BlockNumber>) {
+        let max_change = match T::MaxPriceChange::convert(src) {
+            Some(max_change) => max_change,
+            None => return,
+        };
+        let previous_price = match Self::oracle((src, assets, LAST_BLOCK_PERIOD_LENGTH)) {
+            Some((entry, _)) if !entry.price.is_zero() => entry.price,
+            _ => return,
+        };
+
+        let (previous_n, previous_d) = previous_price.into();
+        let one = U256::from(Permill::one().deconstruct());
+        let change = U256::from(max_change.deconstruct());
+        let bound = |factor: U256| {
+            price_from_u256(
+                U256::from(previous_n).saturating_mul(factor),
+                U256::from(previous_d).saturating_mul(one),
+            )
+        };
+        let lower = bound(one.saturating_sub(change));
+        let upper = bound(one.saturating_add(change));
+
+        let incoming_price = incoming_entry.price;
+        let clamped_price = if compare_prices(incoming_price, upper) == Ordering::Greater {
+            upper
+        } else if compare_prices(incoming_price, lower) == Ordering::Less {
+            lower
+        } else {
+            return;
+        };
+        incoming_entry.price = clamped_price;
+
+        Self::deposit_event(Event::PriceClamped {
+            source: src,
+            assets,
+            incoming_price,
+            clamped_price,
+        });
+    }
+
+    /// Set the volatility of `incoming_entry` to the squared relative change of its price compared
+    /// to the price of the `LastBlock` oracle. Integrating it yields the volatility of the oracles.
+    fn set_incoming_volatility(
+        src: Source,
+        assets: (AssetId, AssetId),
+        incoming_entry: &mut OracleEntry<T::BlockNumber>,
+    ) {
+        if let Some((last_block, _)) = Self::oracle((src, assets, LAST_BLOCK_PERIOD_LENGTH)) {
+            incoming_entry.volatility = squared_relative_change(last_block.price, incoming_entry.price);
+        }
+    }
+
+    /// Update the oracle of the given source, assets and period length with `oracle_entry`.
     fn update_oracle(
         src: Source,
         assets: (AssetId, AssetId),
-        period: OraclePeriod,
+        period: PeriodLength,
         incoming_entry: OracleEntry<T::BlockNumber>,
     ) {
         Oracles::<T>::mutate((src, assets, period), |oracle| {
@@ -349,7 +1188,8 @@ impl<T: Config> Pallet<T> {
         // First get the `LastBlock` oracle to calculate the updated values for the others.
         let (last_block, last_block_init) = Self::last_block_oracle(src, assets, parent)?;
         // If it was requested return it directly.
-        if period == LastBlock {
+        let period = Self::period_length(period);
+        if period == LAST_BLOCK_PERIOD_LENGTH {
             return Some((last_block, last_block_init));
         }
 
@@ -363,6 +1203,370 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+// Queries used by the runtime API.
+impl<T: Config> Pallet<T> {
+    /// Return the updated entries of all supported periods for the given source and assets.
+    ///
+    /// Periods without an oracle entry are omitted. See `get_entry` for the details of the returned
+    /// entries.
+    pub fn get_entries(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+    ) -> Vec<(OraclePeriod, AggregatedEntry<Balance, T::BlockNumber, Price>)> {
+        T::SupportedPeriods::get()
+            .into_iter()
+            .filter_map(|period| {
+                Self::get_entry(asset_a, asset_b, period, source)
+                    .ok()
+                    .map(|entry| (period, entry))
+            })
+            .collect()
+    }
+
+    /// Return the ordered asset pairs tracked by oracles of the given source.
+    pub fn tracked_pairs(source: Source) -> Vec<(AssetId, AssetId)> {
+        // Every tracked pair has a `LastBlock` oracle, so we use it to list each pair once.
+        Oracles::<T>::iter_key_prefix((source,))
+            .filter_map(|(assets, period)| (period == LAST_BLOCK_PERIOD_LENGTH).then_some(assets))
+            .collect()
+    }
+}
+
+// Prices derived via price paths.
+impl<T: Config> Pallet<T> {
+    /// Return whether `path` leads from `asset_a` to `asset_b` without hops to the same asset.
+    fn is_valid_price_path(asset_a: AssetId, asset_b: AssetId, path: &PricePath) -> bool {
+        if asset_a == asset_b {
+            return false;
+        }
+        let mut current = asset_a;
+        for &(_, next) in path.iter() {
+            if next == current {
+                return false;
+            }
+            current = next;
+        }
+        !path.is_empty() && current == asset_b
+    }
+
+    /// Return the price of `asset_a` denominated in `asset_b`, derived by composing the prices of
+    /// the oracles along the configured price path.
+    ///
+    /// The returned age is the oldest `oracle_age` of the oracles on the path. Fails if there is no
+    /// path for the pair or if any of the oracles on the path is missing or has not been updated
+    /// within `MaxPricePathAge` blocks.
+    pub fn get_path_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+    ) -> Result<(Price, T::BlockNumber), OracleError> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        }
+        let assets = ordered_pair(asset_a, asset_b);
+        let path = Self::price_path(assets).ok_or(OracleError::NotPresent)?;
+
+        let mut price = Price::new(1, 1);
+        let mut oldest_age = T::BlockNumber::zero();
+        let mut current = assets.0;
+        for (source, next) in path {
+            let (hop_price, hop_age) = Self::get_fresh_price(source, current, next, period, T::MaxPricePathAge::get())?;
+            price = multiply_prices(price, hop_price);
+            oldest_age = oldest_age.max(hop_age);
+            current = next;
+        }
+
+        let price = if assets == (asset_a, asset_b) || price.is_zero() {
+            price
+        } else {
+            let (n, d) = price.into();
+            (d, n).into()
+        };
+        Ok((price, oldest_age))
+    }
+
+    /// Return the price of the oracle and its age, failing if the oracle was not updated within
+    /// `max_age` blocks.
+    pub(crate) fn get_fresh_price(
+        source: Source,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        period: OraclePeriod,
+        max_age: T::BlockNumber,
+    ) -> Result<(Price, T::BlockNumber), OracleError> {
+        let (last_block, _) = Self::oracle((source, ordered_pair(asset_in, asset_out), LAST_BLOCK_PERIOD_LENGTH))
+            .ok_or(OracleError::NotPresent)?;
+        let parent = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
+        if parent.saturating_sub(last_block.timestamp) > max_age {
+            return Err(OracleError::Stale);
+        }
+        Self::get_entry(asset_in, asset_out, period, source)
+            .map(|AggregatedEntry { price, oracle_age, .. }| (price, oracle_age))
+    }
+}
+
+// Prices aggregated over multiple sources.
+impl<T: Config> Pallet<T> {
+    /// Return the price of `asset_a` denominated in `asset_b` combined from the oracles of the
+    /// given `sources` according to `mode`.
+    ///
+    /// Sources without an oracle for the pair are skipped. The returned age is the age of the
+    /// youngest oracle taken into account. Fails if none of the sources has an oracle for the pair.
+    pub fn get_aggregated_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        sources: &[Source],
+        mode: AggregationMode,
+    ) -> Result<(Price, T::BlockNumber), OracleError> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        }
+        let entries: Vec<_> = sources
+            .iter()
+            .filter_map(|&source| Self::get_entry(asset_a, asset_b, period, source).ok())
+            .collect();
+        let youngest_age = entries
+            .iter()
+            .map(|entry| entry.oracle_age)
+            .min()
+            .ok_or(OracleError::NotPresent)?;
+
+        let price = match mode {
+            AggregationMode::LiquidityWeightedMean => liquidity_weighted_mean(&entries),
+            AggregationMode::Median => median(entries.into_iter().map(|entry| entry.price).collect()),
+        }
+        .ok_or(OracleError::NotPresent)?;
+        Ok((price, youngest_age))
+    }
+}
+
+/// Return the mean of the prices of `entries` weighted by the liquidity of the first asset.
+///
+/// Entries without liquidity are ignored. Returns `None` if no entry has liquidity.
+fn liquidity_weighted_mean<BlockNumber>(entries: &[AggregatedEntry<Balance, BlockNumber, Price>]) -> Option<Price> {
+    let total_weight = entries.iter().fold(U256::zero(), |acc, entry| {
+        acc.saturating_add(U256::from(entry.liquidity.a))
+    });
+    if total_weight.is_zero() {
+        return None;
+    }
+    let mean = entries.iter().fold(U256::zero(), |acc, entry| {
+        // Shares of the total weight are scaled by `2^64`, the prices by `2^64` as well.
+        let share = (U256::from(entry.liquidity.a) << 64) / total_weight;
+        acc.saturating_add(share.saturating_mul(price_to_fixed(entry.price)))
+    });
+    Some(price_from_u256(mean, U256::one() << 128))
+}
+
+/// Return the median of `prices`. The median of an even number of prices is the mean of the two
+/// middle prices. Returns `None` if there are no prices.
+fn median(mut prices: Vec<Price>) -> Option<Price> {
+    prices.sort_by(|a, b| compare_prices(*a, *b));
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        return prices.get(middle).copied();
+    }
+    let (lower, upper) = (*prices.get(middle.checked_sub(1)?)?, *prices.get(middle)?);
+    let sum = price_to_fixed(lower).saturating_add(price_to_fixed(upper));
+    Some(price_from_u256(sum, U256::one() << 65))
+}
+
+/// Return the median of the balances, averaging the two middle values for an even number of
+/// balances. Returns zero if `balances` is empty.
+fn median_balance(mut balances: Vec<Balance>) -> Balance {
+    balances.sort_unstable();
+    let middle = balances.len() / 2;
+    if balances.len() % 2 == 1 {
+        return balances[middle];
+    }
+    match (
+        middle.checked_sub(1).and_then(|i| balances.get(i)),
+        balances.get(middle),
+    ) {
+        (Some(&lower), Some(&upper)) => lower.saturating_add(upper.saturating_sub(lower) / 2),
+        _ => Balance::zero(),
+    }
+}
+
+/// Return the value of `price` as fixed point number with 64 fractional bits.
+fn price_to_fixed(price: Price) -> U256 {
+    let (n, d) = price.into();
+    (U256::from(n) << 64).checked_div(U256::from(d)).unwrap_or_default()
+}
+
+/// Return the path from `asset_b` back to `asset_a` given the `path` from `asset_a` to `asset_b`.
+fn reversed_price_path(asset_a: AssetId, path: &PricePath) -> PricePath {
+    let starts = sp_std::iter::once(asset_a).chain(path.iter().map(|&(_, asset)| asset));
+    let mut reversed: Vec<(Source, AssetId)> = path.iter().map(|&(source, _)| source).zip(starts).collect();
+    reversed.reverse();
+    // The reversed path has the same length as `path`, so it fits into the bounds.
+    PricePath::truncate_from(reversed)
+}
+
+/// Multiply two prices, rounding the result to fit into a `Price`.
+fn multiply_prices(a: Price, b: Price) -> Price {
+    let (a_n, a_d) = a.into();
+    let (b_n, b_d) = b.into();
+    // The products of two `u128` always fit into `U256`.
+    price_from_u256(
+        U256::from(a_n).saturating_mul(U256::from(b_n)),
+        U256::from(a_d).saturating_mul(U256::from(b_d)),
+    )
+}
+
+/// Compare the values of two prices.
+fn compare_prices(a: Price, b: Price) -> Ordering {
+    let (a_n, a_d) = a.into();
+    let (b_n, b_d) = b.into();
+    U256::from(a_n)
+        .saturating_mul(U256::from(b_d))
+        .cmp(&U256::from(b_n).saturating_mul(U256::from(a_d)))
+}
+
+impl<T: Config> VolatilityOracle<AssetId, T::BlockNumber, Volatility> for Pallet<T> {
+    type Error = OracleError;
+
+    /// Returns the volatility of the price of the given assets aggregated over `period` and the
+    /// age of the oracle. The volatility is the exponentially smoothed variance of the relative
+    /// price changes per block, updated to the state of the parent block.
+    fn get_volatility(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(Volatility, T::BlockNumber), Self::Error> {
+        if asset_a == asset_b {
+            return Err(OracleError::SameAsset);
+        };
+        Self::get_updated_entry(source, ordered_pair(asset_a, asset_b), period)
+            .ok_or(OracleError::NotPresent)
+            .map(|(entry, initialized)| (entry.volatility, entry.timestamp.saturating_sub(initialized)))
+    }
+
+    fn get_volatility_weight() -> Weight {
+        T::WeightInfo::get_entry()
+    }
+}
+
+/// A price oracle deriving prices via the price paths configured in the pallet.
+///
+/// The `source` passed to `get_price` is ignored, as each hop of a path specifies its own source.
+pub struct PathPriceOracle<T>(PhantomData<T>);
+
+impl<T: Config> AggregatedPriceOracle<AssetId, T::BlockNumber, Price> for PathPriceOracle<T> {
+    type Error = OracleError;
+
+    fn get_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(Price, T::BlockNumber), Self::Error> {
+        Pallet::<T>::get_path_price(asset_a, asset_b, period)
+    }
+
+    fn get_price_weight() -> Weight {
+        T::WeightInfo::get_entry()
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_mul(MAX_PRICE_PATH_LENGTH as u64)
+            .saturating_add(T::DbWeight::get().reads(1))
+    }
+}
+
+/// A price oracle combining the prices of an asset pair from multiple sources.
+///
+/// Prices requested for `AggregatedSource` are combined from the oracles of `Sources` according
+/// to `Mode`, see [`Pallet::get_aggregated_price`]. Requests for other sources are passed on to the
+/// pallet, so it can replace the pallet in existing consumers.
+pub struct MultiSourcePriceOracle<T, AggregatedSource, Sources, Mode>(
+    PhantomData<(T, AggregatedSource, Sources, Mode)>,
+);
+
+impl<T, AggregatedSource, Sources, Mode> AggregatedPriceOracle<AssetId, T::BlockNumber, Price>
+    for MultiSourcePriceOracle<T, AggregatedSource, Sources, Mode>
+where
+    T: Config,
+    AggregatedSource: Get<Source>,
+    Sources: Get<Vec<Source>>,
+    Mode: Get<AggregationMode>,
+{
+    type Error = OracleError;
+
+    fn get_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(Price, T::BlockNumber), Self::Error> {
+        if source != AggregatedSource::get() {
+            return Pallet::<T>::get_price(asset_a, asset_b, period, source);
+        }
+        Pallet::<T>::get_aggregated_price(asset_a, asset_b, period, &Sources::get(), Mode::get())
+    }
+
+    fn get_price_weight() -> Weight {
+        let sources = Sources::get().len().max(1) as u64;
+        T::WeightInfo::get_entry().saturating_mul(sources)
+    }
+}
+
+/// A price provider backed by the oracle prices of `Period`, e.g. for fee payment in non-native
+/// currencies. Unlike spot prices, these cannot be manipulated by a trade in the same block.
+///
+/// The price is taken from the first source in `Sources` with an oracle for the pair that was
+/// updated within `MaxAge` blocks. Later sources serve as fallback.
+///
+/// Implements `NativePriceOracle` with prices denominated against `NativeAsset`, i.e. the amount
+/// of the currency per unit of the native asset.
+pub struct OraclePriceProvider<T, NativeAsset, Period, Sources, MaxAge>(
+    PhantomData<(T, NativeAsset, Period, Sources, MaxAge)>,
+);
+
+impl<T, NativeAsset, Period, Sources, MaxAge> SpotPriceProvider<AssetId>
+    for OraclePriceProvider<T, NativeAsset, Period, Sources, MaxAge>
+where
+    T: Config,
+    NativeAsset: Get<AssetId>,
+    Period: Get<OraclePeriod>,
+    Sources: Get<Vec<Source>>,
+    MaxAge: Get<T::BlockNumber>,
+{
+    type Price = FixedU128;
+
+    fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+        Self::spot_price(asset_a, asset_b).is_some()
+    }
+
+    /// Return the oracle price of `asset_b` denominated in `asset_a`.
+    fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+        if asset_a == asset_b {
+            return Some(FixedU128::one());
+        }
+        Sources::get().into_iter().find_map(|source| {
+            let (price, _) =
+                Pallet::<T>::get_fresh_price(source, asset_a, asset_b, Period::get(), MaxAge::get()).ok()?;
+            let (n, d): (Balance, Balance) = price.into();
+            FixedU128::checked_from_rational(n, d).filter(|price| !price.is_zero())
+        })
+    }
+}
+
+impl<T, NativeAsset, Period, Sources, MaxAge> NativePriceOracle<AssetId, FixedU128>
+    for OraclePriceProvider<T, NativeAsset, Period, Sources, MaxAge>
+where
+    T: Config,
+    NativeAsset: Get<AssetId>,
+    Period: Get<OraclePeriod>,
+    Sources: Get<Vec<Source>>,
+    MaxAge: Get<T::BlockNumber>,
+{
+    fn price(currency: AssetId) -> Option<FixedU128> {
+        Self::spot_price(currency, NativeAsset::get())
+    }
+}
+
 /// A callback handler for trading and liquidity activity that schedules oracle updates.
 pub struct OnActivityHandler<T>(PhantomData<T>);
 
@@ -373,6 +1577,19 @@ impl<T: Config> OnCreatePoolHandler<AssetId> for OnActivityHandler<T> {
     }
 }
 
+/// Estimated weight of computing the priority of one accumulator entry when looking for an entry
+/// to evict.
+const ENTRY_PRIORITY_WEIGHT: u64 = 1_000_000;
+
+/// Calculate the weight of evicting an entry from a full accumulator in `on_trade`/
+/// `on_liquidity_changed`: reading `WhitelistedAssets` and computing the priority of all
+/// `max_entries` accumulated entries.
+pub(crate) fn eviction_weight<T: Config>(max_entries: u32) -> Weight {
+    T::DbWeight::get().reads(1).saturating_add(Weight::from_ref_time(
+        ENTRY_PRIORITY_WEIGHT.saturating_mul(max_entries.into()),
+    ))
+}
+
 /// Calculate the weight contribution of one `on_trade`/`on_liquidity_changed` call towards
 /// `on_finalize`.
 pub(crate) fn fractional_on_finalize_weight<T: Config>(max_entries: u32) -> Weight {
@@ -405,6 +1622,7 @@ impl<T: Config> OnTradeHandler<AssetId, Balance> for OnActivityHandler<T> {
             price,
             volume,
             liquidity,
+            volatility: Volatility::zero(),
             timestamp,
         };
         Pallet::<T>::on_trade(source, ordered_pair(asset_a, asset_b), entry)
@@ -412,8 +1630,9 @@ impl<T: Config> OnTradeHandler<AssetId, Balance> for OnActivityHandler<T> {
 
     fn on_trade_weight() -> Weight {
         let max_entries = T::MaxUniqueEntries::get();
-        // on_trade + on_finalize / max_entries
+        // on_trade + eviction from a full accumulator + on_finalize / max_entries
         T::WeightInfo::on_trade_multiple_tokens(max_entries)
+            .saturating_add(eviction_weight::<T>(max_entries))
             .saturating_add(fractional_on_finalize_weight::<T>(max_entries))
     }
 }
@@ -446,6 +1665,7 @@ impl<T: Config> OnLiquidityChangedHandler<AssetId, Balance> for OnActivityHandle
             // liquidity provision does not count as trade volume
             volume: Volume::default(),
             liquidity,
+            volatility: Volatility::zero(),
             timestamp,
         };
         Pallet::<T>::on_liquidity_changed(source, ordered_pair(asset_a, asset_b), entry)
@@ -453,8 +1673,9 @@ impl<T: Config> OnLiquidityChangedHandler<AssetId, Balance> for OnActivityHandle
 
     fn on_liquidity_changed_weight() -> Weight {
         let max_entries = T::MaxUniqueEntries::get();
-        // on_liquidity + on_finalize / max_entries
+        // on_liquidity + eviction from a full accumulator + on_finalize / max_entries
         T::WeightInfo::on_liquidity_changed_multiple_tokens(max_entries)
+            .saturating_add(eviction_weight::<T>(max_entries))
             .saturating_add(fractional_on_finalize_weight::<T>(max_entries))
     }
 }
@@ -518,6 +1739,8 @@ pub enum OracleError {
     NotPresent,
     /// The oracle is not defined if the asset ids are the same.
     SameAsset,
+    /// An oracle of the price path was not updated within `MaxPricePathAge` blocks.
+    Stale,
 }
 
 impl<T: Config> AggregatedOracle<AssetId, Balance, T::BlockNumber, Price> for Pallet<T> {
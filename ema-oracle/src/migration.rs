@@ -0,0 +1,283 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{AssetId, Balance, Config, OracleEntry, OraclePeriod, Pallet, PeriodLength, Price, Source, Volatility};
+use codec::{Decode, Encode};
+use frame_support::{
+    log,
+    sp_runtime::{traits::Zero, RuntimeDebug},
+    traits::{Get, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+use hydradx_traits::{Liquidity, Volume};
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Oracle entry before the volatility was added in storage version 2.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct OldOracleEntry<BlockNumber> {
+    pub price: Price,
+    pub volume: Volume<Balance>,
+    pub liquidity: Liquidity<Balance>,
+    pub timestamp: BlockNumber,
+}
+
+impl<BlockNumber> From<OldOracleEntry<BlockNumber>> for OracleEntry<BlockNumber> {
+    fn from(entry: OldOracleEntry<BlockNumber>) -> Self {
+        Self {
+            price: entry.price,
+            volume: entry.volume,
+            liquidity: entry.liquidity,
+            volatility: Volatility::zero(),
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+/// Oracles are keyed by the period length in blocks instead of the named `OraclePeriod`.
+///
+/// Periods with the same length (e.g. `TenMinutes` and `Custom(100)` with six second blocks) map
+/// to the same key. Only the most recently updated of their entries is kept.
+pub mod v1 {
+    use super::*;
+    use frame_support::{pallet_prelude::*, storage_alias};
+    use sp_std::collections::btree_map::{BTreeMap, Entry};
+    #[cfg(feature = "try-runtime")]
+    use sp_std::collections::btree_set::BTreeSet;
+    use sp_std::vec::Vec;
+
+    pub(crate) mod old {
+        use super::*;
+
+        #[storage_alias]
+        pub(crate) type Oracles<T: Config> = StorageNMap<
+            Pallet<T>,
+            (
+                NMapKey<Twox64Concat, Source>,
+                NMapKey<Twox64Concat, (AssetId, AssetId)>,
+                NMapKey<Twox64Concat, OraclePeriod>,
+            ),
+            (
+                OldOracleEntry<<T as frame_system::Config>::BlockNumber>,
+                <T as frame_system::Config>::BlockNumber,
+            ),
+            OptionQuery,
+        >;
+    }
+
+    pub(crate) mod new {
+        use super::*;
+
+        #[storage_alias]
+        pub(crate) type Oracles<T: Config> = StorageNMap<
+            Pallet<T>,
+            (
+                NMapKey<Twox64Concat, Source>,
+                NMapKey<Twox64Concat, (AssetId, AssetId)>,
+                NMapKey<Twox64Concat, PeriodLength>,
+            ),
+            (
+                OldOracleEntry<<T as frame_system::Config>::BlockNumber>,
+                <T as frame_system::Config>::BlockNumber,
+            ),
+            OptionQuery,
+        >;
+    }
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA Oracle migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::ema-oracle",
+            "Running migration to v1 for EMA Oracle"
+        );
+
+        let old_entries: Vec<_> = old::Oracles::<T>::drain().collect();
+        let i = old_entries.len() as u64;
+        let mut new_entries = BTreeMap::new();
+        for ((source, assets, period), entry) in old_entries {
+            let length = Pallet::<T>::period_length(period);
+            match new_entries.entry((source, assets, length)) {
+                Entry::Vacant(vacant) => {
+                    vacant.insert(entry);
+                }
+                Entry::Occupied(mut occupied) => {
+                    log::warn!(
+                        target: "runtime::ema-oracle",
+                        "EMA Oracle migration: period {:?} of oracle {:?} {:?} has the same length {:?} as another period, keeping the most recent entry",
+                        period,
+                        source,
+                        assets,
+                        length
+                    );
+                    if entry.0.timestamp > occupied.get().0.timestamp {
+                        occupied.insert(entry);
+                    }
+                }
+            }
+        }
+        for (key, entry) in new_entries {
+            new::Oracles::<T>::insert(key, entry);
+        }
+
+        StorageVersion::new(1).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(i, i.saturating_mul(2))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Unexpected storage version.");
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA Oracle migration: POST checks successful!"
+        );
+    }
+
+    /// Runs the migration to v1 if the pallet is still at storage version 0.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                log::info!(
+                    target: "runtime::ema-oracle",
+                    "EMA Oracle migration to v1 skipped - storage version already upgraded"
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>();
+
+            // entries of periods with the same length are merged
+            let count = old::Oracles::<T>::iter_keys()
+                .map(|(source, assets, period)| (source, assets, Pallet::<T>::period_length(period)))
+                .collect::<BTreeSet<_>>()
+                .len() as u32;
+            Ok(count.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>();
+
+            let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre upgrade state.")?;
+            if new::Oracles::<T>::iter_keys().count() as u32 != count {
+                return Err("Not all oracles were migrated.");
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Oracle entries track the volatility of the price.
+pub mod v2 {
+    use super::*;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA Oracle migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::ema-oracle",
+            "Running migration to v2 for EMA Oracle"
+        );
+
+        let mut i = 0;
+        crate::Oracles::<T>::translate(
+            |_key,
+             (entry, initialized): (
+                OldOracleEntry<<T as frame_system::Config>::BlockNumber>,
+                <T as frame_system::Config>::BlockNumber,
+            )| {
+                i += 1;
+                Some((entry.into(), initialized))
+            },
+        );
+
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(i, i)
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 2, "Unexpected storage version.");
+
+        log::info!(
+            target: "runtime::ema-oracle",
+            "EMA Oracle migration: POST checks successful!"
+        );
+    }
+
+    /// Runs the migration to v2 if the pallet is at storage version 1.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != 1 {
+                log::info!(
+                    target: "runtime::ema-oracle",
+                    "EMA Oracle migration to v2 skipped - storage version is not 1"
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>();
+
+            let count = crate::Oracles::<T>::iter_keys().count() as u32;
+            Ok(count.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>();
+
+            let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre upgrade state.")?;
+            if crate::Oracles::<T>::iter().count() as u32 != count {
+                return Err("Not all oracles were migrated.");
+            }
+
+            Ok(())
+        }
+    }
+}
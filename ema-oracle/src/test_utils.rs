@@ -0,0 +1,57 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for pallets testing their integration with the oracle in their own mock runtime.
+
+/// Implement `pallet_ema_oracle::Config` for `$runtime` with oracles for the given `$periods` and
+/// test defaults for everything else.
+///
+/// The runtime has to contain `frame_system` as `System` and the oracle pallet, so that
+/// `RuntimeEvent` includes its events. The calling crate needs `frame-support`, `frame-system`
+/// and `hydradx-traits` as dependencies.
+#[macro_export]
+macro_rules! impl_test_config {
+    ($runtime:ty, $periods:expr) => {
+        frame_support::parameter_types! {
+            pub const EmaOracleFeedSource: hydradx_traits::oracle::Source = *b"feeders_";
+            pub EmaOracleSupportedPeriods: frame_support::BoundedVec<
+                hydradx_traits::oracle::OraclePeriod,
+                frame_support::traits::ConstU32<{ $crate::MAX_PERIODS }>,
+            > = $periods;
+        }
+
+        impl $crate::Config for $runtime {
+            type RuntimeEvent = RuntimeEvent;
+            type WeightInfo = ();
+            type BlockNumberProvider = System;
+            type SupportedPeriods = EmaOracleSupportedPeriods;
+            type PeriodLengths = $crate::DefaultPeriodLengths;
+            type MaxUniqueEntries = frame_support::traits::ConstU32<20>;
+            type MaxWhitelistedEntries = frame_support::traits::ConstU32<20>;
+            type OracleWhitelist = frame_support::traits::Everything;
+            type AuthorityOrigin = frame_system::EnsureRoot<<$runtime as frame_system::Config>::AccountId>;
+            type MaxPricePathAge = frame_support::traits::ConstU64<10>;
+            type MaxPriceChange = ();
+            type PruneAfter = frame_support::traits::ConstU64<1_000>;
+            type OnOracleRemoved = ();
+            type FeedSource = EmaOracleFeedSource;
+            type MaxFeeders = frame_support::traits::ConstU32<5>;
+            type FeedRateLimit = frame_support::traits::ConstU64<1>;
+            type MaxFeedPairs = frame_support::traits::ConstU32<5>;
+        }
+    };
+}
@@ -0,0 +1,183 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{ACA, DOT, HDX};
+use super::*;
+
+use frame_support::parameter_types;
+use pretty_assertions::assert_eq;
+
+const OTHER_SOURCE: Source = *b"othersrc";
+const THIRD_SOURCE: Source = *b"thirdsrc";
+const AGGREGATED_SOURCE: Source = *b"aggregat";
+
+parameter_types! {
+    pub const AggregatedSource: Source = AGGREGATED_SOURCE;
+    pub AggregatedSources: Vec<Source> = vec![SOURCE, OTHER_SOURCE, THIRD_SOURCE];
+    pub const MedianMode: AggregationMode = AggregationMode::Median;
+    pub const WeightedMeanMode: AggregationMode = AggregationMode::LiquidityWeightedMean;
+}
+
+type MedianOracle = MultiSourcePriceOracle<Test, AggregatedSource, AggregatedSources, MedianMode>;
+type WeightedMeanOracle = MultiSourcePriceOracle<Test, AggregatedSource, AggregatedSources, WeightedMeanMode>;
+
+/// Record a trade of `HDX/DOT` with the given liquidity for `source`.
+fn trade(source: Source, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        source,
+        HDX,
+        DOT,
+        1_000,
+        1_000,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+}
+
+fn price_of(price: Price) -> Rational {
+    Rational::from(Into::<(u128, u128)>::into(price))
+}
+
+#[test]
+fn median_should_return_middle_price() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        trade(OTHER_SOURCE, 2_000, 1_000);
+        trade(THIRD_SOURCE, 10_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let (price, _) = MedianOracle::get_price(HDX, DOT, LastBlock, AGGREGATED_SOURCE).unwrap();
+
+        assert_eq!(price_of(price), Rational::from(2));
+    });
+}
+
+#[test]
+fn median_should_return_mean_of_middle_prices_when_number_of_prices_is_even() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        trade(OTHER_SOURCE, 3_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let (price, _) = MedianOracle::get_price(HDX, DOT, LastBlock, AGGREGATED_SOURCE).unwrap();
+
+        assert_eq!(price_of(price), Rational::from(2));
+    });
+}
+
+#[test]
+fn weighted_mean_should_weight_prices_by_liquidity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        trade(OTHER_SOURCE, 4_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let (price, _) = WeightedMeanOracle::get_price(HDX, DOT, LastBlock, AGGREGATED_SOURCE).unwrap();
+
+        // (1 * 1_000 + 4 * 4_000) / 5_000 = 3.4
+        let diff = price_of(price) - Rational::from((34, 10));
+        assert!(diff.abs() < Rational::from((1, 1_000_000_000)));
+    });
+}
+
+#[test]
+fn aggregated_price_should_respect_asset_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        trade(OTHER_SOURCE, 2_000, 1_000);
+        trade(THIRD_SOURCE, 10_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let (price, _) = MedianOracle::get_price(DOT, HDX, LastBlock, AGGREGATED_SOURCE).unwrap();
+
+        assert_eq!(price_of(price), Rational::from((1, 2)));
+    });
+}
+
+#[test]
+fn aggregated_price_should_return_age_of_youngest_oracle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(5);
+        trade(OTHER_SOURCE, 2_000, 1_000);
+        EmaOracle::on_finalize(5);
+        System::set_block_number(6);
+
+        let (_, age) = MedianOracle::get_price(HDX, DOT, TenMinutes, AGGREGATED_SOURCE).unwrap();
+
+        assert_eq!(age, 0);
+    });
+}
+
+#[test]
+fn aggregated_price_should_skip_sources_without_oracle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(OTHER_SOURCE, 2_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let (price, _) = MedianOracle::get_price(HDX, DOT, LastBlock, AGGREGATED_SOURCE).unwrap();
+
+        assert_eq!(price_of(price), Rational::from(2));
+    });
+}
+
+#[test]
+fn aggregated_price_should_fail_when_no_source_has_oracle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            MedianOracle::get_price(HDX, ACA, LastBlock, AGGREGATED_SOURCE),
+            Err(OracleError::NotPresent)
+        );
+        assert_eq!(
+            WeightedMeanOracle::get_price(HDX, HDX, LastBlock, AGGREGATED_SOURCE),
+            Err(OracleError::SameAsset)
+        );
+    });
+}
+
+#[test]
+fn other_sources_should_be_passed_on_to_the_pallet() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        trade(SOURCE, 1_000, 1_000);
+        trade(OTHER_SOURCE, 2_000, 1_000);
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            MedianOracle::get_price(HDX, DOT, LastBlock, OTHER_SOURCE),
+            EmaOracle::get_price(HDX, DOT, LastBlock, OTHER_SOURCE)
+        );
+    });
+}
@@ -0,0 +1,174 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{DOT, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+fn max_entries() -> u32 {
+    <<Test as crate::Config>::MaxUniqueEntries as Get<u32>>::get()
+}
+
+/// Fill the accumulator with trades of pairs `(i, i + 1)` with liquidity of `2_000` of both
+/// assets. The first pair trades the given amount, the rest trades `1_000`.
+fn fill_accumulator(first_amount: Balance) {
+    for i in 0..max_entries() {
+        let amount = if i == 0 { first_amount } else { 1_000 };
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE,
+            i,
+            i + 1,
+            amount,
+            amount,
+            2_000,
+            2_000,
+        ));
+    }
+}
+
+#[test]
+fn on_trade_should_evict_entry_with_lowest_relative_volume_when_accumulator_is_full() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(500);
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 1_000, 2_000, 2_000
+        ));
+
+        assert_eq!(Accumulator::<Test>::get().len() as u32, max_entries());
+        assert!(get_accumulator_entry(SOURCE, (HDX, DOT)).is_some());
+        assert_eq!(get_accumulator_entry(SOURCE, (0, 1)), None);
+        System::assert_last_event(
+            Event::AccumulatorEntryEvicted {
+                source: SOURCE,
+                assets: (0, 1),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn on_trade_should_fail_when_accumulator_is_full_and_entry_has_lower_relative_volume() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(500);
+
+        // higher liquidity in absolute numbers does not matter, e.g. for assets with more decimals
+        assert_noop!(
+            OnActivityHandler::<Test>::on_trade(SOURCE, HDX, DOT, 1_000, 1_000, 1_000_000_000, 1_000_000_000)
+                .map_err(|(_w, e)| e),
+            Error::<Test>::TooManyUniqueEntries
+        );
+    });
+}
+
+#[test]
+fn on_trade_should_evict_entry_with_lower_relative_volume_regardless_of_decimals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(500);
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE,
+            HDX,
+            DOT,
+            1_000_000_000,
+            1_000_000_000,
+            2_000_000_000,
+            2_000_000_000
+        ));
+
+        assert!(get_accumulator_entry(SOURCE, (HDX, DOT)).is_some());
+        assert_eq!(get_accumulator_entry(SOURCE, (0, 1)), None);
+    });
+}
+
+#[test]
+fn on_trade_should_update_existing_entry_when_accumulator_is_full() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(500);
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, 0, 1, 1_000, 1_000, 500, 500
+        ));
+
+        assert_eq!(
+            get_accumulator_entry(SOURCE, (0, 1)).map(|entry| entry.liquidity),
+            Some(Liquidity::new(500, 500))
+        );
+        assert!(System::events().is_empty());
+    });
+}
+
+#[test]
+fn whitelisted_oracle_should_evict_entry_with_higher_relative_volume() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(1_000);
+        assert_ok!(EmaOracle::add_oracle(RuntimeOrigin::root(), SOURCE, (HDX, DOT)));
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 10, 10, 2_000, 2_000
+        ));
+
+        assert!(get_accumulator_entry(SOURCE, (HDX, DOT)).is_some());
+        assert_eq!(get_accumulator_entry(SOURCE, (0, 1)), None);
+        System::assert_last_event(
+            Event::AccumulatorEntryEvicted {
+                source: SOURCE,
+                assets: (0, 1),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn whitelisted_oracle_should_not_be_evicted_by_oracle_with_higher_relative_volume() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for i in 0..max_entries() {
+            assert_ok!(EmaOracle::add_oracle(RuntimeOrigin::root(), SOURCE, (i, i + 1)));
+        }
+        fill_accumulator(1_000);
+
+        assert_noop!(
+            OnActivityHandler::<Test>::on_trade(SOURCE, HDX, DOT, 1_000, 1_000, 10, 10).map_err(|(_w, e)| e),
+            Error::<Test>::TooManyUniqueEntries
+        );
+    });
+}
+
+#[test]
+fn evicted_entry_should_not_update_oracle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        fill_accumulator(500);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 1_000, 2_000, 2_000
+        ));
+
+        EmaOracle::on_finalize(1);
+
+        assert_eq!(get_oracle_entry(0, 1, LastBlock), None);
+        assert!(get_oracle_entry(HDX, DOT, LastBlock).is_some());
+    });
+}
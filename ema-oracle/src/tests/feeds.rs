@@ -0,0 +1,317 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{ACA, DOT, FEED_SOURCE, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+fn add_feeders(feeders: impl IntoIterator<Item = u64>) {
+    for feeder in feeders {
+        assert_ok!(EmaOracle::add_feeder(RuntimeOrigin::root(), feeder));
+    }
+}
+
+fn submit(feeder: u64, price: (Balance, Balance), liquidity: (Balance, Balance)) -> DispatchResult {
+    EmaOracle::submit_price(RuntimeOrigin::signed(feeder), HDX, DOT, price.into(), liquidity.into())
+}
+
+fn accumulated_entry() -> OracleEntry<BlockNumber> {
+    get_accumulator_entry(FEED_SOURCE, (HDX, DOT)).expect("entry should be accumulated")
+}
+
+#[test]
+fn add_feeder_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(EmaOracle::add_feeder(RuntimeOrigin::root(), 1));
+
+        assert!(EmaOracle::feeders().contains(&1));
+        System::assert_last_event(Event::FeederAdded { feeder: 1 }.into());
+    });
+}
+
+#[test]
+fn add_feeder_should_fail_when_not_called_by_authority() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_feeder(RuntimeOrigin::signed(1), 1),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn add_feeder_should_fail_when_feeder_list_is_full() {
+    new_test_ext().execute_with(|| {
+        add_feeders(1..=5);
+
+        assert_noop!(
+            EmaOracle::add_feeder(RuntimeOrigin::root(), 6),
+            Error::<Test>::TooManyFeeders
+        );
+    });
+}
+
+#[test]
+fn remove_feeder_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+
+        assert_ok!(EmaOracle::remove_feeder(RuntimeOrigin::root(), 1));
+
+        assert!(!EmaOracle::feeders().contains(&1));
+        System::assert_last_event(Event::FeederRemoved { feeder: 1 }.into());
+        assert_noop!(submit(1, (2, 1), (2_000, 1_000)), Error::<Test>::FeederNotFound);
+    });
+}
+
+#[test]
+fn remove_feeder_should_fail_when_account_is_not_a_feeder() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::remove_feeder(RuntimeOrigin::root(), 1),
+            Error::<Test>::FeederNotFound
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_accumulate_entry_under_feed_source() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+
+        let entry = accumulated_entry();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 1_000));
+        assert_eq!(entry.volume, Volume::default());
+        System::assert_last_event(
+            Event::PriceSubmitted {
+                feeder: 1,
+                assets: ordered_pair(HDX, DOT),
+                price: Price::new(2, 1),
+                liquidity: Liquidity::new(2_000, 1_000),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn submit_price_should_normalize_asset_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(1),
+            DOT,
+            HDX,
+            Price::new(1, 2),
+            Liquidity::new(1_000, 2_000),
+        ));
+
+        let entry = accumulated_entry();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 1_000));
+    });
+}
+
+#[test]
+fn submit_price_should_use_median_of_submissions_in_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1, 2, 3]);
+
+        assert_ok!(submit(1, (10, 1), (1_000, 100)));
+        assert_ok!(submit(2, (1, 1), (3_000, 300)));
+        assert_ok!(submit(3, (2, 1), (2_000, 200)));
+
+        let entry = accumulated_entry();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 200));
+
+        EmaOracle::on_finalize(1);
+        let (last_block, _) =
+            EmaOracle::oracle((FEED_SOURCE, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH)).unwrap();
+        assert_eq!(last_block.price, Price::new(2, 1));
+    });
+}
+
+#[test]
+fn submit_price_should_ignore_submissions_of_previous_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1, 2]);
+        assert_ok!(submit(1, (10, 1), (1_000, 100)));
+        EmaOracle::on_finalize(1);
+
+        System::set_block_number(2);
+        assert_ok!(submit(2, (2, 1), (2_000, 200)));
+
+        let entry = accumulated_entry();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 200));
+    });
+}
+
+#[test]
+fn submit_price_should_be_rate_limited_per_feeder_and_pair() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1, 2]);
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+        EmaOracle::on_finalize(1);
+
+        System::set_block_number(3);
+        assert_noop!(submit(1, (2, 1), (2_000, 1_000)), Error::<Test>::FeedRateLimited);
+        assert_ok!(submit(2, (2, 1), (2_000, 1_000)));
+        EmaOracle::on_finalize(3);
+
+        System::set_block_number(4);
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+    });
+}
+
+#[test]
+fn submit_price_should_replace_submission_of_feeder_in_same_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+        assert_ok!(submit(1, (10, 1), (1_000, 100)));
+
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+
+        let entry = accumulated_entry();
+        assert_eq!(entry.price, Price::new(2, 1));
+        assert_eq!(entry.liquidity, Liquidity::new(2_000, 1_000));
+        assert_eq!(EmaOracle::feed_submissions()[&ordered_pair(HDX, DOT)].len(), 1);
+    });
+}
+
+#[test]
+fn on_finalize_should_clear_feed_submissions() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1, 2]);
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+        assert_ok!(submit(2, (2, 1), (2_000, 1_000)));
+
+        EmaOracle::on_finalize(1);
+
+        assert!(EmaOracle::feed_submissions().is_empty());
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_too_many_pairs_were_submitted_in_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+        // `MaxFeedPairs` is 2 in the mock runtime
+        for asset in [DOT, ACA] {
+            assert_ok!(EmaOracle::submit_price(
+                RuntimeOrigin::signed(1),
+                HDX,
+                asset,
+                (2, 1).into(),
+                (2_000, 1_000).into()
+            ));
+        }
+
+        assert_noop!(
+            EmaOracle::submit_price(RuntimeOrigin::signed(1), DOT, ACA, (2, 1).into(), (2_000, 1_000).into()),
+            Error::<Test>::TooManyFeedPairs
+        );
+        // pairs submitted in the block can still be updated
+        assert_ok!(submit(1, (3, 1), (2_000, 1_000)));
+    });
+}
+
+#[test]
+fn on_finalize_should_allow_feed_submissions_for_new_pairs_in_next_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+        for asset in [DOT, ACA] {
+            assert_ok!(EmaOracle::submit_price(
+                RuntimeOrigin::signed(1),
+                HDX,
+                asset,
+                (2, 1).into(),
+                (2_000, 1_000).into()
+            ));
+        }
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_ok!(EmaOracle::submit_price(
+            RuntimeOrigin::signed(1),
+            DOT,
+            ACA,
+            (2, 1).into(),
+            (2_000, 1_000).into()
+        ));
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_feed_rate_limits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1, 2]);
+        assert_ok!(submit(1, (2, 1), (2_000, 1_000)));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+        assert_ok!(submit(2, (2, 1), (2_000, 1_000)));
+        EmaOracle::on_finalize(2);
+
+        // `FeedRateLimit` is 3 blocks in the mock runtime
+        System::set_block_number(4);
+        EmaOracle::on_idle(4, Weight::MAX);
+
+        assert_eq!(EmaOracle::last_feed_submission(1u64, ordered_pair(HDX, DOT)), None);
+        assert_eq!(EmaOracle::last_feed_submission(2u64, ordered_pair(HDX, DOT)), Some(2));
+        assert_eq!(EmaOracle::feed_prune_cursor(), None);
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_not_called_by_feeder() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(submit(1, (2, 1), (2_000, 1_000)), Error::<Test>::FeederNotFound);
+    });
+}
+
+#[test]
+fn submit_price_should_fail_when_price_or_liquidity_is_zero() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        add_feeders([1]);
+
+        assert_noop!(submit(1, (0, 1), (2_000, 1_000)), Error::<Test>::InvalidFeedPrice);
+        assert_noop!(submit(1, (2, 0), (2_000, 1_000)), Error::<Test>::InvalidFeedPrice);
+        assert_noop!(submit(1, (2, 1), (0, 1_000)), Error::<Test>::InvalidFeedPrice);
+    });
+}
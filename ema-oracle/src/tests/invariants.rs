@@ -57,6 +57,7 @@ fn oracle_entry_with_timestamp(timestamp: BlockNumber) -> impl Strategy<Value =
             price,
             volume,
             liquidity,
+            volatility: Volatility::zero(),
             timestamp,
         }
     })
@@ -70,6 +71,7 @@ fn oracle_entry_within_timestamp_range(
             price,
             volume,
             liquidity,
+            volatility: Volatility::zero(),
             timestamp,
         },
     )
@@ -115,10 +117,10 @@ proptest! {
         start_oracle in oracle_entry_within_timestamp_range((0, 1_000)),
         incoming_value in oracle_entry_within_timestamp_range((1_001, 100_000)),
     ) {
-        let next_oracle = start_oracle.calculate_current_from_outdated(TenMinutes, &incoming_value);
+        let next_oracle = start_oracle.calculate_current_from_outdated(EmaOracle::period_length(TenMinutes), &incoming_value);
 
         let mut start_oracle = start_oracle;
-        start_oracle.update_outdated_to_current(TenMinutes, &incoming_value);
+        start_oracle.update_outdated_to_current(EmaOracle::period_length(TenMinutes), &incoming_value);
         prop_assert_eq!(next_oracle, Some(start_oracle));
     }
 }
@@ -129,10 +131,10 @@ proptest! {
         start_oracle in oracle_entry_with_timestamp(10_000),
         incoming_value in oracle_entry_with_timestamp(10_001),
     ) {
-        let next_oracle = start_oracle.calculate_new_by_integrating_incoming(TenMinutes, &incoming_value);
+        let next_oracle = start_oracle.calculate_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &incoming_value);
 
         let mut start_oracle = start_oracle;
-        start_oracle.update_to_new_by_integrating_incoming(TenMinutes, &incoming_value);
+        start_oracle.update_to_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &incoming_value);
         prop_assert_eq!(next_oracle, Some(start_oracle));
     }
 }
@@ -151,7 +153,7 @@ proptest! {
             EmaOracle::on_finalize(1);
             let oracle_age: u32 = 98;
             System::set_block_number(u64::from(oracle_age) + 2);
-            let smoothing = into_smoothing(LastBlock);
+            let smoothing = into_smoothing(EmaOracle::period_length(LastBlock));
             let price = Price::new(liquidity_hdx, liquidity_dot);
             let volume = (amount_hdx, amount_dot, 0, 0);
             let expected = AggregatedEntry {
@@ -163,7 +165,7 @@ proptest! {
             };
             prop_assert_eq!(EmaOracle::get_entry(HDX, DOT, LastBlock, SOURCE), Ok(expected));
 
-            let smoothing = into_smoothing(TenMinutes);
+            let smoothing = into_smoothing(EmaOracle::period_length(TenMinutes));
             let expected_ten_min = AggregatedEntry {
                 price: iterated_price_ema(oracle_age, price, price, smoothing),
                 volume: iterated_volume_ema(oracle_age, volume, smoothing).into(),
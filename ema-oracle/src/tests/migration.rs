@@ -0,0 +1,85 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::migration::{v1, v2, OldOracleEntry};
+
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use pretty_assertions::assert_eq;
+
+fn old_entry(price: (Balance, Balance), timestamp: BlockNumber) -> OldOracleEntry<BlockNumber> {
+    OldOracleEntry {
+        price: price.into(),
+        volume: Volume::default(),
+        liquidity: Liquidity::new(1_000, 1_000),
+        timestamp,
+    }
+}
+
+#[test]
+fn migration_should_key_oracles_by_period_length() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<EmaOracle>();
+        let entry = old_entry((1, 2), 5);
+        v1::old::Oracles::<Test>::insert((SOURCE, (HDX, DOT), Day), (entry.clone(), 1));
+
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        let day = EmaOracle::period_length(Day);
+        assert_eq!(EmaOracle::on_chain_storage_version(), 2);
+        assert_eq!(EmaOracle::oracle((SOURCE, (HDX, DOT), day)), Some((entry.into(), 1)));
+        assert_eq!(Oracles::<Test>::iter().count(), 1);
+    });
+}
+
+#[test]
+fn migration_should_keep_most_recent_entry_of_periods_with_same_length() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<EmaOracle>();
+        let ten_minutes = EmaOracle::period_length(TenMinutes);
+        let older = old_entry((1, 2), 5);
+        let newer = old_entry((1, 3), 10);
+        v1::old::Oracles::<Test>::insert((SOURCE, (HDX, DOT), TenMinutes), (newer.clone(), 2));
+        v1::old::Oracles::<Test>::insert((SOURCE, (HDX, DOT), Custom(ten_minutes)), (older, 1));
+
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            EmaOracle::oracle((SOURCE, (HDX, DOT), ten_minutes)),
+            Some((newer.into(), 2))
+        );
+        assert_eq!(Oracles::<Test>::iter().count(), 1);
+    });
+}
+
+#[test]
+fn migrations_should_be_skipped_when_storage_version_is_current() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(2).put::<EmaOracle>();
+        let day = EmaOracle::period_length(Day);
+        let entry: OracleEntry<BlockNumber> = old_entry((1, 2), 5).into();
+        Oracles::<Test>::insert((SOURCE, (HDX, DOT), day), (entry.clone(), 1));
+
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(EmaOracle::on_chain_storage_version(), 2);
+        assert_eq!(EmaOracle::oracle((SOURCE, (HDX, DOT), day)), Some((entry, 1)));
+    });
+}
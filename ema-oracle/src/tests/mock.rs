@@ -23,13 +23,16 @@ use frame_support::pallet_prelude::ConstU32;
 use frame_support::parameter_types;
 use frame_support::sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Convert, IdentityLookup},
+    Permill,
 };
-use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::traits::{ConstU64, Contains, Everything, GenesisBuild};
 use frame_support::BoundedVec;
+use frame_system::EnsureRoot;
 use hydradx_traits::OraclePeriod::{self, *};
-use hydradx_traits::{AssetPairAccountIdFor, Liquidity, Volume};
+use hydradx_traits::{AssetPairAccountIdFor, Liquidity, OnOracleRemovedHandler, Volume};
 use sp_core::H256;
+use std::cell::RefCell;
 
 pub use hydradx_traits::Source;
 
@@ -39,7 +42,7 @@ pub type BlockNumber = u64;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
-use crate::MAX_PERIODS;
+use crate::{DefaultPeriodLengths, MAX_PERIODS};
 
 pub const HDX: AssetId = 1_000;
 pub const DOT: AssetId = 2_000;
@@ -54,6 +57,7 @@ pub const ORACLE_ENTRY_1: OracleEntry<BlockNumber> = OracleEntry {
         b_in: 0,
     },
     liquidity: Liquidity::new(2_000, 1_000),
+    volatility: Volatility::from_inner(0),
     timestamp: 5,
 };
 pub const ORACLE_ENTRY_2: OracleEntry<BlockNumber> = OracleEntry {
@@ -65,6 +69,7 @@ pub const ORACLE_ENTRY_2: OracleEntry<BlockNumber> = OracleEntry {
         b_in: 2_000,
     },
     liquidity: Liquidity::new(4_000, 4_000),
+    volatility: Volatility::from_inner(0),
     timestamp: 5,
 };
 
@@ -124,8 +129,54 @@ impl AssetPairAccountIdFor<AssetId, u64> for AssetPairAccountIdTest {
     }
 }
 
+/// Source whose oracles are only tracked when added to `WhitelistedAssets`.
+pub const RESTRICTED_SOURCE: Source = *b"restrict";
+
+/// Whitelists the oracles of all sources except `RESTRICTED_SOURCE`.
+pub struct MockOracleWhitelist;
+
+impl Contains<(Source, AssetId, AssetId)> for MockOracleWhitelist {
+    fn contains(&(source, _, _): &(Source, AssetId, AssetId)) -> bool {
+        source != RESTRICTED_SOURCE
+    }
+}
+
+/// Source whose oracle prices may change by at most 10% per block.
+pub const CLAMPED_SOURCE: Source = *b"clamped_";
+
+/// Limits the price change of oracles of `CLAMPED_SOURCE`.
+pub struct MockMaxPriceChange;
+
+impl Convert<Source, Option<Permill>> for MockMaxPriceChange {
+    fn convert(source: Source) -> Option<Permill> {
+        (source == CLAMPED_SOURCE).then_some(Permill::from_percent(10))
+    }
+}
+
+/// Source of the prices submitted by feeders.
+pub const FEED_SOURCE: Source = *b"feeders_";
+
+thread_local! {
+    static REMOVED_ORACLES: RefCell<Vec<(Source, AssetId, AssetId)>> = RefCell::new(Vec::new());
+}
+
+/// Records the oracles removed by the pallet.
+pub struct MockOnOracleRemoved;
+
+impl OnOracleRemovedHandler<AssetId> for MockOnOracleRemoved {
+    fn on_oracle_removed(source: Source, asset_a: AssetId, asset_b: AssetId) {
+        REMOVED_ORACLES.with(|v| v.borrow_mut().push((source, asset_a, asset_b)));
+    }
+}
+
+/// Return the oracles removed so far, in the order of removal.
+pub fn removed_oracles() -> Vec<(Source, AssetId, AssetId)> {
+    REMOVED_ORACLES.with(|v| v.borrow().clone())
+}
+
 parameter_types! {
-    pub SupportedPeriods: BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>> = bounded_vec![LastBlock, TenMinutes, Day, Week];
+    pub const FeedSource: Source = FEED_SOURCE;
+    pub SupportedPeriods: BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>> = bounded_vec![LastBlock, TenMinutes, Day, Week, Custom(50)];
 }
 
 impl Config for Test {
@@ -133,7 +184,19 @@ impl Config for Test {
     type WeightInfo = ();
     type BlockNumberProvider = System;
     type SupportedPeriods = SupportedPeriods;
+    type PeriodLengths = DefaultPeriodLengths;
     type MaxUniqueEntries = ConstU32<45>;
+    type MaxWhitelistedEntries = ConstU32<50>;
+    type OracleWhitelist = MockOracleWhitelist;
+    type AuthorityOrigin = EnsureRoot<u64>;
+    type MaxPricePathAge = ConstU64<10>;
+    type MaxPriceChange = MockMaxPriceChange;
+    type PruneAfter = ConstU64<100>;
+    type OnOracleRemoved = MockOnOracleRemoved;
+    type FeedSource = FeedSource;
+    type MaxFeeders = ConstU32<5>;
+    type FeedRateLimit = ConstU64<3>;
+    type MaxFeedPairs = ConstU32<2>;
 }
 
 pub type InitialDataEntry = (Source, (AssetId, AssetId), Price, Liquidity<Balance>);
@@ -161,6 +224,7 @@ impl ExtBuilder {
         let mut ext: sp_io::TestExternalities = t.into();
         ext.execute_with(|| {
             System::set_block_number(0);
+            REMOVED_ORACLES.with(|v| v.borrow_mut().clear());
         });
         ext
     }
@@ -15,8 +15,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregation;
+mod eviction;
+mod feeds;
 mod invariants;
+mod migration;
 mod mock;
+mod native_price;
+mod periods;
+mod price_clamp;
+mod price_path;
+mod pruning;
+mod queries;
+mod volatility;
+mod whitelist;
 
 use super::*;
 pub use mock::{
@@ -71,7 +83,7 @@ fn get_accumulator_entry(src: Source, (a, b): (AssetId, AssetId)) -> Option<Orac
 }
 
 fn get_oracle_entry(a: AssetId, b: AssetId, period: OraclePeriod) -> Option<OracleEntry<BlockNumber>> {
-    Oracles::<Test>::get((SOURCE, ordered_pair(a, b), period)).map(|(e, _)| e)
+    Oracles::<Test>::get((SOURCE, ordered_pair(a, b), EmaOracle::period_length(period))).map(|(e, _)| e)
 }
 
 #[test]
@@ -100,6 +112,7 @@ fn genesis_config_works() {
                         price: Price::new(1_000_000, 1),
                         volume: Volume::default(),
                         liquidity: Liquidity::new(2_000_000, 2_000_000_000),
+                        volatility: Volatility::zero(),
                         timestamp: 0,
                     })
                 );
@@ -110,6 +123,7 @@ fn genesis_config_works() {
                         price: Price::new(3_000_000, 1),
                         volume: Volume::default(),
                         liquidity: Liquidity::new(4_000_000, 4_000_000_000),
+                        volatility: Volatility::zero(),
                         timestamp: 0,
                     })
                 );
@@ -140,6 +154,7 @@ fn on_trade_handler_should_work() {
             price: Price::new(2_000, 1_000),
             volume: Volume::from_a_in_b_out(1_000, 500),
             liquidity: Liquidity::new(2_000, 1_000),
+            volatility: Volatility::zero(),
             timestamp: 5,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(expected));
@@ -155,6 +170,7 @@ fn on_liquidity_changed_handler_should_work() {
             price: Price::new(2_000, 1_000),
             volume: Volume::default(),
             liquidity: Liquidity::new(2_000, 1_000),
+            volatility: Volatility::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), None);
@@ -205,6 +221,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::new(liquidity_a, liquidity_b),
             volume: Volume::default(),
             liquidity: (liquidity_a, liquidity_b).into(),
+            volatility: Volatility::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_liquidity_entry));
@@ -225,6 +242,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::new(liquidity_a, liquidity_b),
             volume: Volume::default(),
             liquidity: (liquidity_a, liquidity_b).into(),
+            volatility: Volatility::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_liquidity_entry));
@@ -245,6 +263,7 @@ fn on_liquidity_changed_should_allow_zero_values() {
             price: Price::zero(),
             volume: Volume::default(),
             liquidity: (Balance::zero(), Balance::zero()).into(),
+            volatility: Volatility::zero(),
             timestamp,
         };
         assert_eq!(get_accumulator_entry(SOURCE, (HDX, DOT)), Some(only_price_entry));
@@ -347,12 +366,14 @@ fn oracle_volume_should_factor_in_asset_order() {
             price: Price::new(2_000, 1),
             volume: Volume::from_a_in_b_out(2_000_000, 1_000),
             liquidity: (2_000, 1).into(),
+            volatility: Volatility::zero(),
             timestamp: 0,
         };
         let second_entry = OracleEntry {
             price: Price::new(2_000, 1),
             volume: Volume::from_a_out_b_in(2_000_000, 1_000),
             liquidity: (2_000, 1).into(),
+            volatility: Volatility::zero(),
             timestamp: 0,
         };
 
@@ -423,13 +444,14 @@ fn update_data_should_use_old_last_block_oracle_to_update_to_parent() {
                 volume: Volume::default(),
                 ..second_entry.clone()
             };
+            let period_length = EmaOracle::period_length(period);
             let mut expected = ORACLE_ENTRY_1.clone();
             expected
-                .update_to_new_by_integrating_incoming(period, &second_entry)
+                .update_to_new_by_integrating_incoming(period_length, &second_entry)
                 .unwrap()
-                .update_outdated_to_current(period, &second_at_50)
+                .update_outdated_to_current(period_length, &second_at_50)
                 .unwrap()
-                .update_to_new_by_integrating_incoming(period, &third_entry)
+                .update_to_new_by_integrating_incoming(period_length, &third_entry)
                 .unwrap();
             assert_eq!(
                 get_oracle_entry(HDX, DOT, period).unwrap(),
@@ -443,11 +465,12 @@ fn update_data_should_use_old_last_block_oracle_to_update_to_parent() {
 
 #[test]
 fn calculate_new_by_integrating_incoming_only_updates_timestamp_on_stable_values() {
-    let period = TenMinutes;
+    let period = EmaOracle::period_length(TenMinutes);
     let start_oracle = OracleEntry {
         price: Price::new(4, 1),
         volume: Volume::from_a_in_b_out(1, 4),
         liquidity: Liquidity::new(4, 1),
+        volatility: Volatility::zero(),
         timestamp: 5_u32,
     };
     let next_value = OracleEntry {
@@ -464,6 +487,7 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(50, 1),
         volume: Volume::from_a_in_b_out(1, 50),
         liquidity: Liquidity::new(50, 1),
+        volatility: Volatility::zero(),
         timestamp: 5_u32,
     };
 
@@ -471,10 +495,11 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(151, 1),
         volume: Volume::from_a_in_b_out(1, 151),
         liquidity: Liquidity::new(151, 1),
+        volatility: Volatility::zero(),
         timestamp: 6,
     };
     let next_oracle = start_oracle
-        .calculate_new_by_integrating_incoming(TenMinutes, &next_value)
+        .calculate_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &next_value)
         .unwrap();
     // ten minutes corresponds to 100 blocks which corresponds to a smoothing factor of
     // `2 / 101 ≈ 1 / 50` which means that for an update from 50 to 151 we expect an update of
@@ -483,6 +508,7 @@ fn calculate_new_by_integrating_incoming_with_works() {
         price: Price::new(52, 1),
         volume: Volume::from_a_in_b_out(1, 52),
         liquidity: Liquidity::new(52, 1),
+        volatility: Volatility::zero(),
         timestamp: 6,
     };
     let tolerance = Price::new(1, 1e10 as u128);
@@ -498,6 +524,7 @@ fn calculate_new_by_integrating_incoming_last_block_period_returns_new_value() {
         price: Price::new(4, 1),
         volume: Volume::from_a_in_b_out(1_u128, 4_u128),
         liquidity: Liquidity::new(4_u128, 1_u128),
+        volatility: Volatility::zero(),
         timestamp: 5_u32,
     };
 
@@ -505,26 +532,30 @@ fn calculate_new_by_integrating_incoming_last_block_period_returns_new_value() {
         price: Price::new(8, 1),
         volume: Volume::from_a_in_b_out(1_u128, 8_u128),
         liquidity: Liquidity::new(8_u128, 1_u128),
+        volatility: Volatility::zero(),
         timestamp: 6,
     };
-    let next_oracle = start_oracle.calculate_new_by_integrating_incoming(LastBlock, &next_value);
+    let next_oracle =
+        start_oracle.calculate_new_by_integrating_incoming(EmaOracle::period_length(LastBlock), &next_value);
     let expected_oracle = next_value;
     assert_eq!(next_oracle, Some(expected_oracle));
 }
 
 #[test]
 fn calculate_current_from_outdated_should_incorporate_longer_time_deltas() {
-    let period = TenMinutes;
+    let period = EmaOracle::period_length(TenMinutes);
     let start_oracle = OracleEntry {
         price: Price::new(4_000, 1),
         volume: Volume::from_a_in_b_out(1, 4_000),
         liquidity: Liquidity::new(4_000, 1),
+        volatility: Volatility::zero(),
         timestamp: 5_u32,
     };
     let next_value = OracleEntry {
         price: Price::new(8_000, 1),
         volume: Volume::from_a_in_b_out(1, 8_000),
         liquidity: Liquidity::new(8_000, 1),
+        volatility: Volatility::zero(),
         timestamp: 1_000,
     };
     let next_oracle = start_oracle
@@ -635,6 +666,7 @@ fn get_price_returns_updated_price() {
                 price: Price::new(500_000, 1),
                 volume: Volume::default(),
                 liquidity: Liquidity::new(2_000_000, 2),
+                volatility: Volatility::zero(),
                 timestamp: 1,
             };
             System::set_block_number(1);
@@ -694,14 +726,20 @@ fn ema_update_should_return_none_if_new_entry_is_older() {
         timestamp: 9,
         ..ORACLE_ENTRY_2
     };
-    assert_eq!(entry.calculate_current_from_outdated(TenMinutes, &outdated_entry), None);
-    assert_eq!(entry.calculate_current_from_outdated(LastBlock, &outdated_entry), None);
     assert_eq!(
-        entry.calculate_new_by_integrating_incoming(TenMinutes, &outdated_entry),
+        entry.calculate_current_from_outdated(EmaOracle::period_length(TenMinutes), &outdated_entry),
         None
     );
     assert_eq!(
-        entry.calculate_new_by_integrating_incoming(LastBlock, &outdated_entry),
+        entry.calculate_current_from_outdated(EmaOracle::period_length(LastBlock), &outdated_entry),
+        None
+    );
+    assert_eq!(
+        entry.calculate_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &outdated_entry),
+        None
+    );
+    assert_eq!(
+        entry.calculate_new_by_integrating_incoming(EmaOracle::period_length(LastBlock), &outdated_entry),
         None
     );
     // same timestamp as current
@@ -709,23 +747,32 @@ fn ema_update_should_return_none_if_new_entry_is_older() {
         timestamp: 10,
         ..ORACLE_ENTRY_2
     };
-    assert_eq!(entry.calculate_current_from_outdated(TenMinutes, &outdated_entry), None);
-    assert_eq!(entry.calculate_current_from_outdated(LastBlock, &outdated_entry), None);
     assert_eq!(
-        entry.calculate_new_by_integrating_incoming(TenMinutes, &outdated_entry),
+        entry.calculate_current_from_outdated(EmaOracle::period_length(TenMinutes), &outdated_entry),
         None
     );
     assert_eq!(
-        entry.calculate_new_by_integrating_incoming(LastBlock, &outdated_entry),
+        entry.calculate_current_from_outdated(EmaOracle::period_length(LastBlock), &outdated_entry),
+        None
+    );
+    assert_eq!(
+        entry.calculate_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &outdated_entry),
+        None
+    );
+    assert_eq!(
+        entry.calculate_new_by_integrating_incoming(EmaOracle::period_length(LastBlock), &outdated_entry),
         None
     );
 
     assert_eq!(
-        entry.update_to_new_by_integrating_incoming(TenMinutes, &outdated_entry),
+        entry.update_to_new_by_integrating_incoming(EmaOracle::period_length(TenMinutes), &outdated_entry),
         None
     );
     assert_eq!(entry, original);
-    assert_eq!(entry.update_outdated_to_current(TenMinutes, &outdated_entry), None);
+    assert_eq!(
+        entry.update_outdated_to_current(EmaOracle::period_length(TenMinutes), &outdated_entry),
+        None
+    );
     assert_eq!(entry, original);
 }
 
@@ -741,25 +788,25 @@ fn check_period_smoothing_factors() {
 
     let last_block = smoothing_from_period(1);
     println!("Last Block: {} (bits: {})", last_block, last_block.to_bits());
-    assert_eq!(into_smoothing(LastBlock), last_block);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(LastBlock)), last_block);
 
     let short = smoothing_from_period(9);
     println!("Short: {} (bits: {})", short, short.to_bits());
-    assert_eq!(into_smoothing(Short), short);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(Short)), short);
 
     let ten_minutes = smoothing_from_period(10 * minutes);
     println!("Ten Minutes: {} (bits: {})", ten_minutes, ten_minutes.to_bits());
-    assert_eq!(into_smoothing(TenMinutes), ten_minutes);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(TenMinutes)), ten_minutes);
 
     let hour = smoothing_from_period(hours);
     println!("Hour: {} (bits: {})", hour, hour.to_bits());
-    assert_eq!(into_smoothing(Hour), hour);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(Hour)), hour);
 
     let day = smoothing_from_period(days);
     println!("Day: {} (bits: {})", day, day.to_bits());
-    assert_eq!(into_smoothing(Day), day);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(Day)), day);
 
     let week = smoothing_from_period(7 * days);
     println!("Week: {} (bits: {})", week, week.to_bits());
-    assert_eq!(into_smoothing(Week), week);
+    assert_eq!(into_smoothing(DefaultPeriodLengths::convert(Week)), week);
 }
@@ -0,0 +1,123 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{DOT, HDX};
+use super::*;
+
+use frame_support::parameter_types;
+use hydradx_traits::pools::SpotPriceProvider;
+use pretty_assertions::assert_eq;
+
+const OTHER_SOURCE: Source = *b"othersrc";
+
+parameter_types! {
+    pub const NativeAsset: AssetId = HDX;
+    pub const PricePeriod: OraclePeriod = OraclePeriod::LastBlock;
+    pub PriceSources: Vec<Source> = vec![SOURCE, OTHER_SOURCE];
+    pub const MaxPriceAge: BlockNumber = 10;
+}
+
+type PriceProvider = OraclePriceProvider<Test, NativeAsset, PricePeriod, PriceSources, MaxPriceAge>;
+
+/// Record a trade of `HDX/DOT` with the given liquidity for `source` and finalize the block.
+fn trade(source: Source, block: BlockNumber, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        source,
+        HDX,
+        DOT,
+        1_000,
+        1_000,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+#[test]
+fn price_should_be_denominated_against_native_asset() {
+    new_test_ext().execute_with(|| {
+        trade(SOURCE, 1, 1_000, 2_000);
+        System::set_block_number(2);
+
+        assert_eq!(PriceProvider::price(DOT), Some(FixedU128::from(2)));
+        assert_eq!(PriceProvider::spot_price(DOT, HDX), Some(FixedU128::from(2)));
+        assert_eq!(
+            PriceProvider::spot_price(HDX, DOT),
+            Some(FixedU128::saturating_from_rational(1, 2))
+        );
+    });
+}
+
+#[test]
+fn price_of_native_asset_should_be_one() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(PriceProvider::price(HDX), Some(FixedU128::one()));
+    });
+}
+
+#[test]
+fn price_should_fall_back_to_next_source() {
+    new_test_ext().execute_with(|| {
+        trade(OTHER_SOURCE, 1, 1_000, 4_000);
+        System::set_block_number(2);
+
+        assert_eq!(PriceProvider::price(DOT), Some(FixedU128::from(4)));
+    });
+}
+
+#[test]
+fn price_should_skip_stale_oracles() {
+    new_test_ext().execute_with(|| {
+        trade(SOURCE, 1, 1_000, 2_000);
+        trade(OTHER_SOURCE, 8, 1_000, 4_000);
+
+        // the oracle of `SOURCE` was last updated `MaxPriceAge` blocks before the parent block
+        System::set_block_number(12);
+        assert_eq!(PriceProvider::price(DOT), Some(FixedU128::from(2)));
+
+        System::set_block_number(13);
+        assert_eq!(PriceProvider::price(DOT), Some(FixedU128::from(4)));
+
+        System::set_block_number(20);
+        assert_eq!(PriceProvider::price(DOT), None);
+        assert!(!PriceProvider::pair_exists(DOT, HDX));
+    });
+}
+
+#[test]
+fn price_should_not_be_affected_by_trades_in_current_block() {
+    new_test_ext().execute_with(|| {
+        trade(SOURCE, 1, 1_000, 2_000);
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 1_000, 1_000, 20_000
+        ));
+
+        assert_eq!(PriceProvider::price(DOT), Some(FixedU128::from(2)));
+    });
+}
+
+#[test]
+fn price_should_be_none_without_oracle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(2);
+
+        assert_eq!(PriceProvider::price(DOT), None);
+        assert!(!PriceProvider::pair_exists(DOT, HDX));
+    });
+}
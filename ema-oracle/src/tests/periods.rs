@@ -0,0 +1,118 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{DOT, HDX};
+use super::*;
+
+use hydra_dx_math::ema::smoothing_from_period;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn period_length_should_use_configured_lengths() {
+    assert_eq!(EmaOracle::period_length(LastBlock), 1);
+    assert_eq!(EmaOracle::period_length(Short), 9);
+    assert_eq!(EmaOracle::period_length(TenMinutes), 100);
+    assert_eq!(EmaOracle::period_length(Hour), 600);
+    assert_eq!(EmaOracle::period_length(Day), 14_400);
+    assert_eq!(EmaOracle::period_length(Week), 100_800);
+    assert_eq!(EmaOracle::period_length(Custom(50)), 50);
+}
+
+#[test]
+fn period_length_should_be_at_least_one_block() {
+    assert_eq!(EmaOracle::period_length(Custom(0)), LAST_BLOCK_PERIOD_LENGTH);
+}
+
+#[test]
+fn block_time_period_lengths_should_scale_with_block_time() {
+    type TwelveSecondBlocks = BlockTimePeriodLengths<ConstU32<12>>;
+
+    assert_eq!(TwelveSecondBlocks::convert(Short), 9);
+    assert_eq!(TwelveSecondBlocks::convert(TenMinutes), 50);
+    assert_eq!(TwelveSecondBlocks::convert(Hour), 300);
+    assert_eq!(TwelveSecondBlocks::convert(Day), 7_200);
+    assert_eq!(TwelveSecondBlocks::convert(Week), 50_400);
+    assert_eq!(TwelveSecondBlocks::convert(Custom(42)), 42);
+}
+
+#[test]
+fn smoothing_should_be_derived_from_period_length() {
+    assert_eq!(into_smoothing(50), smoothing_from_period(50));
+    assert_eq!(into_smoothing(0), smoothing_from_period(1));
+}
+
+#[test]
+fn supported_period_lengths_should_be_sorted_and_include_last_block() {
+    assert_eq!(EmaOracle::supported_period_lengths(), vec![1, 50, 100, 14_400, 100_800]);
+}
+
+#[test]
+fn custom_period_oracle_should_be_tracked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+
+        assert!(Oracles::<Test>::contains_key((SOURCE, ordered_pair(HDX, DOT), 50)));
+
+        System::set_block_number(2);
+        let entry = EmaOracle::get_entry(HDX, DOT, Custom(50), SOURCE).unwrap();
+        assert_eq!(entry.price, Price::new(2_000, 1_000));
+        assert_eq!(entry.oracle_age, 0);
+    });
+}
+
+#[test]
+fn custom_period_should_share_oracle_with_named_period_of_same_length() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 3_000, 1_000
+        ));
+        EmaOracle::on_finalize(2);
+        System::set_block_number(20);
+
+        assert_eq!(
+            EmaOracle::get_entry(HDX, DOT, Custom(100), SOURCE),
+            EmaOracle::get_entry(HDX, DOT, TenMinutes, SOURCE)
+        );
+    });
+}
+
+#[test]
+fn get_entry_should_fail_for_untracked_custom_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            EmaOracle::get_entry(HDX, DOT, Custom(30), SOURCE),
+            Err(OracleError::NotPresent)
+        );
+    });
+}
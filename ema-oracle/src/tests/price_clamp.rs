@@ -0,0 +1,141 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{CLAMPED_SOURCE, DOT, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+/// Record a trade of `HDX/DOT` with the given liquidity and finalize the block.
+fn trade(source: Source, block: BlockNumber, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        source,
+        HDX,
+        DOT,
+        1_000,
+        1_000,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn last_block_price(source: Source) -> (u128, u128) {
+    Oracles::<Test>::get((source, ordered_pair(HDX, DOT), LAST_BLOCK_PERIOD_LENGTH))
+        .map(|(entry, _)| entry.price.into())
+        .unwrap()
+}
+
+fn price_clamped_events() -> Vec<TestEvent> {
+    System::events()
+        .into_iter()
+        .map(|record| record.event)
+        .filter(|event| matches!(event, TestEvent::EmaOracle(Event::PriceClamped { .. })))
+        .collect()
+}
+
+#[test]
+fn price_above_max_change_should_be_clamped() {
+    new_test_ext().execute_with(|| {
+        trade(CLAMPED_SOURCE, 1, 1_000, 1_000);
+
+        System::set_block_number(2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            CLAMPED_SOURCE,
+            HDX,
+            DOT,
+            1_000,
+            1_000,
+            2_000,
+            1_000
+        ));
+        let incoming_price = get_accumulator_entry(CLAMPED_SOURCE, (HDX, DOT)).unwrap().price;
+        EmaOracle::on_finalize(2);
+
+        assert_eq!(last_block_price(CLAMPED_SOURCE), (1_100_000_000, 1_000_000_000));
+        System::assert_last_event(
+            Event::PriceClamped {
+                source: CLAMPED_SOURCE,
+                assets: ordered_pair(HDX, DOT),
+                incoming_price,
+                clamped_price: (1_100_000_000, 1_000_000_000).into(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn price_below_max_change_should_be_clamped() {
+    new_test_ext().execute_with(|| {
+        trade(CLAMPED_SOURCE, 1, 1_000, 1_000);
+        trade(CLAMPED_SOURCE, 2, 500, 1_000);
+
+        assert_eq!(last_block_price(CLAMPED_SOURCE), (900_000_000, 1_000_000_000));
+        assert_eq!(price_clamped_events().len(), 1);
+    });
+}
+
+#[test]
+fn price_within_max_change_should_not_be_clamped() {
+    new_test_ext().execute_with(|| {
+        trade(CLAMPED_SOURCE, 1, 1_000, 1_000);
+        trade(CLAMPED_SOURCE, 2, 1_050, 1_000);
+
+        assert_eq!(last_block_price(CLAMPED_SOURCE), (1_050, 1_000));
+        assert!(price_clamped_events().is_empty());
+    });
+}
+
+#[test]
+fn price_of_first_entry_should_not_be_clamped() {
+    new_test_ext().execute_with(|| {
+        trade(CLAMPED_SOURCE, 1, 2_000, 1_000);
+
+        assert_eq!(last_block_price(CLAMPED_SOURCE), (2_000, 1_000));
+        assert!(price_clamped_events().is_empty());
+    });
+}
+
+#[test]
+fn price_should_not_be_clamped_when_source_has_no_max_change() {
+    new_test_ext().execute_with(|| {
+        trade(SOURCE, 1, 1_000, 1_000);
+        trade(SOURCE, 2, 2_000, 1_000);
+
+        assert_eq!(last_block_price(SOURCE), (2_000, 1_000));
+        assert!(price_clamped_events().is_empty());
+    });
+}
+
+#[test]
+fn clamped_price_should_be_integrated_into_all_periods() {
+    new_test_ext().execute_with(|| {
+        trade(CLAMPED_SOURCE, 1, 1_000, 1_000);
+        trade(CLAMPED_SOURCE, 2, 2_000, 1_000);
+        System::set_block_number(3);
+
+        for period in supported_periods() {
+            let price = EmaOracle::get_price(HDX, DOT, period, CLAMPED_SOURCE).unwrap().0;
+            assert!(
+                crate::compare_prices(price, (11, 10).into()) != sp_std::cmp::Ordering::Greater,
+                "Price of {period:?} oracle should not exceed the clamped price."
+            );
+        }
+    });
+}
@@ -0,0 +1,289 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{ACA, DOT, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+const OTHER_SOURCE: Source = *b"othersrc";
+
+fn path(hops: Vec<(Source, AssetId)>) -> PricePath {
+    hops.try_into().unwrap()
+}
+
+/// Record trades so that `HDX/DOT` is priced at 2 and `DOT/ACA` at 1/4.
+fn trade_hops(block: BlockNumber) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+    ));
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        OTHER_SOURCE,
+        DOT,
+        ACA,
+        1_000,
+        4_000,
+        1_000,
+        4_000
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn set_hdx_aca_path() {
+    assert_ok!(EmaOracle::set_price_path(
+        RuntimeOrigin::root(),
+        HDX,
+        ACA,
+        path(vec![(SOURCE, DOT), (OTHER_SOURCE, ACA)])
+    ));
+}
+
+fn into_tuple(price: Price) -> (u128, u128) {
+    price.into()
+}
+
+#[test]
+fn set_price_path_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        set_hdx_aca_path();
+
+        let expected = path(vec![(SOURCE, DOT), (OTHER_SOURCE, ACA)]);
+        assert_eq!(EmaOracle::price_path((HDX, ACA)), Some(expected.clone()));
+        System::assert_last_event(
+            Event::PricePathSet {
+                assets: (HDX, ACA),
+                path: expected,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn set_price_path_should_store_reversed_path_when_assets_are_not_ordered() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EmaOracle::set_price_path(
+            RuntimeOrigin::root(),
+            ACA,
+            HDX,
+            path(vec![(OTHER_SOURCE, DOT), (SOURCE, HDX)])
+        ));
+
+        assert_eq!(
+            EmaOracle::price_path((HDX, ACA)),
+            Some(path(vec![(SOURCE, DOT), (OTHER_SOURCE, ACA)]))
+        );
+    });
+}
+
+#[test]
+fn set_price_path_should_fail_when_path_is_invalid() {
+    new_test_ext().execute_with(|| {
+        // empty path
+        assert_noop!(
+            EmaOracle::set_price_path(RuntimeOrigin::root(), HDX, ACA, path(vec![])),
+            Error::<Test>::InvalidPricePath
+        );
+        // path does not lead to `asset_b`
+        assert_noop!(
+            EmaOracle::set_price_path(RuntimeOrigin::root(), HDX, ACA, path(vec![(SOURCE, DOT)])),
+            Error::<Test>::InvalidPricePath
+        );
+        // hop to the same asset
+        assert_noop!(
+            EmaOracle::set_price_path(
+                RuntimeOrigin::root(),
+                HDX,
+                ACA,
+                path(vec![(SOURCE, HDX), (SOURCE, ACA)])
+            ),
+            Error::<Test>::InvalidPricePath
+        );
+        // same assets
+        assert_noop!(
+            EmaOracle::set_price_path(RuntimeOrigin::root(), HDX, HDX, path(vec![(SOURCE, HDX)])),
+            Error::<Test>::InvalidPricePath
+        );
+    });
+}
+
+#[test]
+fn set_price_path_should_fail_when_origin_is_not_authority() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::set_price_path(
+                RuntimeOrigin::signed(1),
+                HDX,
+                ACA,
+                path(vec![(SOURCE, DOT), (OTHER_SOURCE, ACA)])
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn remove_price_path_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        set_hdx_aca_path();
+
+        assert_ok!(EmaOracle::remove_price_path(RuntimeOrigin::root(), (ACA, HDX)));
+
+        assert_eq!(EmaOracle::price_path((HDX, ACA)), None);
+        System::assert_last_event(Event::PricePathRemoved { assets: (HDX, ACA) }.into());
+    });
+}
+
+#[test]
+fn remove_price_path_should_fail_when_path_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::remove_price_path(RuntimeOrigin::root(), (HDX, ACA)),
+            Error::<Test>::PricePathNotFound
+        );
+    });
+}
+
+#[test]
+fn remove_price_path_should_fail_when_origin_is_not_authority() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+
+        assert_noop!(
+            EmaOracle::remove_price_path(RuntimeOrigin::signed(1), (HDX, ACA)),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn get_path_price_should_compose_prices_along_the_path() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        trade_hops(1);
+        System::set_block_number(2);
+
+        let (price, age) = EmaOracle::get_path_price(HDX, ACA, LastBlock).unwrap();
+
+        assert_eq!(into_tuple(price), (2_000 * 1_000, 1_000 * 4_000));
+        assert_eq!(age, 0);
+    });
+}
+
+#[test]
+fn get_path_price_should_invert_price_when_assets_are_not_ordered() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        trade_hops(1);
+        System::set_block_number(2);
+
+        let (price, _) = EmaOracle::get_path_price(ACA, HDX, LastBlock).unwrap();
+
+        assert_eq!(into_tuple(price), (1_000 * 4_000, 2_000 * 1_000));
+    });
+}
+
+#[test]
+fn get_path_price_should_return_oldest_oracle_age() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        trade_hops(5);
+        System::set_block_number(6);
+
+        let (_, age) = EmaOracle::get_path_price(HDX, ACA, TenMinutes).unwrap();
+
+        let hdx_dot_age = EmaOracle::get_entry(HDX, DOT, TenMinutes, SOURCE).unwrap().oracle_age;
+        let dot_aca_age = EmaOracle::get_entry(DOT, ACA, TenMinutes, OTHER_SOURCE)
+            .unwrap()
+            .oracle_age;
+        assert!(hdx_dot_age > dot_aca_age);
+        assert_eq!(age, hdx_dot_age);
+    });
+}
+
+#[test]
+fn get_path_price_should_fail_when_hop_is_missing() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            EmaOracle::get_path_price(HDX, ACA, LastBlock),
+            Err(OracleError::NotPresent)
+        );
+    });
+}
+
+#[test]
+fn get_path_price_should_fail_when_hop_is_stale() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        trade_hops(1);
+        let max_age = <<Test as crate::Config>::MaxPricePathAge as Get<BlockNumber>>::get();
+
+        System::set_block_number(max_age + 2);
+        assert_ok!(EmaOracle::get_path_price(HDX, ACA, LastBlock));
+
+        System::set_block_number(max_age + 3);
+        assert_eq!(EmaOracle::get_path_price(HDX, ACA, LastBlock), Err(OracleError::Stale));
+    });
+}
+
+#[test]
+fn get_path_price_should_fail_when_path_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        trade_hops(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            EmaOracle::get_path_price(HDX, ACA, LastBlock),
+            Err(OracleError::NotPresent)
+        );
+        assert_eq!(
+            EmaOracle::get_path_price(HDX, HDX, LastBlock),
+            Err(OracleError::SameAsset)
+        );
+    });
+}
+
+#[test]
+fn path_price_oracle_should_return_path_price() {
+    new_test_ext().execute_with(|| {
+        set_hdx_aca_path();
+        trade_hops(1);
+        System::set_block_number(2);
+
+        assert_eq!(
+            PathPriceOracle::<Test>::get_price(HDX, ACA, LastBlock, SOURCE),
+            EmaOracle::get_path_price(HDX, ACA, LastBlock)
+        );
+    });
+}
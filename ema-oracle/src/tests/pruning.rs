@@ -0,0 +1,191 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{removed_oracles, ACA, DOT, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+/// Blocks without update after which oracles are inactive in the mock runtime.
+const PRUNE_AFTER: BlockNumber = 100;
+
+/// Record a trade of the pair and finalize the block.
+fn trade(block: BlockNumber, asset_a: AssetId, asset_b: AssetId) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE, asset_a, asset_b, 1_000, 500, 2_000, 1_000
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn has_entries(asset_a: AssetId, asset_b: AssetId) -> bool {
+    EmaOracle::supported_period_lengths()
+        .into_iter()
+        .any(|period| Oracles::<Test>::contains_key((SOURCE, ordered_pair(asset_a, asset_b), period)))
+}
+
+#[test]
+fn prune_should_remove_entries_of_inactive_oracle() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        // the parent block is the last one without update before the oracle becomes inactive
+        System::set_block_number(1 + PRUNE_AFTER + 2);
+
+        assert_ok!(EmaOracle::prune(RuntimeOrigin::signed(1), SOURCE, (DOT, HDX)));
+
+        assert!(!has_entries(HDX, DOT));
+        assert_eq!(removed_oracles(), vec![(SOURCE, HDX, DOT)]);
+        System::assert_last_event(
+            Event::OraclePruned {
+                source: SOURCE,
+                assets: ordered_pair(HDX, DOT),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn prune_should_fail_when_oracle_is_not_inactive() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        System::set_block_number(1 + PRUNE_AFTER + 1);
+
+        assert_noop!(
+            EmaOracle::prune(RuntimeOrigin::signed(1), SOURCE, (HDX, DOT)),
+            Error::<Test>::OracleNotInactive
+        );
+    });
+}
+
+#[test]
+fn prune_should_fail_when_oracle_has_data_in_current_block() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        System::set_block_number(1 + PRUNE_AFTER + 2);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+
+        assert_noop!(
+            EmaOracle::prune(RuntimeOrigin::signed(1), SOURCE, (HDX, DOT)),
+            Error::<Test>::OracleNotInactive
+        );
+    });
+}
+
+#[test]
+fn prune_should_fail_when_oracle_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1 + PRUNE_AFTER + 2);
+
+        assert_noop!(
+            EmaOracle::prune(RuntimeOrigin::signed(1), SOURCE, (HDX, DOT)),
+            Error::<Test>::OracleNotFound
+        );
+    });
+}
+
+#[test]
+fn prune_should_require_signed_origin() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        System::set_block_number(1 + PRUNE_AFTER + 2);
+
+        assert_noop!(
+            EmaOracle::prune(RuntimeOrigin::none(), SOURCE, (HDX, DOT)),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_prune_only_inactive_oracles() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        trade(50, HDX, ACA);
+        let now = 1 + PRUNE_AFTER + 2;
+        System::set_block_number(now);
+
+        EmaOracle::on_idle(now, Weight::MAX);
+
+        assert!(!has_entries(HDX, DOT));
+        assert!(has_entries(HDX, ACA));
+        assert_eq!(removed_oracles(), vec![(SOURCE, HDX, DOT)]);
+        assert_eq!(EmaOracle::prune_cursor(), None);
+    });
+}
+
+#[test]
+fn on_idle_should_not_prune_without_weight() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        let now = 1 + PRUNE_AFTER + 2;
+        System::set_block_number(now);
+
+        assert_eq!(EmaOracle::on_idle(now, Weight::zero()), Weight::zero());
+
+        assert!(has_entries(HDX, DOT));
+        assert!(removed_oracles().is_empty());
+    });
+}
+
+#[test]
+fn on_idle_should_continue_after_cursor_when_out_of_weight() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        trade(2, HDX, ACA);
+        let now = 2 + PRUNE_AFTER + 2;
+        System::set_block_number(now);
+
+        // the mock runtime does not charge for database access
+        EmaOracle::on_idle(
+            now,
+            <Test as crate::Config>::WeightInfo::prune(EmaOracle::supported_period_lengths().len() as u32),
+        );
+
+        assert_eq!(removed_oracles().len(), 1);
+        let (_, asset_a, asset_b) = removed_oracles()[0];
+        assert_eq!(EmaOracle::prune_cursor(), Some((SOURCE, (asset_a, asset_b))));
+
+        EmaOracle::on_idle(now, Weight::MAX);
+
+        assert!(!has_entries(HDX, DOT));
+        assert!(!has_entries(HDX, ACA));
+        assert_eq!(removed_oracles().len(), 2);
+        assert_eq!(EmaOracle::prune_cursor(), None);
+    });
+}
+
+#[test]
+fn pruned_oracle_should_be_initialised_again_by_next_trade() {
+    new_test_ext().execute_with(|| {
+        trade(1, HDX, DOT);
+        let now = 1 + PRUNE_AFTER + 2;
+        System::set_block_number(now);
+        assert_ok!(EmaOracle::prune(RuntimeOrigin::signed(1), SOURCE, (HDX, DOT)));
+
+        trade(now, HDX, DOT);
+
+        for period in EmaOracle::supported_period_lengths() {
+            let (entry, init) = Oracles::<Test>::get((SOURCE, ordered_pair(HDX, DOT), period)).unwrap();
+            assert_eq!(init, now);
+            assert_eq!(entry.timestamp, now);
+            assert_eq!(entry.volatility, Volatility::zero());
+        }
+    });
+}
@@ -0,0 +1,128 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{ACA, DOT, HDX};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+const OTHER_SOURCE: Source = *b"othersrc";
+
+#[test]
+fn get_entries_should_return_entries_of_all_supported_periods() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(100);
+
+        let entries = EmaOracle::get_entries(SOURCE, HDX, DOT);
+
+        let expected: Vec<_> = supported_periods()
+            .into_iter()
+            .map(|period| (period, EmaOracle::get_entry(HDX, DOT, period, SOURCE).unwrap()))
+            .collect();
+        assert_eq!(entries.len(), supported_periods().len());
+        assert_eq!(entries, expected);
+    });
+}
+
+#[test]
+fn get_entries_should_respect_asset_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        let entries = EmaOracle::get_entries(SOURCE, DOT, HDX);
+
+        assert_eq!(
+            entries.first(),
+            Some(&(
+                LastBlock,
+                AggregatedEntry {
+                    price: Price::new(1_000, 2_000),
+                    volume: Volume::from_a_out_b_in(500, 1_000),
+                    liquidity: Liquidity::new(1_000, 2_000),
+                    oracle_age: 0,
+                }
+            ))
+        );
+    });
+}
+
+#[test]
+fn get_entries_should_return_nothing_when_oracle_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert!(EmaOracle::get_entries(SOURCE, HDX, ACA).is_empty());
+        assert!(EmaOracle::get_entries(OTHER_SOURCE, HDX, DOT).is_empty());
+        assert!(EmaOracle::get_entries(SOURCE, HDX, HDX).is_empty());
+    });
+}
+
+#[test]
+fn tracked_pairs_should_list_each_pair_of_source_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, ACA, HDX, 1_000, 500, 2_000, 1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            OTHER_SOURCE,
+            DOT,
+            ACA,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        EmaOracle::on_finalize(1);
+
+        let mut pairs = EmaOracle::tracked_pairs(SOURCE);
+        pairs.sort();
+
+        assert_eq!(pairs, vec![ordered_pair(HDX, DOT), ordered_pair(ACA, HDX)]);
+        assert_eq!(EmaOracle::tracked_pairs(OTHER_SOURCE), vec![ordered_pair(DOT, ACA)]);
+    });
+}
+
+#[test]
+fn tracked_pairs_should_not_include_pairs_not_yet_recorded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            SOURCE, HDX, DOT, 1_000, 500, 2_000, 1_000
+        ));
+
+        assert!(EmaOracle::tracked_pairs(SOURCE).is_empty());
+    });
+}
@@ -0,0 +1,145 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{DOT, HDX};
+use super::*;
+
+use frame_support::sp_runtime::FixedU128;
+use pretty_assertions::assert_eq;
+
+/// Record a trade of `HDX/DOT` with the given liquidity and finalize the block.
+fn trade(block: BlockNumber, liquidity_hdx: Balance, liquidity_dot: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE,
+        HDX,
+        DOT,
+        1_000,
+        1_000,
+        liquidity_hdx,
+        liquidity_dot
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+fn volatility(period: OraclePeriod) -> Volatility {
+    EmaOracle::get_volatility(HDX, DOT, period, SOURCE).unwrap().0
+}
+
+#[test]
+fn squared_relative_change_should_work() {
+    assert_eq!(
+        squared_relative_change(Price::new(1_000, 1_000), Price::new(1_100, 1_000)),
+        FixedU128::from_rational(1, 100)
+    );
+    assert_eq!(
+        squared_relative_change(Price::new(1_000, 1_000), Price::new(900, 1_000)),
+        FixedU128::from_rational(1, 100)
+    );
+    assert_eq!(
+        squared_relative_change(Price::new(2_000, 1_000), Price::new(4_000, 2_000)),
+        Volatility::zero()
+    );
+    assert_eq!(
+        squared_relative_change(Price::new(0, 1), Price::new(1_000, 1)),
+        Volatility::zero()
+    );
+}
+
+#[test]
+fn volatility_should_be_zero_when_price_is_constant() {
+    new_test_ext().execute_with(|| {
+        trade(1, 1_000, 1_000);
+        trade(2, 2_000, 2_000);
+        System::set_block_number(3);
+
+        for period in supported_periods() {
+            assert_eq!(volatility(period), Volatility::zero());
+        }
+    });
+}
+
+#[test]
+fn last_block_volatility_should_be_squared_relative_price_change() {
+    new_test_ext().execute_with(|| {
+        trade(1, 1_000, 1_000);
+        trade(2, 1_100, 1_000);
+        System::set_block_number(3);
+
+        assert_eq!(volatility(LastBlock), FixedU128::from_rational(1, 100));
+    });
+}
+
+#[test]
+fn volatility_of_longer_periods_should_be_smoothed() {
+    new_test_ext().execute_with(|| {
+        trade(1, 1_000, 1_000);
+        trade(2, 1_100, 1_000);
+        System::set_block_number(3);
+
+        let ten_minutes = volatility(TenMinutes);
+        let day = volatility(Day);
+        assert!(ten_minutes > Volatility::zero());
+        assert!(ten_minutes < volatility(LastBlock));
+        assert!(day < ten_minutes);
+    });
+}
+
+#[test]
+fn volatility_should_decay_when_price_stays_constant() {
+    new_test_ext().execute_with(|| {
+        trade(1, 1_000, 1_000);
+        trade(2, 1_100, 1_000);
+        System::set_block_number(3);
+        let ten_minutes = volatility(TenMinutes);
+
+        System::set_block_number(50);
+
+        assert_eq!(volatility(LastBlock), Volatility::zero());
+        assert!(volatility(TenMinutes) < ten_minutes);
+    });
+}
+
+#[test]
+fn volatility_should_not_depend_on_asset_order() {
+    new_test_ext().execute_with(|| {
+        trade(1, 1_000, 1_000);
+        trade(2, 1_100, 1_000);
+        System::set_block_number(3);
+
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, DOT, TenMinutes, SOURCE),
+            EmaOracle::get_volatility(DOT, HDX, TenMinutes, SOURCE)
+        );
+    });
+}
+
+#[test]
+fn get_volatility_should_fail_when_oracle_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(2);
+
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, DOT, LastBlock, SOURCE),
+            Err(OracleError::NotPresent)
+        );
+        assert_eq!(
+            EmaOracle::get_volatility(HDX, HDX, LastBlock, SOURCE),
+            Err(OracleError::SameAsset)
+        );
+    });
+}
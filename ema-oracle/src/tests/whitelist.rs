@@ -0,0 +1,252 @@
+// This file is part of pallet-ema-oracle.
+
+// Copyright (C) 2022-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::mock::{removed_oracles, ACA, DOT, HDX, RESTRICTED_SOURCE};
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+fn oracle_exists(source: Source, assets: (AssetId, AssetId), period: OraclePeriod) -> bool {
+    Oracles::<Test>::contains_key((
+        source,
+        ordered_pair(assets.0, assets.1),
+        EmaOracle::period_length(period),
+    ))
+}
+
+#[test]
+fn add_oracle_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(EmaOracle::add_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (DOT, HDX)
+        ));
+
+        assert!(EmaOracle::whitelisted_assets().contains(&(RESTRICTED_SOURCE, ordered_pair(HDX, DOT))));
+        System::assert_last_event(
+            Event::AddedToWhitelist {
+                source: RESTRICTED_SOURCE,
+                assets: ordered_pair(HDX, DOT),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn add_oracle_should_fail_when_origin_is_not_authority() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::add_oracle(RuntimeOrigin::signed(1), RESTRICTED_SOURCE, (HDX, DOT)),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn add_oracle_should_fail_when_whitelist_is_full() {
+    new_test_ext().execute_with(|| {
+        let max_entries = <<Test as crate::Config>::MaxWhitelistedEntries as Get<u32>>::get();
+        for i in 0..max_entries {
+            assert_ok!(EmaOracle::add_oracle(
+                RuntimeOrigin::root(),
+                RESTRICTED_SOURCE,
+                (i, i + 1)
+            ));
+        }
+
+        assert_noop!(
+            EmaOracle::add_oracle(RuntimeOrigin::root(), RESTRICTED_SOURCE, (HDX, DOT)),
+            Error::<Test>::TooManyWhitelistedOracles
+        );
+    });
+}
+
+#[test]
+fn on_trade_should_be_ignored_when_oracle_is_not_whitelisted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            RESTRICTED_SOURCE,
+            HDX,
+            DOT,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        EmaOracle::on_finalize(1);
+
+        assert_eq!(get_accumulator_entry(RESTRICTED_SOURCE, (HDX, DOT)), None);
+        assert!(!oracle_exists(RESTRICTED_SOURCE, (HDX, DOT), LastBlock));
+    });
+}
+
+#[test]
+fn on_trade_should_be_recorded_when_oracle_is_whitelisted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EmaOracle::add_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (HDX, DOT)
+        ));
+
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            RESTRICTED_SOURCE,
+            HDX,
+            DOT,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            RESTRICTED_SOURCE,
+            HDX,
+            ACA,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        EmaOracle::on_finalize(1);
+
+        for period in supported_periods() {
+            assert!(oracle_exists(RESTRICTED_SOURCE, (HDX, DOT), period));
+            assert!(!oracle_exists(RESTRICTED_SOURCE, (HDX, ACA), period));
+        }
+    });
+}
+
+#[test]
+fn remove_oracle_should_delete_entries_of_all_supported_periods() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EmaOracle::add_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (HDX, DOT)
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            RESTRICTED_SOURCE,
+            HDX,
+            DOT,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+        EmaOracle::on_finalize(1);
+        System::set_block_number(2);
+
+        assert_ok!(EmaOracle::remove_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (DOT, HDX)
+        ));
+
+        assert!(!EmaOracle::whitelisted_assets().contains(&(RESTRICTED_SOURCE, ordered_pair(HDX, DOT))));
+        for period in supported_periods() {
+            assert!(!oracle_exists(RESTRICTED_SOURCE, (HDX, DOT), period));
+        }
+        assert_eq!(removed_oracles(), vec![(RESTRICTED_SOURCE, HDX, DOT)]);
+        System::assert_last_event(
+            Event::RemovedFromWhitelist {
+                source: RESTRICTED_SOURCE,
+                assets: ordered_pair(HDX, DOT),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn remove_oracle_should_discard_data_of_current_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EmaOracle::add_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (HDX, DOT)
+        ));
+        assert_ok!(OnActivityHandler::<Test>::on_trade(
+            RESTRICTED_SOURCE,
+            HDX,
+            DOT,
+            1_000,
+            500,
+            2_000,
+            1_000
+        ));
+
+        assert_ok!(EmaOracle::remove_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (HDX, DOT)
+        ));
+        EmaOracle::on_finalize(1);
+
+        assert_eq!(get_accumulator_entry(RESTRICTED_SOURCE, (HDX, DOT)), None);
+        assert!(!oracle_exists(RESTRICTED_SOURCE, (HDX, DOT), LastBlock));
+    });
+}
+
+#[test]
+fn remove_oracle_should_fail_when_oracle_is_not_whitelisted() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EmaOracle::remove_oracle(RuntimeOrigin::root(), RESTRICTED_SOURCE, (HDX, DOT)),
+            Error::<Test>::OracleNotFound
+        );
+    });
+}
+
+#[test]
+fn remove_oracle_should_fail_when_origin_is_not_authority() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EmaOracle::add_oracle(
+            RuntimeOrigin::root(),
+            RESTRICTED_SOURCE,
+            (HDX, DOT)
+        ));
+
+        assert_noop!(
+            EmaOracle::remove_oracle(RuntimeOrigin::signed(1), RESTRICTED_SOURCE, (HDX, DOT)),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn genesis_oracles_should_be_whitelisted() {
+    ExtBuilder::default()
+        .with_initial_data(vec![(
+            RESTRICTED_SOURCE,
+            (DOT, HDX),
+            (1_000, 500).into(),
+            Liquidity::new(1_000, 500),
+        )])
+        .build()
+        .execute_with(|| {
+            assert!(EmaOracle::whitelisted_assets().contains(&(RESTRICTED_SOURCE, ordered_pair(HDX, DOT))));
+        });
+}
@@ -16,12 +16,18 @@
 // limitations under the License.
 
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::sp_runtime::RuntimeDebug;
-use hydra_dx_math::ema::{calculate_new_by_integrating_incoming, update_outdated_to_current, EmaPrice};
+use frame_support::sp_runtime::{traits::Convert, RuntimeDebug};
+use frame_support::traits::{ConstU32, Get};
+use hydra_dx_math::ema::{
+    calculate_new_by_integrating_incoming, smoothing_from_period, update_outdated_to_current, EmaPrice,
+};
 use hydra_dx_math::types::Fraction;
 use hydradx_traits::{AggregatedEntry, Liquidity, Volume};
 use scale_info::TypeInfo;
-use sp_arithmetic::traits::{AtLeast32BitUnsigned, SaturatedConversion, UniqueSaturatedInto};
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, One, SaturatedConversion, Saturating, UniqueSaturatedInto, Zero};
+use sp_arithmetic::{FixedPointNumber, FixedU128};
+use sp_core::U256;
+use sp_std::marker::PhantomData;
 
 pub use hydradx_traits::{OraclePeriod, Source};
 
@@ -34,6 +40,22 @@ pub type AssetId = u32;
 pub type Balance = u128;
 /// A price is a tuple of two `u128`s representing the numerator and denominator of a rational number.
 pub type Price = EmaPrice;
+/// Volatility of a price, i.e. the exponentially smoothed variance of its relative changes.
+pub type Volatility = FixedU128;
+/// Mode of combining the prices of an asset pair from multiple sources.
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum AggregationMode {
+    /// Mean of the prices weighted by the liquidity of the first asset of the pair.
+    LiquidityWeightedMean,
+    /// Median of the prices.
+    Median,
+}
+
+/// Length of an oracle period in blocks. Determines the smoothing factor of the oracle.
+pub type PeriodLength = u32;
+
+/// Length of the `LastBlock` oracle period.
+pub const LAST_BLOCK_PERIOD_LENGTH: PeriodLength = 1;
 
 /// A type representing data produced by a trade or liquidity event. Timestamped to the block where
 /// it was created.
@@ -43,6 +65,7 @@ pub struct OracleEntry<BlockNumber> {
     pub price: Price,
     pub volume: Volume<Balance>,
     pub liquidity: Liquidity<Balance>,
+    pub volatility: Volatility,
     pub timestamp: BlockNumber,
 }
 
@@ -82,6 +105,8 @@ where
             price,
             volume,
             liquidity,
+            // The squared relative changes of the inverted price are approximately the same.
+            volatility: self.volatility,
             timestamp: self.timestamp,
         }
     }
@@ -95,10 +120,12 @@ where
         self.timestamp = incoming.timestamp;
     }
 
-    /// Fast forward the oracle value to `new_timestamp`. Updates the timestamp and resets the volume.
+    /// Fast forward the oracle value to `new_timestamp`. Updates the timestamp and resets the volume
+    /// and volatility, as the price stays constant.
     pub fn fast_forward_to(&mut self, new_timestamp: BlockNumber) {
         self.timestamp = new_timestamp;
         self.volume = Volume::default();
+        self.volatility = Volatility::zero();
     }
 
     /// Determine a new entry based on `self` and a previous entry. Adds the volumes together and
@@ -109,42 +136,45 @@ where
             price: self.price,
             volume,
             liquidity: self.liquidity,
+            volatility: self.volatility,
             timestamp: self.timestamp,
         }
     }
 
     /// Determine the next oracle entry based on a previous (`self`) and an `incoming` entry as well as
-    /// a `period`.
+    /// a `period` length.
     ///
     /// Returns `None` if any of the calculations fail (including the `incoming` entry not being
     /// one iteration (block) more recent than `self`).
     ///
     /// The period is used to determine the smoothing factor alpha for an exponential moving average.
-    pub fn calculate_new_by_integrating_incoming(&self, period: OraclePeriod, incoming: &Self) -> Option<Self> {
+    pub fn calculate_new_by_integrating_incoming(&self, period: PeriodLength, incoming: &Self) -> Option<Self> {
         // incoming should be one step ahead of the previous value
         if !incoming.timestamp.checked_sub(&self.timestamp)?.is_one() {
             return None;
         }
-        if period == OraclePeriod::LastBlock {
+        if period == LAST_BLOCK_PERIOD_LENGTH {
             return Some(incoming.clone());
         }
         // determine smoothing factor
         let smoothing = into_smoothing(period);
         let (price, volume, liquidity) =
             calculate_new_by_integrating_incoming(self.raw_data(), incoming.raw_data(), smoothing);
+        let volatility = volatility_ema(self.volatility, incoming.volatility, smoothing);
 
         Some(Self {
             price,
             volume: volume.into(),
             liquidity: liquidity.into(),
+            volatility,
             timestamp: incoming.timestamp,
         })
     }
 
-    /// Update `self` based on a previous (`self`) and an `incoming` oracle entry as well as  a `period`.
+    /// Update `self` based on a previous (`self`) and an `incoming` oracle entry as well as  a `period` length.
     pub fn update_to_new_by_integrating_incoming(
         &mut self,
-        period: OraclePeriod,
+        period: PeriodLength,
         incoming: &Self,
     ) -> Option<&mut Self> {
         *self = self.calculate_new_by_integrating_incoming(period, incoming)?;
@@ -152,7 +182,7 @@ where
     }
 
     /// Determine the current intended oracle entry based on a previous (`self`) and an `update_with` entry as well as
-    /// a `period`.
+    /// a `period` length.
     ///
     /// Returns `None` if any of the calculations fail (including the `update_with` entry not being
     /// more recent than `self`).
@@ -160,12 +190,12 @@ where
     /// The period is used to determine the smoothing factor alpha for an exponential moving average.
     ///
     /// Uses the difference between the `timestamp`s to determine the time (i.e. iterations) to cover.
-    pub fn calculate_current_from_outdated(&self, period: OraclePeriod, update_with: &Self) -> Option<Self> {
+    pub fn calculate_current_from_outdated(&self, period: PeriodLength, update_with: &Self) -> Option<Self> {
         let iterations = update_with.timestamp.checked_sub(&self.timestamp)?;
         if iterations.is_zero() {
             return None;
         }
-        if period == OraclePeriod::LastBlock {
+        if period == LAST_BLOCK_PERIOD_LENGTH {
             return Some(update_with.clone());
         }
         // determine smoothing factor
@@ -176,42 +206,125 @@ where
             (update_with.price, update_with.liquidity.into()),
             smoothing,
         );
+        // The price stays constant in the skipped blocks, so the volatility decays toward zero.
+        let volatility = decayed_volatility(self.volatility, iterations.saturated_into(), smoothing);
 
         Some(Self {
             price,
             volume: volume.into(),
             liquidity: liquidity.into(),
+            volatility,
             timestamp: update_with.timestamp,
         })
     }
 
-    /// Update `self` based on a previous (`self`) and an `update_with` entry as well as a `period`.
+    /// Update `self` based on a previous (`self`) and an `update_with` entry as well as a `period` length.
     /// See [`calculate_current_from_outdated`].
-    pub fn update_outdated_to_current(&mut self, period: OraclePeriod, update_with: &Self) -> Option<&mut Self> {
+    pub fn update_outdated_to_current(&mut self, period: PeriodLength, update_with: &Self) -> Option<&mut Self> {
         *self = self.calculate_current_from_outdated(period, update_with)?;
         Some(self)
     }
 }
 
-/// Convert a given `period` into the smoothing factor used in the weighted average.
-/// See [`check_period_smoothing_factors`] for how the values are generated.
-pub fn into_smoothing(period: OraclePeriod) -> Fraction {
-    match period {
-        OraclePeriod::LastBlock => Fraction::from_bits(170141183460469231731687303715884105728),
-        OraclePeriod::Short => Fraction::from_bits(34028236692093846346337460743176821146),
-        OraclePeriod::TenMinutes => Fraction::from_bits(3369132345751865974884897103284833777),
-        OraclePeriod::Hour => Fraction::from_bits(566193622164623067326746434994622648),
-        OraclePeriod::Day => Fraction::from_bits(23629079016800115510268356880200556),
-        OraclePeriod::Week => Fraction::from_bits(3375783642235081630771268215908257),
+/// Convert a given `period` length into the smoothing factor used in the weighted average.
+///
+/// The smoothing factor is `2 / (period + 1)`. A zero length is treated as the `LastBlock` period.
+pub fn into_smoothing(period: PeriodLength) -> Fraction {
+    smoothing_from_period(u64::from(period.max(LAST_BLOCK_PERIOD_LENGTH)))
+}
+
+/// Convert a smoothing factor into a `FixedU128`.
+fn smoothing_to_fixed(smoothing: Fraction) -> FixedU128 {
+    // `Fraction` has 127 fractional bits.
+    let inner = U256::from(smoothing.to_bits()).saturating_mul(U256::from(FixedU128::DIV)) >> 127;
+    FixedU128::from_inner(inner.low_u128())
+}
+
+/// Integrate `incoming` into the exponential moving average `previous` using `smoothing`.
+fn volatility_ema(previous: Volatility, incoming: Volatility, smoothing: Fraction) -> Volatility {
+    let smoothing = smoothing_to_fixed(smoothing);
+    if incoming >= previous {
+        previous.saturating_add(smoothing.saturating_mul(incoming.saturating_sub(previous)))
+    } else {
+        previous.saturating_sub(smoothing.saturating_mul(previous.saturating_sub(incoming)))
     }
 }
 
+/// Decay `previous` over `iterations` blocks without price change.
+fn decayed_volatility(previous: Volatility, iterations: u64, smoothing: Fraction) -> Volatility {
+    let decay = FixedU128::one().saturating_sub(smoothing_to_fixed(smoothing));
+    let iterations = usize::try_from(iterations).unwrap_or(usize::MAX);
+    previous.saturating_mul(decay.saturating_pow(iterations))
+}
+
+/// Return the squared relative change from `previous` to `current` price.
+///
+/// Returns zero if the `previous` price is zero.
+pub fn squared_relative_change(previous: Price, current: Price) -> Volatility {
+    if previous.is_zero() {
+        return Volatility::zero();
+    }
+    let (previous_n, previous_d) = previous.into();
+    let (current_n, current_d) = current.into();
+    // The products of two `u128` always fit into `U256`.
+    let (ratio_n, ratio_d) = price_from_u256(
+        U256::from(current_n).saturating_mul(U256::from(previous_d)),
+        U256::from(current_d).saturating_mul(U256::from(previous_n)),
+    )
+    .into();
+    let ratio = FixedU128::checked_from_rational(ratio_n, ratio_d).unwrap_or_else(|| FixedU128::from_inner(u128::MAX));
+    let change = if ratio >= FixedU128::one() {
+        ratio.saturating_sub(FixedU128::one())
+    } else {
+        FixedU128::one().saturating_sub(ratio)
+    };
+    change.saturating_mul(change)
+}
+
+/// Convert the fraction `n / d` into a `Price`, dropping the least significant bits of both if
+/// they don't fit into `u128`.
+pub(crate) fn price_from_u256(n: U256, d: U256) -> Price {
+    let shift = n.bits().max(d.bits()).saturating_sub(128);
+    let (n, d) = (n >> shift, d >> shift);
+    if d.is_zero() {
+        return (u128::MAX, 1).into();
+    }
+    (n.low_u128(), d.low_u128()).into()
+}
+
+/// Period lengths for a chain producing a block every `BlockTime` seconds.
+///
+/// `LastBlock` covers a single block and `Short` the last nine blocks, independent of the block time.
+/// `Custom` periods are taken as they are.
+pub struct BlockTimePeriodLengths<BlockTime>(PhantomData<BlockTime>);
+
+impl<BlockTime: Get<u32>> Convert<OraclePeriod, PeriodLength> for BlockTimePeriodLengths<BlockTime> {
+    fn convert(period: OraclePeriod) -> PeriodLength {
+        let minute = 60 / BlockTime::get().max(1);
+        let hour = 60 * minute;
+        let day = 24 * hour;
+        match period {
+            OraclePeriod::LastBlock => LAST_BLOCK_PERIOD_LENGTH,
+            OraclePeriod::Short => 9,
+            OraclePeriod::TenMinutes => 10 * minute,
+            OraclePeriod::Hour => hour,
+            OraclePeriod::Day => day,
+            OraclePeriod::Week => 7 * day,
+            OraclePeriod::Custom(length) => length,
+        }
+    }
+}
+
+/// Period lengths of a chain with six second block time.
+pub type DefaultPeriodLengths = BlockTimePeriodLengths<ConstU32<6>>;
+
 impl<BlockNumber> From<(Price, Volume<Balance>, Liquidity<Balance>, BlockNumber)> for OracleEntry<BlockNumber> {
     fn from((price, volume, liquidity, timestamp): (Price, Volume<Balance>, Liquidity<Balance>, BlockNumber)) -> Self {
         Self {
             price,
             volume,
             liquidity,
+            volatility: Volatility::zero(),
             timestamp,
         }
     }
@@ -15,26 +15,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_ema_oracle
+//! Weights for pallet_ema_oracle
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-02-23, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! PLACEHOLDER WEIGHTS - NOT GENERATED BY THE BENCHMARK CLI.
+//! The weights of the hooks, `on_trade`, `on_liquidity_changed` and `get_entry` are taken from the benchmark run of
+//! 2023-02-23 (STEPS: 5, REPEAT: 20, CHAIN: Some("dev")). The weights of the calls were derived by hand from their
+//! storage accesses. `p` is the number of distinct supported period lengths, one oracle entry is removed per period.
+//! Regenerate them with the `pallet-ema-oracle` benchmarks before release:
+//!
+//! target/release/basilisk benchmark pallet --chain=dev --steps=5 --repeat=20 --execution=wasm
+//! --wasm-execution=compiled --heap-pages=4096 --template=.maintain/pallet-weight-template-no-back.hbs
+//! --pallet=pallet_ema_oracle --output=oracle.rs --extrinsic=*
 
-// Executed Command:
-// target/release/basilisk
-// benchmark
-// pallet
-// --chain=dev
-// --steps=5
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --template=.maintain/pallet-weight-template-no-back.hbs
-// --pallet=pallet_ema_oracle
-// --output=oracle.rs
-// --extrinsic=*
 #![allow(unused_parens)]
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
@@ -52,6 +44,14 @@ pub trait WeightInfo {
     fn on_trade_multiple_tokens(b: u32) -> Weight;
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight;
     fn get_entry() -> Weight;
+    fn add_oracle() -> Weight;
+    fn remove_oracle(p: u32) -> Weight;
+    fn set_price_path() -> Weight;
+    fn remove_price_path() -> Weight;
+    fn prune(p: u32) -> Weight;
+    fn add_feeder() -> Weight;
+    fn remove_feeder() -> Weight;
+    fn submit_price() -> Weight;
 }
 
 pub struct BasiliskWeight<T>(PhantomData<T>);
@@ -71,18 +71,59 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
     fn on_trade_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_042_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(507_000 as u64).saturating_mul(b as u64))
-            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
             .saturating_add(T::DbWeight::get().writes(1 as u64))
     }
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_385_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(506_000 as u64).saturating_mul(b as u64))
-            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
             .saturating_add(T::DbWeight::get().writes(1 as u64))
     }
     fn get_entry() -> Weight {
         Weight::from_ref_time(23_575_000 as u64).saturating_add(T::DbWeight::get().reads(2 as u64))
     }
+    fn add_oracle() -> Weight {
+        Weight::from_ref_time(11_827_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn remove_oracle(p: u32) -> Weight {
+        Weight::from_ref_time(24_146_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_200_000 as u64).saturating_mul(p as u64))
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
+            .saturating_add(T::DbWeight::get().writes(2 as u64))
+            .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+    }
+    fn set_price_path() -> Weight {
+        Weight::from_ref_time(13_204_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn remove_price_path() -> Weight {
+        Weight::from_ref_time(14_671_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn prune(p: u32) -> Weight {
+        Weight::from_ref_time(21_889_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_100_000 as u64).saturating_mul(p as u64))
+            .saturating_add(T::DbWeight::get().reads(2 as u64))
+            .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+    }
+    fn add_feeder() -> Weight {
+        Weight::from_ref_time(12_153_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn remove_feeder() -> Weight {
+        Weight::from_ref_time(12_462_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    fn submit_price() -> Weight {
+        Weight::from_ref_time(58_716_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(3 as u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -101,16 +142,57 @@ impl WeightInfo for () {
     fn on_trade_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_042_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(507_000 as u64).saturating_mul(b as u64))
-            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
             .saturating_add(RocksDbWeight::get().writes(1 as u64))
     }
     fn on_liquidity_changed_multiple_tokens(b: u32) -> Weight {
         Weight::from_ref_time(19_385_000 as u64) // Standard Error: 4_000
             .saturating_add(Weight::from_ref_time(506_000 as u64).saturating_mul(b as u64))
-            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
             .saturating_add(RocksDbWeight::get().writes(1 as u64))
     }
     fn get_entry() -> Weight {
         Weight::from_ref_time(23_575_000 as u64).saturating_add(RocksDbWeight::get().reads(2 as u64))
     }
+    fn add_oracle() -> Weight {
+        Weight::from_ref_time(11_827_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn remove_oracle(p: u32) -> Weight {
+        Weight::from_ref_time(24_146_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_200_000 as u64).saturating_mul(p as u64))
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
+            .saturating_add(RocksDbWeight::get().writes(2 as u64))
+            .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+    }
+    fn set_price_path() -> Weight {
+        Weight::from_ref_time(13_204_000 as u64).saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn remove_price_path() -> Weight {
+        Weight::from_ref_time(14_671_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn prune(p: u32) -> Weight {
+        Weight::from_ref_time(21_889_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_100_000 as u64).saturating_mul(p as u64))
+            .saturating_add(RocksDbWeight::get().reads(2 as u64))
+            .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+    }
+    fn add_feeder() -> Weight {
+        Weight::from_ref_time(12_153_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn remove_feeder() -> Weight {
+        Weight::from_ref_time(12_462_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    fn submit_price() -> Weight {
+        Weight::from_ref_time(58_716_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(3 as u64))
+    }
 }
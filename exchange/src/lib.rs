@@ -0,0 +1,756 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Exchange pallet
+//!
+//! Batch-auction exchange which nets opposing trade intentions of an asset pair before touching the AMM.
+//!
+//! ## Overview
+//!
+//! Instead of trading with an AMM pool immediately, users register sell and buy intentions. The amount an
+//! intention sells is reserved at registration, so an account cannot register intentions it cannot pay for.
+//! Intentions of an asset pair are collected during the block and resolved in `on_finalize`:
+//!
+//! 1. reserved amounts are unreserved and amounts of all intentions are re-calculated at the AMM spot price
+//!    of the pair,
+//! 2. intentions selling opposite assets are matched and traded directly between the accounts at the spot price,
+//!    the untraded part of a partially matched intention is matched with the next opposite intention,
+//! 3. only the unmatched remainder of an intention is traded with the AMM pool.
+//!
+//! Directly traded intentions pay the same trade fee as the AMM pool would charge. The fee is transferred to the
+//! pool account. Sell intentions pay the fee from the amount received, buy intentions pay the fee on top of the
+//! amount paid.
+//!
+//! As all intentions of a block are executed at the same price, the order of transactions within a block cannot be
+//! exploited (e.g. by sandwiching).
+//!
+//! Trade limits of an intention are applied proportionally to each of its direct trades and to the AMM remainder.
+//!
+//! ## Dispatchable functions
+//! * `sell` - register an intention to sell an asset.
+//! * `buy` - register an intention to buy an asset.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::HasCompact;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::{dispatch::DispatchResult, ensure};
+use hydradx_traits::{Resolver, AMM};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use sp_core::U256;
+use sp_runtime::traits::{Hash, Zero};
+use sp_runtime::{ArithmeticError, DispatchError};
+use sp_std::collections::vec_deque::VecDeque;
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+pub mod types;
+pub mod weights;
+
+use crate::types::{AssetPair, Balance, ExchangeIntention, Intention, IntentionId, IntentionType};
+use weights::WeightInfo;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Identifier for the class of asset.
+        type AssetId: Member
+            + Parameter
+            + Ord
+            + Default
+            + Copy
+            + HasCompact
+            + MaybeSerializeDeserialize
+            + MaxEncodedLen
+            + TypeInfo;
+
+        /// AMM pool used to determine the spot price and to trade unmatched intentions.
+        type AMMPool: AMM<Self::AccountId, Self::AssetId, AssetPair<Self::AssetId>, Balance>;
+
+        /// Multi currency mechanism, used to reserve the amounts sold by registered intentions.
+        type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+
+        /// Maximum number of intentions of an asset pair in a block.
+        #[pallet::constant]
+        type MaxIntentionsPerPair: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Number of intentions registered in current block.
+    #[pallet::storage]
+    #[pallet::getter(fn intention_count)]
+    pub type IntentionCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Intentions registered in current block, keyed by ordered asset pair.
+    #[pallet::storage]
+    #[pallet::getter(fn get_intentions)]
+    pub type ExchangeAssetsIntentions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        (T::AssetId, T::AssetId),
+        BoundedVec<Intention<T>, T::MaxIntentionsPerPair>,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Intention has been registered.
+        IntentionRegistered {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount: Balance,
+            intention_type: IntentionType,
+            intention_id: IntentionId<T>,
+        },
+        /// Intention (or its remainder) has been resolved by trading with the AMM pool.
+        IntentionResolvedAMMTrade {
+            who: T::AccountId,
+            intention_type: IntentionType,
+            intention_id: IntentionId<T>,
+            amount: Balance,
+            amount_sold_or_bought: Balance,
+            pool: T::AccountId,
+        },
+        /// Two intentions have been matched and traded directly.
+        /// `amount_a` of asset sold by `account_id_a` has been exchanged for `amount_b` of asset sold by `account_id_b`.
+        IntentionResolvedDirectTrade {
+            account_id_a: T::AccountId,
+            account_id_b: T::AccountId,
+            intention_id_a: IntentionId<T>,
+            intention_id_b: IntentionId<T>,
+            amount_a: Balance,
+            amount_b: Balance,
+        },
+        /// Trade fee of a direct trade has been paid to the pool account.
+        IntentionResolvedDirectTradeFees {
+            who: T::AccountId,
+            intention_id: IntentionId<T>,
+            pool: T::AccountId,
+            asset: T::AssetId,
+            fee_amount: Balance,
+        },
+        /// Intention could not be resolved.
+        IntentionResolveErrorEvent {
+            who: T::AccountId,
+            assets: AssetPair<T::AssetId>,
+            intention_type: IntentionType,
+            intention_id: IntentionId<T>,
+            error_detail: DispatchError,
+        },
+    }
+
+    #[pallet::error]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
+    pub enum Error<T> {
+        /// Pool of given assets does not exist.
+        TokenPoolNotFound,
+
+        /// Selling and buying the same asset is not allowed.
+        SameAssetTrade,
+
+        /// Maximum number of intentions of an asset pair in a block has been reached.
+        MaxIntentionsReached,
+
+        /// Intention count overflow.
+        IntentionCountOverflow,
+
+        /// Trade limit of a directly matched intention has not been satisfied.
+        TradeLimitNotSatisfied,
+
+        /// Fee calculation failed.
+        FeeAmountInvalid,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            T::WeightInfo::known_overhead_for_on_finalize()
+        }
+
+        fn on_finalize(_n: T::BlockNumber) {
+            for ((asset_a, _), intentions) in ExchangeAssetsIntentions::<T>::drain() {
+                for intention in intentions.iter() {
+                    T::Currency::unreserve(intention.assets.asset_in, &intention.who, intention.amount_in);
+                }
+                Self::process_exchange_intentions(asset_a, intentions.into_inner());
+            }
+
+            IntentionCount::<T>::kill();
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register an intention to sell `amount_sell` of `asset_sell` for at least `min_bought` of `asset_buy`.
+        ///
+        /// The intention is validated against the AMM pool of the pair and resolved at the end of the block.
+        /// `amount_sell` is reserved until then.
+        ///
+        /// Emits `IntentionRegistered` event when successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::sell_intention()
+            .saturating_add(<T as Config>::WeightInfo::on_finalize_for_one_sell_extrinsic()))]
+        pub fn sell(
+            origin: OriginFor<T>,
+            asset_sell: T::AssetId,
+            asset_buy: T::AssetId,
+            amount_sell: Balance,
+            min_bought: Balance,
+            discount: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let assets = AssetPair::new(asset_sell, asset_buy);
+            Self::ensure_pool_exists(assets)?;
+
+            let transfer = T::AMMPool::validate_sell(&who, assets, amount_sell, min_bought, discount)?;
+
+            Self::register_intention(
+                who,
+                assets,
+                amount_sell,
+                transfer.amount_b,
+                min_bought,
+                discount,
+                IntentionType::Sell,
+                amount_sell,
+            )
+        }
+
+        /// Register an intention to buy `amount_buy` of `asset_buy` for at most `max_sold` of `asset_sell`.
+        ///
+        /// The intention is validated against the AMM pool of the pair and resolved at the end of the block.
+        /// The amount of `asset_sell` the trade costs at registration, including the fee, is reserved until then.
+        ///
+        /// Emits `IntentionRegistered` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::buy_intention()
+            .saturating_add(<T as Config>::WeightInfo::on_finalize_for_one_buy_extrinsic()))]
+        pub fn buy(
+            origin: OriginFor<T>,
+            asset_buy: T::AssetId,
+            asset_sell: T::AssetId,
+            amount_buy: Balance,
+            max_sold: Balance,
+            discount: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let assets = AssetPair::new(asset_sell, asset_buy);
+            Self::ensure_pool_exists(assets)?;
+
+            let transfer = T::AMMPool::validate_buy(&who, assets, amount_buy, max_sold, discount)?;
+
+            Self::register_intention(
+                who,
+                assets,
+                transfer.amount_b.saturating_add(transfer.fee.1),
+                amount_buy,
+                max_sold,
+                discount,
+                IntentionType::Buy,
+                amount_buy,
+            )
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn ensure_pool_exists(assets: AssetPair<T::AssetId>) -> DispatchResult {
+        ensure!(assets.asset_in != assets.asset_out, Error::<T>::SameAssetTrade);
+        ensure!(T::AMMPool::exists(assets), Error::<T>::TokenPoolNotFound);
+        Ok(())
+    }
+
+    /// Register an intention and reserve `amount_in` of the sold asset until the intention is resolved.
+    #[allow(clippy::too_many_arguments)]
+    fn register_intention(
+        who: T::AccountId,
+        assets: AssetPair<T::AssetId>,
+        amount_in: Balance,
+        amount_out: Balance,
+        trade_limit: Balance,
+        discount: bool,
+        sell_or_buy: IntentionType,
+        amount: Balance,
+    ) -> DispatchResult {
+        let intention_count = Self::intention_count();
+        let intention_id = Self::generate_intention_id(&who, intention_count, &assets);
+
+        let intention = ExchangeIntention {
+            who: who.clone(),
+            assets,
+            amount_in,
+            amount_out,
+            trade_limit,
+            discount,
+            sell_or_buy,
+            intention_id,
+        };
+
+        <ExchangeAssetsIntentions<T>>::try_mutate(assets.ordered_pair(), |intentions| {
+            intentions
+                .try_push(intention)
+                .map_err(|_| Error::<T>::MaxIntentionsReached)
+        })?;
+
+        <IntentionCount<T>>::put(
+            intention_count
+                .checked_add(1)
+                .ok_or(Error::<T>::IntentionCountOverflow)?,
+        );
+
+        T::Currency::reserve(assets.asset_in, &who, amount_in)?;
+
+        Self::deposit_event(Event::IntentionRegistered {
+            who,
+            asset_a: assets.asset_in,
+            asset_b: assets.asset_out,
+            amount,
+            intention_type: sell_or_buy,
+            intention_id,
+        });
+
+        Ok(())
+    }
+
+    fn generate_intention_id(
+        who: &T::AccountId,
+        intention_count: u32,
+        assets: &AssetPair<T::AssetId>,
+    ) -> IntentionId<T> {
+        let block_number = <frame_system::Pallet<T>>::block_number();
+        T::Hashing::hash_of(&(who, intention_count, assets, block_number))
+    }
+
+    /// Match and resolve all intentions of an asset pair.
+    ///
+    /// `asset_a` is the first asset of the ordered asset pair.
+    fn process_exchange_intentions(asset_a: T::AssetId, intentions: Vec<Intention<T>>) {
+        let (matchable, unmatchable): (Vec<Intention<T>>, Vec<Intention<T>>) = intentions
+            .into_iter()
+            .map(Self::with_spot_price_amounts)
+            .partition(|intention| !intention.amount_in.is_zero() && !intention.amount_out.is_zero());
+
+        // Intentions too small to be priced at spot price can only be traded with the pool
+        for intention in unmatchable.iter() {
+            Self::resolve_single_intention(intention);
+        }
+
+        let (mut a_sells, mut b_sells): (Vec<Intention<T>>, Vec<Intention<T>>) = matchable
+            .into_iter()
+            .partition(|intention| intention.assets.asset_in == asset_a);
+
+        // Largest intentions first to minimize number of direct trades
+        a_sells.sort_by(|x, y| y.amount_in.cmp(&x.amount_in));
+        b_sells.sort_by(|x, y| y.amount_out.cmp(&x.amount_out));
+
+        let mut b_sells: VecDeque<Intention<T>> = b_sells.into();
+
+        for intention in a_sells.iter() {
+            let pair_account = T::AMMPool::get_pair_id(intention.assets);
+
+            // Amounts of `intention` traded directly so far
+            let mut given = Balance::zero();
+            let mut received = Balance::zero();
+
+            while given < intention.amount_in {
+                let b_intention = match b_sells.pop_front() {
+                    Some(b_intention) => b_intention,
+                    None => break,
+                };
+
+                let (traded_in, traded_out, left) =
+                    Self::trade_with_matched(&pair_account, intention, &b_intention, given, received);
+                given = traded_in;
+                received = traded_out;
+
+                // Untraded part of the matched intention is matched with the next intention
+                if let Some(left) = left {
+                    b_sells.push_front(left);
+                }
+            }
+
+            if given < intention.amount_in {
+                Self::resolve_remainder(intention, given, received);
+            }
+        }
+
+        for intention in b_sells {
+            Self::resolve_single_intention(&intention);
+        }
+    }
+
+    /// Trade the rest of `intention`, of which `given` and `received` have been traded directly so far,
+    /// directly with `matched` selling the opposite asset.
+    ///
+    /// Returns the amounts of `intention` traded directly after the trade and the untraded part of `matched`,
+    /// if it can be traded further. If the direct trade fails, `matched` is traded with the AMM pool.
+    fn trade_with_matched(
+        pair_account: &T::AccountId,
+        intention: &Intention<T>,
+        matched: &Intention<T>,
+        given: Balance,
+        received: Balance,
+    ) -> (Balance, Balance, Option<Intention<T>>) {
+        let left_in = intention.amount_in.saturating_sub(given);
+        let left_out = intention.amount_out.saturating_sub(received);
+
+        if left_in.is_zero() {
+            return (given, received, Some(matched.clone()));
+        }
+
+        // Either the matched intention is traded completely or the rest of `intention` is
+        let (amount_a, amount_b) = if matched.amount_out <= left_in {
+            (matched.amount_out, matched.amount_in)
+        } else {
+            (left_in, left_out.min(matched.amount_in))
+        };
+
+        if Self::execute_direct_trade(pair_account, intention, matched, amount_a, amount_b).is_err() {
+            Self::resolve_single_intention(matched);
+            return (given, received, None);
+        }
+
+        let left = if amount_a < matched.amount_out {
+            match Self::remainder_of(matched, amount_b, amount_a) {
+                Ok(remainder) if !remainder.amount_in.is_zero() && !remainder.amount_out.is_zero() => Some(remainder),
+                Ok(remainder) => {
+                    Self::resolve_remainder_of(&remainder);
+                    None
+                }
+                Err(error) => {
+                    Self::deposit_resolve_error(matched, error);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        (given.saturating_add(amount_a), received.saturating_add(amount_b), left)
+    }
+
+    /// Re-calculate amounts of an intention at current AMM spot price.
+    fn with_spot_price_amounts(mut intention: Intention<T>) -> Intention<T> {
+        let (asset_in, asset_out) = (intention.assets.asset_in, intention.assets.asset_out);
+        match intention.sell_or_buy {
+            IntentionType::Sell => {
+                intention.amount_out = T::AMMPool::get_spot_price_unchecked(asset_in, asset_out, intention.amount_in);
+            }
+            IntentionType::Buy => {
+                intention.amount_in = T::AMMPool::get_spot_price_unchecked(asset_out, asset_in, intention.amount_out);
+            }
+        }
+        intention
+    }
+
+    fn calculate_fee(pair_account: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+        hydra_dx_math::fee::calculate_pool_trade_fee(amount, T::AMMPool::get_fee(pair_account))
+            .ok_or_else(|| Error::<T>::FeeAmountInvalid.into())
+    }
+
+    /// Return `limit * part / whole`, rounded up if `round_up` is true.
+    fn proportional_limit(
+        limit: Balance,
+        part: Balance,
+        whole: Balance,
+        round_up: bool,
+    ) -> Result<Balance, DispatchError> {
+        if whole.is_zero() {
+            return Ok(limit);
+        }
+
+        let numerator = U256::from(limit)
+            .checked_mul(U256::from(part))
+            .ok_or(ArithmeticError::Overflow)?;
+        let whole = U256::from(whole);
+        let result = if round_up {
+            numerator
+                .checked_add(whole.saturating_sub(U256::one()))
+                .ok_or(ArithmeticError::Overflow)?
+                / whole
+        } else {
+            numerator / whole
+        };
+
+        Balance::try_from(result).map_err(|_| ArithmeticError::Overflow.into())
+    }
+
+    /// Ensure that giving `given` and receiving `received` in a direct trade satisfies the intention's trade limit.
+    fn ensure_direct_trade_limit(
+        pair_account: &T::AccountId,
+        intention: &Intention<T>,
+        given: Balance,
+        received: Balance,
+    ) -> DispatchResult {
+        match intention.sell_or_buy {
+            IntentionType::Sell => {
+                let fee = Self::calculate_fee(pair_account, received)?;
+                let min_received = Self::proportional_limit(intention.trade_limit, given, intention.amount_in, false)?;
+                ensure!(
+                    received.saturating_sub(fee) >= min_received,
+                    Error::<T>::TradeLimitNotSatisfied
+                );
+            }
+            IntentionType::Buy => {
+                let fee = Self::calculate_fee(pair_account, given)?;
+                let max_given = Self::proportional_limit(intention.trade_limit, received, intention.amount_out, true)?;
+                ensure!(
+                    given.saturating_add(fee) <= max_given,
+                    Error::<T>::TradeLimitNotSatisfied
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Transfer `amount` of asset sold by `from` to the account of `to`, including the trade fees.
+    ///
+    /// If `to` is a sell intention, its fee is subtracted from the transferred amount.
+    /// If `from` is a buy intention, its fee is paid on top of the transferred amount.
+    fn transfer_with_fees(
+        pair_account: &T::AccountId,
+        from: &Intention<T>,
+        to: &Intention<T>,
+        amount: Balance,
+    ) -> DispatchResult {
+        let asset = from.assets.asset_in;
+        let fee = Self::calculate_fee(pair_account, amount)?;
+
+        let to_fee = if to.sell_or_buy == IntentionType::Sell {
+            fee
+        } else {
+            Balance::zero()
+        };
+        let from_fee = if from.sell_or_buy == IntentionType::Buy {
+            fee
+        } else {
+            Balance::zero()
+        };
+
+        T::Currency::transfer(asset, &from.who, &to.who, amount.saturating_sub(to_fee))?;
+
+        let fee_amount = to_fee.saturating_add(from_fee);
+        if !fee_amount.is_zero() {
+            T::Currency::transfer(asset, &from.who, pair_account, fee_amount)?;
+
+            Self::deposit_event(Event::IntentionResolvedDirectTradeFees {
+                who: from.who.clone(),
+                intention_id: from.intention_id,
+                pool: pair_account.clone(),
+                asset,
+                fee_amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Exchange `amount_a` of asset sold by `intention_a` for `amount_b` of asset sold by `intention_b`.
+    ///
+    /// Either both transfers are executed or none.
+    fn execute_direct_trade(
+        pair_account: &T::AccountId,
+        intention_a: &Intention<T>,
+        intention_b: &Intention<T>,
+        amount_a: Balance,
+        amount_b: Balance,
+    ) -> DispatchResult {
+        with_transaction(
+            || match Self::do_direct_trade(pair_account, intention_a, intention_b, amount_a, amount_b) {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            },
+        )
+    }
+
+    fn do_direct_trade(
+        pair_account: &T::AccountId,
+        intention_a: &Intention<T>,
+        intention_b: &Intention<T>,
+        amount_a: Balance,
+        amount_b: Balance,
+    ) -> DispatchResult {
+        Self::ensure_direct_trade_limit(pair_account, intention_a, amount_a, amount_b)?;
+        Self::ensure_direct_trade_limit(pair_account, intention_b, amount_b, amount_a)?;
+
+        Self::transfer_with_fees(pair_account, intention_a, intention_b, amount_a)?;
+        Self::transfer_with_fees(pair_account, intention_b, intention_a, amount_b)?;
+
+        Self::deposit_event(Event::IntentionResolvedDirectTrade {
+            account_id_a: intention_a.who.clone(),
+            account_id_b: intention_b.who.clone(),
+            intention_id_a: intention_a.intention_id,
+            intention_id_b: intention_b.intention_id,
+            amount_a,
+            amount_b,
+        });
+
+        Ok(())
+    }
+
+    /// Return the part of an intention which has not been traded directly yet.
+    ///
+    /// Trade limit is reduced proportionally to the traded amount.
+    fn remainder_of(
+        intention: &Intention<T>,
+        given: Balance,
+        received: Balance,
+    ) -> Result<Intention<T>, DispatchError> {
+        let amount_in = intention.amount_in.saturating_sub(given);
+        let amount_out = intention.amount_out.saturating_sub(received);
+
+        let trade_limit = match intention.sell_or_buy {
+            IntentionType::Sell => {
+                Self::proportional_limit(intention.trade_limit, amount_in, intention.amount_in, false)?
+            }
+            IntentionType::Buy => {
+                Self::proportional_limit(intention.trade_limit, amount_out, intention.amount_out, true)?
+            }
+        };
+
+        Ok(ExchangeIntention {
+            amount_in,
+            amount_out,
+            trade_limit,
+            ..intention.clone()
+        })
+    }
+
+    /// Resolve the remainder of an intention via AMM pool, if there is anything left to trade.
+    fn resolve_remainder(intention: &Intention<T>, given: Balance, received: Balance) {
+        if given.is_zero() && received.is_zero() {
+            Self::resolve_single_intention(intention);
+            return;
+        }
+
+        match Self::remainder_of(intention, given, received) {
+            Ok(remainder) => Self::resolve_remainder_of(&remainder),
+            Err(error) => Self::deposit_resolve_error(intention, error),
+        }
+    }
+
+    /// Trade a remainder returned by `remainder_of` with the AMM pool, if there is anything left to trade.
+    fn resolve_remainder_of(remainder: &Intention<T>) {
+        let left = match remainder.sell_or_buy {
+            IntentionType::Sell => remainder.amount_in,
+            IntentionType::Buy => remainder.amount_out,
+        };
+        if !left.is_zero() {
+            Self::resolve_single_intention(remainder);
+        }
+    }
+
+    fn deposit_resolve_error(intention: &Intention<T>, error: DispatchError) {
+        Self::deposit_event(Event::IntentionResolveErrorEvent {
+            who: intention.who.clone(),
+            assets: intention.assets,
+            intention_type: intention.sell_or_buy,
+            intention_id: intention.intention_id,
+            error_detail: error,
+        });
+    }
+}
+
+impl<T: Config> Resolver<T::AccountId, Intention<T>, Error<T>> for Pallet<T> {
+    /// Resolve an intention by trading with the AMM pool.
+    fn resolve_single_intention(intention: &Intention<T>) {
+        let result = with_transaction(|| {
+            let result = match intention.sell_or_buy {
+                IntentionType::Sell => T::AMMPool::validate_sell(
+                    &intention.who,
+                    intention.assets,
+                    intention.amount_in,
+                    intention.trade_limit,
+                    intention.discount,
+                )
+                .and_then(|transfer| T::AMMPool::execute_sell(&transfer).map(|_| transfer)),
+                IntentionType::Buy => T::AMMPool::validate_buy(
+                    &intention.who,
+                    intention.assets,
+                    intention.amount_out,
+                    intention.trade_limit,
+                    intention.discount,
+                )
+                .and_then(|transfer| T::AMMPool::execute_buy(&transfer).map(|_| transfer)),
+            };
+
+            match result {
+                Ok(transfer) => TransactionOutcome::Commit(Ok(transfer)),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            }
+        });
+
+        match result {
+            Ok(transfer) => {
+                Self::deposit_event(Event::IntentionResolvedAMMTrade {
+                    who: intention.who.clone(),
+                    intention_type: intention.sell_or_buy,
+                    intention_id: intention.intention_id,
+                    amount: transfer.amount,
+                    amount_sold_or_bought: transfer.amount_b,
+                    pool: T::AMMPool::get_pair_id(intention.assets),
+                });
+            }
+            Err(error) => Self::deposit_resolve_error(intention, error),
+        }
+    }
+
+    /// Trade `intention` directly with `matched` intentions selling the opposite asset.
+    ///
+    /// Matched intentions are traded in given order. Any part of an intention which is not traded directly
+    /// is traded with the AMM pool.
+    fn resolve_matched_intentions(pair_account: &T::AccountId, intention: &Intention<T>, matched: &[&Intention<T>]) {
+        // Amounts of `intention` traded directly so far
+        let mut given = Balance::zero();
+        let mut received = Balance::zero();
+
+        for matched_intention in matched {
+            let (traded_in, traded_out, left) =
+                Self::trade_with_matched(pair_account, intention, matched_intention, given, received);
+            given = traded_in;
+            received = traded_out;
+
+            if let Some(left) = left {
+                Self::resolve_single_intention(&left);
+            }
+        }
+
+        if given < intention.amount_in {
+            Self::resolve_remainder(intention, given, received);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::intention_id_at;
+use crate::tests::mock::*;
+use crate::types::{AssetPair, IntentionType};
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use pretty_assertions::assert_eq;
+use test_utils::assert_balance;
+
+#[test]
+fn sell_should_register_intention() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Act
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+
+            // Assert
+            let intentions = Exchange::get_intentions((HDX, DAI));
+            assert_eq!(intentions.len(), 1);
+            assert_eq!(intentions[0].who, ALICE);
+            assert_eq!(intentions[0].assets, AssetPair::new(HDX, DAI));
+            assert_eq!(intentions[0].amount_in, 10 * ONE);
+            assert_eq!(intentions[0].trade_limit, 19 * ONE);
+            assert_eq!(intentions[0].sell_or_buy, IntentionType::Sell);
+            assert_eq!(Exchange::intention_count(), 1);
+
+            // Nothing is traded until the end of the block, the sold amount is reserved
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_eq!(Tokens::reserved_balance(HDX, &ALICE), 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::IntentionRegistered {
+                who: ALICE,
+                asset_a: HDX,
+                asset_b: DAI,
+                amount: 10 * ONE,
+                intention_type: IntentionType::Sell,
+                intention_id: intention_id_at(HDX, DAI, 0),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn buy_should_register_intention() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Act
+            assert_ok!(Exchange::buy(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                21 * ONE,
+                false
+            ));
+
+            // Assert
+            let intentions = Exchange::get_intentions((HDX, DAI));
+            assert_eq!(intentions.len(), 1);
+            assert_eq!(intentions[0].assets, AssetPair::new(DAI, HDX));
+            assert_eq!(intentions[0].amount_out, 10 * ONE);
+            assert_eq!(intentions[0].trade_limit, 21 * ONE);
+            assert_eq!(intentions[0].sell_or_buy, IntentionType::Buy);
+            assert_eq!(Tokens::reserved_balance(DAI, &ALICE), intentions[0].amount_in);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::IntentionRegistered {
+                who: ALICE,
+                asset_a: DAI,
+                asset_b: HDX,
+                amount: 10 * ONE,
+                intention_type: IntentionType::Buy,
+                intention_id: intention_id_at(HDX, DAI, 0),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn intentions_should_have_unique_ids() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Act
+            assert_ok!(Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, ONE, 0, false));
+            assert_ok!(Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, ONE, 0, false));
+
+            // Assert
+            assert_ne!(intention_id_at(HDX, DAI, 0), intention_id_at(HDX, DAI, 1));
+        });
+}
+
+#[test]
+fn sell_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DOT, 10 * ONE, 0, false),
+                Error::<Test>::TokenPoolNotFound
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_assets_are_same() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, HDX, 10 * ONE, 0, false),
+                Error::<Test>::SameAssetTrade
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_amm_validation_fails() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, 1_001 * ONE, 0, false),
+                pallet_xyk::Error::<Test>::InsufficientAssetBalance
+            );
+            assert_noop!(
+                Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, 10 * ONE, 20 * ONE, false),
+                pallet_xyk::Error::<Test>::AssetAmountNotReachedLimit
+            );
+        });
+}
+
+#[test]
+fn buy_should_fail_when_amm_validation_fails() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Exchange::buy(RuntimeOrigin::signed(ALICE), HDX, DAI, 10 * ONE, 20 * ONE, false),
+                pallet_xyk::Error::<Test>::AssetAmountExceededLimit
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_max_intentions_per_pair_is_reached() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            for _ in 0..5 {
+                assert_ok!(Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, ONE, 0, false));
+            }
+
+            // Act & Assert
+            assert_noop!(
+                Exchange::buy(RuntimeOrigin::signed(BOB), HDX, DAI, ONE, 10 * ONE, false),
+                Error::<Test>::MaxIntentionsReached
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_balance_is_reserved_by_previous_intentions() {
+    ExtBuilder::default()
+        .with_pool(HDX, 10_000 * ONE, DAI, 20_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                ENDOWED_AMOUNT - ONE,
+                0,
+                false
+            ));
+
+            // Act & Assert
+            assert_noop!(
+                Exchange::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, 2 * ONE, 0, false),
+                pallet_xyk::Error::<Test>::InsufficientAssetBalance
+            );
+        });
+}
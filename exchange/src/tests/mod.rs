@@ -0,0 +1,15 @@
+mod intentions;
+pub(crate) mod mock;
+mod resolution;
+
+use crate::types::IntentionId;
+use mock::*;
+
+pub(crate) fn intention_id_at(asset_a: AssetId, asset_b: AssetId, idx: usize) -> IntentionId<Test> {
+    let pair = if asset_a <= asset_b {
+        (asset_a, asset_b)
+    } else {
+        (asset_b, asset_a)
+    };
+    crate::Pallet::<Test>::get_intentions(pair)[idx].intention_id
+}
@@ -0,0 +1,421 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::intention_id_at;
+use crate::tests::mock::*;
+use crate::types::{AssetPair, IntentionType};
+use crate::Event;
+use frame_support::assert_ok;
+use frame_support::traits::OnFinalize;
+use hydradx_traits::router::{PoolType, TradeExecution};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use pretty_assertions::assert_eq;
+use test_utils::assert_balance;
+
+/// Fee of 0.2% charged by the pool
+fn fee(amount: Balance) -> Balance {
+    amount * 2 / 1_000
+}
+
+#[test]
+fn single_intention_should_be_resolved_via_pool() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+            let intention_id = intention_id_at(HDX, DAI, 0);
+            let expected_received = XYK::calculate_sell(PoolType::XYK, HDX, DAI, 10 * ONE).unwrap();
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT + expected_received);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::IntentionResolvedAMMTrade {
+                who: ALICE,
+                intention_type: IntentionType::Sell,
+                intention_id,
+                amount: 10 * ONE,
+                amount_sold_or_bought: expected_received,
+                pool: pool_account(HDX, DAI),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn opposite_intentions_should_be_traded_directly_when_amounts_match() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DAI);
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(BOB),
+                DAI,
+                HDX,
+                20 * ONE,
+                9 * ONE,
+                false
+            ));
+            let alice_intention = intention_id_at(HDX, DAI, 0);
+            let bob_intention = intention_id_at(HDX, DAI, 1);
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT + 20 * ONE - fee(20 * ONE));
+            assert_balance!(BOB, HDX, ENDOWED_AMOUNT + 10 * ONE - fee(10 * ONE));
+            assert_balance!(BOB, DAI, ENDOWED_AMOUNT - 20 * ONE);
+
+            // Pool reserves changed only by the fees
+            assert_balance!(pool, HDX, 1_000 * ONE + fee(10 * ONE));
+            assert_balance!(pool, DAI, 2_000 * ONE + fee(20 * ONE));
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![
+                Event::IntentionResolvedDirectTradeFees {
+                    who: ALICE,
+                    intention_id: alice_intention,
+                    pool,
+                    asset: HDX,
+                    fee_amount: fee(10 * ONE),
+                }
+                .into(),
+                Event::IntentionResolvedDirectTradeFees {
+                    who: BOB,
+                    intention_id: bob_intention,
+                    pool,
+                    asset: DAI,
+                    fee_amount: fee(20 * ONE),
+                }
+                .into(),
+                Event::IntentionResolvedDirectTrade {
+                    account_id_a: ALICE,
+                    account_id_b: BOB,
+                    intention_id_a: alice_intention,
+                    intention_id_b: bob_intention,
+                    amount_a: 10 * ONE,
+                    amount_b: 20 * ONE,
+                }
+                .into(),
+            ]);
+        });
+}
+
+#[test]
+fn remainder_of_partially_matched_intention_should_be_traded_via_pool() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(BOB),
+                DAI,
+                HDX,
+                10 * ONE,
+                4 * ONE,
+                false
+            ));
+            let alice_intention = intention_id_at(HDX, DAI, 0);
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            // Bob's intention is matched completely with half of Alice's intention
+            assert_balance!(BOB, HDX, ENDOWED_AMOUNT + 5 * ONE - fee(5 * ONE));
+            assert_balance!(BOB, DAI, ENDOWED_AMOUNT - 10 * ONE);
+
+            // The other half of Alice's intention is sold to the pool, which received the direct trade fees
+            let amm_out = hydra_dx_math::xyk::calculate_out_given_in(
+                1_000 * ONE + fee(5 * ONE),
+                2_000 * ONE + fee(10 * ONE),
+                5 * ONE,
+            )
+            .unwrap();
+            let amm_received = amm_out - hydra_dx_math::fee::calculate_pool_trade_fee(amm_out, (2, 1_000)).unwrap();
+
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT + 10 * ONE - fee(10 * ONE) + amm_received);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::IntentionResolvedAMMTrade {
+                who: ALICE,
+                intention_type: IntentionType::Sell,
+                intention_id: alice_intention,
+                amount: 5 * ONE,
+                amount_sold_or_bought: amm_received,
+                pool: pool_account(HDX, DAI),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn intention_should_be_matched_with_multiple_opposite_intentions() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                0,
+                false
+            ));
+            assert_ok!(Exchange::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 4 * ONE, 0, false));
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(CHARLIE),
+                DAI,
+                HDX,
+                6 * ONE,
+                0,
+                false
+            ));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            assert_balance!(BOB, HDX, ENDOWED_AMOUNT + 2 * ONE - fee(2 * ONE));
+            assert_balance!(BOB, DAI, ENDOWED_AMOUNT - 4 * ONE);
+            assert_balance!(CHARLIE, HDX, ENDOWED_AMOUNT + 3 * ONE - fee(3 * ONE));
+            assert_balance!(CHARLIE, DAI, ENDOWED_AMOUNT - 6 * ONE);
+
+            // Remaining 5 HDX of Alice's intention is sold to the pool
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert!(Tokens::free_balance(DAI, &ALICE) > ENDOWED_AMOUNT + 10 * ONE - fee(10 * ONE));
+        });
+}
+
+#[test]
+fn buy_intention_should_be_traded_directly_with_sell_intention() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DAI);
+            assert_ok!(Exchange::buy(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                21 * ONE,
+                false
+            ));
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            // Buyer receives exact amount and pays the fee on top, seller pays the fee from the amount received
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT + 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT - 20 * ONE - fee(20 * ONE));
+            assert_balance!(BOB, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_balance!(BOB, DAI, ENDOWED_AMOUNT + 20 * ONE - fee(20 * ONE));
+
+            assert_balance!(pool, HDX, 1_000 * ONE);
+            assert_balance!(pool, DAI, 2_000 * ONE + 2 * fee(20 * ONE));
+        });
+}
+
+#[test]
+fn intentions_should_be_cleared_at_the_end_of_block() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                0,
+                false
+            ));
+            assert_ok!(Exchange::buy(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                ONE,
+                10 * ONE,
+                false
+            ));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            assert!(Exchange::get_intentions((HDX, DAI)).is_empty());
+            assert_eq!(Exchange::intention_count(), 0);
+        });
+}
+
+#[test]
+fn failed_intention_should_emit_error_event() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                19 * ONE,
+                false
+            ));
+            let intention_id = intention_id_at(HDX, DAI, 0);
+            // price moves before the intention is resolved
+            assert_ok!(XYK::sell(RuntimeOrigin::signed(DAVE), HDX, DAI, 100 * ONE, 0, false));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            // reserved amount is returned
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT);
+            assert_eq!(Tokens::reserved_balance(HDX, &ALICE), 0);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::IntentionResolveErrorEvent {
+                who: ALICE,
+                assets: AssetPair::new(HDX, DAI),
+                intention_type: IntentionType::Sell,
+                intention_id,
+                error_detail: pallet_xyk::Error::<Test>::AssetAmountNotReachedLimit.into(),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn untraded_part_of_matched_intention_should_be_matched_with_next_intention() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                0,
+                false
+            ));
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(CHARLIE),
+                HDX,
+                DAI,
+                4 * ONE,
+                0,
+                false
+            ));
+            assert_ok!(Exchange::sell(RuntimeOrigin::signed(BOB), DAI, HDX, 30 * ONE, 0, false));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            // Bob's intention is traded directly with Alice's intention first and with Charlie's intention after
+            assert_balance!(ALICE, HDX, ENDOWED_AMOUNT - 10 * ONE);
+            assert_balance!(ALICE, DAI, ENDOWED_AMOUNT + 20 * ONE - fee(20 * ONE));
+            assert_balance!(CHARLIE, HDX, ENDOWED_AMOUNT - 4 * ONE);
+            assert_balance!(CHARLIE, DAI, ENDOWED_AMOUNT + 8 * ONE - fee(8 * ONE));
+
+            // Only the last 2 DAI of Bob's intention are sold to the pool
+            assert_balance!(BOB, DAI, ENDOWED_AMOUNT - 30 * ONE);
+            let pool = pool_account(HDX, DAI);
+            assert_balance!(pool, DAI, 2_000 * ONE + 2 * ONE + fee(20 * ONE) + fee(8 * ONE));
+        });
+}
+
+#[test]
+fn reserved_amounts_should_be_released_when_intentions_are_resolved() {
+    ExtBuilder::default()
+        .with_pool(HDX, 1_000 * ONE, DAI, 2_000 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(Exchange::sell(
+                RuntimeOrigin::signed(ALICE),
+                HDX,
+                DAI,
+                10 * ONE,
+                0,
+                false
+            ));
+            assert_ok!(Exchange::buy(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                ONE,
+                10 * ONE,
+                false
+            ));
+
+            // Act
+            Exchange::on_finalize(1);
+
+            // Assert
+            assert_eq!(Tokens::reserved_balance(HDX, &ALICE), 0);
+            assert_eq!(Tokens::reserved_balance(DAI, &BOB), 0);
+        });
+}
@@ -0,0 +1,50 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+
+pub use hydradx_traits::AssetPair;
+
+pub type Balance = u128;
+
+pub type IntentionId<T> = <T as frame_system::Config>::Hash;
+
+pub type Intention<T> =
+    ExchangeIntention<<T as frame_system::Config>::AccountId, <T as crate::Config>::AssetId, Balance, IntentionId<T>>;
+
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum IntentionType {
+    Sell,
+    Buy,
+}
+
+/// Intention to trade registered in current block.
+///
+/// `amount_in` and `amount_out` are estimated at registration and re-calculated at AMM spot price
+/// when intentions are resolved.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ExchangeIntention<AccountId, AssetId, Balance, IntentionId> {
+    pub who: AccountId,
+    pub assets: AssetPair<AssetId>,
+    pub amount_in: Balance,
+    pub amount_out: Balance,
+    /// Minimum amount to receive for sell, maximum amount to pay for buy.
+    pub trade_limit: Balance,
+    pub discount: bool,
+    pub sell_or_buy: IntentionType,
+    pub intention_id: IntentionId,
+}
@@ -0,0 +1,100 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_exchange
+//!
+//! PLACEHOLDER WEIGHTS - NOT GENERATED BY THE BENCHMARK CLI.
+//! The pallet has no benchmarks yet. The weights are estimates derived by hand from the storage accesses of each
+//! call. Replace them with the output of the `pallet-exchange` benchmarks before release:
+//!
+//! target/release/hydradx benchmark pallet --chain=dev --steps=5 --repeat=20 --execution=wasm
+//! --wasm-execution=compiled --heap-pages=4096 --template=.maintain/pallet-weight-template.hbs --pallet=pallet-exchange
+//! --output=exchange.rs --extrinsic=*
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_exchange.
+pub trait WeightInfo {
+    fn known_overhead_for_on_finalize() -> Weight;
+    fn sell_intention() -> Weight;
+    fn buy_intention() -> Weight;
+    fn on_finalize_for_one_sell_extrinsic() -> Weight;
+    fn on_finalize_for_one_buy_extrinsic() -> Weight;
+}
+
+pub struct BasiliskWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
+    fn known_overhead_for_on_finalize() -> Weight {
+        Weight::from_ref_time(5_794_000 as u64).saturating_add(T::DbWeight::get().reads(1 as u64))
+    }
+    fn sell_intention() -> Weight {
+        Weight::from_ref_time(60_870_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(10 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn buy_intention() -> Weight {
+        Weight::from_ref_time(60_335_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(10 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn on_finalize_for_one_sell_extrinsic() -> Weight {
+        Weight::from_ref_time(83_172_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(10 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
+    }
+    fn on_finalize_for_one_buy_extrinsic() -> Weight {
+        Weight::from_ref_time(86_734_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(10 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn known_overhead_for_on_finalize() -> Weight {
+        Weight::from_ref_time(5_794_000 as u64).saturating_add(RocksDbWeight::get().reads(1 as u64))
+    }
+    fn sell_intention() -> Weight {
+        Weight::from_ref_time(60_870_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(10 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn buy_intention() -> Weight {
+        Weight::from_ref_time(60_335_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(10 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn on_finalize_for_one_sell_extrinsic() -> Weight {
+        Weight::from_ref_time(83_172_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(10 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
+    }
+    fn on_finalize_for_one_buy_extrinsic() -> Weight {
+        Weight::from_ref_time(86_734_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(10 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
+    }
+}
@@ -0,0 +1,44 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the OTC pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_otc::{Balance, FillQuote, Order, OrderId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query OTC orders.
+    pub trait OtcApi<AccountId, AssetId, BlockNumber>
+    where
+        AccountId: Codec,
+        AssetId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns the open orders of `owner`.
+        fn orders_by_owner(owner: AccountId) -> Vec<(OrderId, Order<AccountId, AssetId, BlockNumber>)>;
+
+        /// Returns the open orders of the pair of assets, best price first. Pegged orders come last.
+        fn orders_by_pair(
+            asset_in: AssetId,
+            asset_out: AssetId,
+        ) -> Vec<(OrderId, Order<AccountId, AssetId, BlockNumber>)>;
+
+        /// Returns the amounts exchanged when the order is filled with `amount_in`, or `None` if it cannot be filled.
+        fn fill_quote(order_id: OrderId, amount_in: Balance) -> Option<FillQuote>;
+    }
+}
@@ -33,7 +33,8 @@ benchmarks! {
         let (hdx, dai) = seed_registry::<T>()?;
 
         let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
-  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+        let takers = allowed_takers::<T>(account("taker", 0, 0));
+  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), Some(takers))
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 100 * ONE);
     }
@@ -45,7 +46,7 @@ benchmarks! {
         let filler: T::AccountId = create_account_with_balances::<T>("filler", 2, vec!(hdx, dai))?;
 
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), Some(allowed_takers::<T>(filler.clone())))
         );
   }:  _(RawOrigin::Signed(filler.clone()), 0u32, 10 * ONE)
     verify {
@@ -59,7 +60,7 @@ benchmarks! {
         let filler: T::AccountId = create_account_with_balances::<T>("filler", 2, vec!(hdx, dai))?;
 
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), Some(allowed_takers::<T>(filler.clone())))
         );
   }:  _(RawOrigin::Signed(filler.clone()), 0u32)
     verify {
@@ -71,12 +72,86 @@ benchmarks! {
 
         let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
         assert_ok!(
-            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true)
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), None)
         );
   }:  _(RawOrigin::Signed(owner.clone()), 0u32)
     verify {
         assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
     }
+
+    cancel_expired {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        let caller: T::AccountId = create_account_with_balances::<T>("caller", 2, vec!(hdx, dai))?;
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), None)
+        );
+
+        frame_system::Pallet::<T>::set_block_number(10u32.into());
+  }:  _(RawOrigin::Signed(caller), 0u32)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 0);
+        assert!(<Orders<T>>::get(0u32).is_none());
+    }
+
+    fill_best {
+        let n in 1 .. T::MaxOrdersPerPair::get();
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let filler: T::AccountId = create_account_with_balances::<T>("filler", 0, vec!(hdx, dai))?;
+        T::Currency::update_balance(dai.into(), &filler, (20 * ONE * n as u128) as i128)?;
+        for i in 1 ..= n {
+            let owner: T::AccountId = create_account_with_balances::<T>("owner", i, vec!(hdx, dai))?;
+            assert_ok!(
+                crate::Pallet::<T>::place_order(RawOrigin::Signed(owner).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), Some(allowed_takers::<T>(filler.clone())))
+            );
+        }
+  }:  _(RawOrigin::Signed(filler.clone()), AssetPair::new(dai.into(), hdx.into()), 20 * ONE * n as u128, Price::saturating_from_integer(5u128))
+    verify {
+        assert!(<OrderBook<T>>::get((T::AssetId::from(dai), T::AssetId::from(hdx))).is_empty());
+    }
+
+    match_orders {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner_a: T::AccountId = create_account_with_balances::<T>("owner_a", 1, vec!(hdx, dai))?;
+        let owner_b: T::AccountId = create_account_with_balances::<T>("owner_b", 2, vec!(hdx, dai))?;
+        let keeper: T::AccountId = create_account_with_balances::<T>("keeper", 3, vec!(hdx, dai))?;
+
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner_a.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), Some(allowed_takers::<T>(owner_b.clone())))
+        );
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner_b.clone()).into(), hdx.into(), dai.into(), 50 * ONE, 15 * ONE, true, Some(10u32.into()), Some(allowed_takers::<T>(owner_a.clone())))
+        );
+  }:  _(RawOrigin::Signed(keeper), 0u32, 1u32)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner_a), 50 * ONE);
+        assert!(<Orders<T>>::get(1u32).is_none());
+    }
+
+    update_order {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        assert_ok!(
+            crate::Pallet::<T>::place_order(RawOrigin::Signed(owner.clone()).into(), dai.into(), hdx.into(), 20 * ONE, 100 * ONE, true, Some(10u32.into()), None)
+        );
+  }:  _(RawOrigin::Signed(owner.clone()), 0u32, 30 * ONE, 150 * ONE, false)
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 150 * ONE);
+    }
+
+    place_pegged_order {
+        let (hdx, dai) = seed_registry::<T>()?;
+
+        let owner: T::AccountId = create_account_with_balances::<T>("owner", 1, vec!(hdx, dai))?;
+        let takers = allowed_takers::<T>(account("taker", 0, 0));
+  }:  _(RawOrigin::Signed(owner.clone()), dai.into(), hdx.into(), 100 * ONE, OraclePeriod::Short, PriceAdjustment::Premium(Permill::from_percent(1)), true, Some(10u32.into()), Some(takers))
+    verify {
+        assert_eq!(T::Currency::reserved_balance_named(&NAMED_RESERVE_ID, hdx.into(), &owner), 100 * ONE);
+    }
 }
 
 fn seed_registry<T: Config>() -> Result<(u32, u32), DispatchError>
@@ -90,6 +165,16 @@ where
     Ok((hdx.into(), dai.into()))
 }
 
+// Fills the list of allowed takers up to its bound, with `taker` placed last.
+fn allowed_takers<T: Config>(taker: T::AccountId) -> BoundedVec<T::AccountId, T::MaxAllowedTakers> {
+    let mut takers: Vec<T::AccountId> = (1..T::MaxAllowedTakers::get())
+        .map(|i| account("other_taker", i, i))
+        .collect();
+    takers.push(taker);
+
+    takers.try_into().unwrap_or_default()
+}
+
 fn create_account_with_balances<T: Config>(
     name: &'static str,
     index: u32,
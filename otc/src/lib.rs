@@ -18,6 +18,8 @@
 // This pallet provides basic over-the-counter (OTC) trading functionality.
 // It allows anyone to `place_order` by specifying a pair of assets (in and out), their respective amounts, and
 // whether the order is partially fillable. The order price is static and calculated as `amount_out / amount_in`.
+// Orders can also be priced by an oracle, restricted to a list of takers and expire at a given block. Open orders
+// are indexed per pair of assets and per owner, and exposed via the `OtcApi` runtime API.
 //
 // ## Notes
 // The pallet implements a minimum order size as an alternative to storage fees. The amounts of an open order cannot
@@ -30,18 +32,33 @@
 // * `partial_fill_order` - fill an OTC order (partially).
 // * `fill_order` - fill an OTC order (completely).
 // * `cancel_order` - cancel an open OTC order.
+// * `cancel_expired` - remove an expired OTC order.
+// * `update_order` - change the amounts and the partially fillable flag of an open OTC order.
+// * `fill_best` - fill the best priced OTC orders of a pair of assets.
+// * `match_orders` - settle two crossing OTC orders against each other.
+// * `place_pegged_order` - create a new OTC order priced by the oracle.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::MaxEncodedLen;
-use frame_support::{pallet_prelude::*, require_transactional};
-use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-use hydradx_traits::Registry;
+use frame_support::{
+    pallet_prelude::*,
+    require_transactional,
+    storage::{with_transaction, TransactionOutcome},
+};
+use frame_system::{
+    ensure_signed,
+    pallet_prelude::{BlockNumberFor, OriginFor},
+};
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OraclePeriod, Source},
+    AssetPair, Registry,
+};
 use orml_traits::{GetByKey, MultiCurrency, NamedMultiReservableCurrency};
 use sp_core::U256;
 use sp_runtime::{
-    traits::{One, Zero},
-    DispatchError,
+    traits::{One, Saturating, Zero},
+    DispatchError, FixedPointNumber, FixedU128, PerThing, Permill,
 };
 use sp_std::vec::Vec;
 #[cfg(test)]
@@ -50,6 +67,7 @@ mod tests;
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarks;
 
+pub mod migration;
 pub mod weights;
 
 use weights::WeightInfo;
@@ -59,27 +77,76 @@ pub use pallet::*;
 
 pub type Balance = u128;
 pub type OrderId = u32;
+pub type Price = FixedU128;
 pub type NamedReserveIdentifier = [u8; 8];
 
 pub const NAMED_RESERVE_ID: NamedReserveIdentifier = *b"otcorder";
 
+/// Provides the block in which the oracle of a pair of assets was last updated.
+pub trait OracleLastUpdate<AssetId, BlockNumber> {
+    fn last_update(asset_a: AssetId, asset_b: AssetId, source: Source) -> Option<BlockNumber>;
+
+    fn last_update_weight() -> Weight;
+}
+
+/// Adjustment of the oracle price of a pegged order, in favour of the order owner.
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub enum PriceAdjustment {
+    /// The owner asks for more `asset_in` than the oracle price.
+    Premium(Permill),
+    /// The owner asks for less `asset_in` than the oracle price.
+    Discount(Permill),
+}
+
+/// Oracle price reference of a pegged order.
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub struct OraclePeg {
+    pub period: OraclePeriod,
+    pub adjustment: PriceAdjustment,
+}
+
 #[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, TypeInfo, MaxEncodedLen)]
-pub struct Order<AccountId, AssetId> {
+pub struct Order<AccountId, AssetId, BlockNumber> {
     pub owner: AccountId,
     pub asset_in: AssetId,
     pub asset_out: AssetId,
+    /// Zero for pegged orders - the amount is resolved from the oracle at fill time.
     pub amount_in: Balance,
     pub amount_out: Balance,
     pub partially_fillable: bool,
+    pub expires_at: Option<BlockNumber>,
+    pub peg: Option<OraclePeg>,
+}
+
+impl<AccountId, AssetId, BlockNumber: PartialOrd> Order<AccountId, AssetId, BlockNumber> {
+    /// Returns true if the order cannot be filled anymore at block `now`.
+    pub fn is_expired(&self, now: &BlockNumber) -> bool {
+        self.expires_at.as_ref().map_or(false, |expires_at| now >= expires_at)
+    }
+}
+
+/// Amounts exchanged when an order is filled, as returned by `Pallet::quote_fill`.
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, TypeInfo)]
+pub struct FillQuote {
+    /// Amount of `asset_in` paid by the taker.
+    pub amount_in: Balance,
+    /// Amount of `asset_out` received by the taker, after the fee is deducted.
+    pub amount_out: Balance,
+    pub fee: Balance,
 }
 
+pub type OrderOf<T> = Order<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, BlockNumberFor<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use codec::HasCompact;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -105,6 +172,44 @@ pub mod pallet {
         #[pallet::constant]
         type ExistentialDepositMultiplier: Get<u8>;
 
+        /// Maximum number of accounts an order can be restricted to.
+        #[pallet::constant]
+        type MaxAllowedTakers: Get<u32>;
+
+        /// Maximum number of open orders of a pair of assets indexed in `OrderBook`. No orders can be placed for a
+        /// pair whose book is full.
+        #[pallet::constant]
+        type MaxOrdersPerPair: Get<u32>;
+
+        /// Maximum number of open orders of a single account.
+        #[pallet::constant]
+        type MaxOrdersPerAccount: Get<u32>;
+
+        /// Fee deducted from the amount received by the taker when an order is filled.
+        #[pallet::constant]
+        type Fee: Get<Permill>;
+
+        /// Account which receives the fees.
+        #[pallet::constant]
+        type FeeReceiver: Get<Self::AccountId>;
+
+        /// Oracle providing the price of pegged orders and the block of its last update.
+        type Oracle: AggregatedPriceOracle<Self::AssetId, Self::BlockNumber, Price>
+            + OracleLastUpdate<Self::AssetId, Self::BlockNumber>;
+
+        /// Oracle source used to price pegged orders.
+        #[pallet::constant]
+        type OracleSource: Get<Source>;
+
+        /// Minimum age of the oracle, as reported by `Oracle`, with which a pegged order can be filled. Oracles of
+        /// pairs which have only been tracked for a few blocks are easier to manipulate.
+        #[pallet::constant]
+        type MinOracleAge: Get<Self::BlockNumber>;
+
+        /// Maximum number of blocks since the last update of the oracle with which a pegged order can be filled.
+        #[pallet::constant]
+        type MaxOracleStaleness: Get<Self::BlockNumber>;
+
         /// Weight information for the extrinsics.
         type WeightInfo: WeightInfo;
     }
@@ -114,19 +219,21 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// An Order has been cancelled
         Cancelled { order_id: OrderId },
-        /// An Order has been completely filled
+        /// An Order has been completely filled. The taker received `amount_out` reduced by `fee`.
         Filled {
             order_id: OrderId,
             who: T::AccountId,
             amount_in: Balance,
             amount_out: Balance,
+            fee: Balance,
         },
-        /// An Order has been partially filled
+        /// An Order has been partially filled. The taker received `amount_out` reduced by `fee`.
         PartiallyFilled {
             order_id: OrderId,
             who: T::AccountId,
             amount_in: Balance,
             amount_out: Balance,
+            fee: Balance,
         },
         /// An Order has been placed
         Placed {
@@ -136,7 +243,35 @@ pub mod pallet {
             amount_in: Balance,
             amount_out: Balance,
             partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<BoundedVec<T::AccountId, T::MaxAllowedTakers>>,
+            peg: Option<OraclePeg>,
         },
+        /// An expired Order has been removed and its reserved amount released
+        Expired { order_id: OrderId },
+        /// An Order has been updated by its owner
+        Updated {
+            order_id: OrderId,
+            amount_in: Balance,
+            amount_out: Balance,
+            partially_fillable: bool,
+        },
+        /// Two Orders have been matched against each other
+        Matched {
+            order_a: OrderId,
+            order_b: OrderId,
+            who: T::AccountId,
+            amount_a: Balance,
+            amount_b: Balance,
+            surplus: Balance,
+        },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::remove_expired_orders(now, remaining_weight)
+        }
     }
 
     #[pallet::error]
@@ -159,6 +294,34 @@ pub mod pallet {
         Forbidden,
         /// Reserved amount not sufficient.
         InsufficientReservedAmount,
+        /// Order has expired and cannot be filled anymore
+        OrderExpired,
+        /// Order has not expired yet
+        OrderNotExpired,
+        /// Expiry block must be in the future
+        InvalidExpiry,
+        /// The caller is not allowed to fill the order
+        NotAllowedTaker,
+        /// List of allowed takers cannot be empty
+        NoAllowedTakers,
+        /// Maximum number of open orders for the pair of assets has been reached
+        OrderBookFull,
+        /// Maximum number of open orders of the account has been reached
+        MaxOrdersPerAccountReached,
+        /// No order could be filled within the given amount and limit price
+        NoOrderFilled,
+        /// Orders cannot be matched because their assets are not opposite to each other
+        OrdersNotMatching,
+        /// Orders cannot be matched because their prices do not cross
+        PricesNotCrossing,
+        /// Oracle price for the pair of assets is not available
+        OraclePriceNotAvailable,
+        /// Oracle has been tracked for fewer than MinOracleAge blocks
+        OracleNotMature,
+        /// Oracle was not updated within MaxOracleStaleness blocks
+        OracleStale,
+        /// The action is not supported for pegged orders
+        OrderIsPegged,
     }
 
     /// ID sequencer for Orders
@@ -168,21 +331,73 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn orders)]
-    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, Order<T::AccountId, T::AssetId>, OptionQuery>;
+    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>, OptionQuery>;
+
+    /// Orders with an expiry, indexed by the block in which they expire
+    #[pallet::storage]
+    pub type OrderExpiries<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Blake2_128Concat, OrderId, (), OptionQuery>;
+
+    /// Next block to be checked for expired orders in `on_idle`. Zero if no order with an expiry has been placed yet.
+    #[pallet::storage]
+    #[pallet::getter(fn expiry_cursor)]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Open orders indexed by (asset_in, asset_out), sorted by price from the highest to the lowest.
+    /// Orders with the same price are sorted by their ID.
+    #[pallet::storage]
+    #[pallet::getter(fn order_book)]
+    pub type OrderBook<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        (T::AssetId, T::AssetId),
+        BoundedVec<(Price, OrderId), T::MaxOrdersPerPair>,
+        ValueQuery,
+    >;
+
+    /// Pegged orders indexed by their pair of assets, as they are not part of `OrderBook`.
+    #[pallet::storage]
+    pub type PeggedOrders<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, (T::AssetId, T::AssetId), Blake2_128Concat, OrderId, (), OptionQuery>;
+
+    /// Accounts which are allowed to fill an order. Orders without an entry can be filled by anyone.
+    #[pallet::storage]
+    #[pallet::getter(fn allowed_takers)]
+    pub type AllowedTakers<T: Config> =
+        StorageMap<_, Blake2_128Concat, OrderId, BoundedVec<T::AccountId, T::MaxAllowedTakers>, OptionQuery>;
+
+    /// Open orders indexed by their owner
+    #[pallet::storage]
+    pub type OrdersByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, OrderId, (), OptionQuery>;
+
+    /// Number of open orders of an account
+    #[pallet::storage]
+    #[pallet::getter(fn order_count)]
+    pub type OrderCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a new OTC order
         ///  
+        /// The order is added to the order book of its pair of assets, used by `fill_best`. If `expires_at` is set,
+        /// the order is removed in `on_idle` once it has expired, as long as there is enough weight left in the block.
+        ///
         /// Parameters:
         /// - `asset_in`: Asset which is being bought
         /// - `asset_out`: Asset which is being sold
         /// - `amount_in`: Amount that the order is seeking to buy
         /// - `amount_out`: Amount that the order is selling
         /// - `partially_fillable`: Flag indicating whether users can fill the order partially
+        /// - `expires_at`: Optional block starting from which the order can no longer be filled
+        /// - `allowed_takers`: Optional list of accounts which are allowed to fill the order
         ///
         /// Validations:
         /// - asset_in must be registered
+        /// - expires_at, if set, must be higher than the current block number
+        /// - allowed_takers, if set, must not be empty
+        /// - owner must have less than MaxOrdersPerAccount open orders
+        /// - order book of the pair must have less than MaxOrdersPerPair orders
         /// - amount_in must be higher than the existential deposit of asset_in multiplied by
         ///   ExistentialDepositMultiplier
         /// - amount_out must be higher than the existential deposit of asset_out multiplied by
@@ -199,6 +414,8 @@ pub mod pallet {
             amount_in: Balance,
             amount_out: Balance,
             partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<BoundedVec<T::AccountId, T::MaxAllowedTakers>>,
         ) -> DispatchResult {
             let owner = ensure_signed(origin)?;
             let order = Order {
@@ -208,39 +425,27 @@ pub mod pallet {
                 amount_in,
                 amount_out,
                 partially_fillable,
+                expires_at,
+                peg: None,
             };
 
-            ensure!(T::AssetRegistry::exists(order.asset_in), Error::<T>::AssetNotRegistered);
-            Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
-            Self::ensure_min_order_amount(order.asset_out, amount_out)?;
-
-            <NextOrderId<T>>::try_mutate(|next_id| -> DispatchResult {
-                let order_id = *next_id;
-
-                T::Currency::reserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out)?;
-                <Orders<T>>::insert(order_id, &order);
-
-                Self::deposit_event(Event::Placed {
-                    order_id,
-                    asset_in: order.asset_in,
-                    asset_out: order.asset_out,
-                    amount_in: order.amount_in,
-                    amount_out,
-                    partially_fillable: order.partially_fillable,
-                });
-
-                *next_id = next_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOutOfBound)?;
-                Ok(())
-            })
+            Self::do_place_order(order, allowed_takers)
         }
 
         /// Fill an OTC order (partially)
         ///  
+        /// A fee of `Fee` is deducted from the received amount of `asset_out` and transferred to `FeeReceiver`,
+        /// unless it would leave `FeeReceiver` with a balance below the existential deposit of the asset.
+        ///
         /// Parameters:
         /// - `order_id`: ID of the order
         /// - `amount_in`: Amount with which the order is being filled
         ///
         /// Validations:
+        /// - order must not be expired
+        /// - caller must be an allowed taker, if the order is restricted
+        /// - for pegged orders, the oracle price must be available, the oracle at least MinOracleAge old and
+        ///   updated within MaxOracleStaleness blocks
         /// - order must be partially_fillable
         /// - after the partial_fill, the remaining order.amount_in must be higher than the existential deposit
         ///   of asset_in multiplied by ExistentialDepositMultiplier
@@ -250,61 +455,37 @@ pub mod pallet {
         /// Events:
         /// `PartiallyFilled` event when successful.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::partial_fill_order())]
+        #[pallet::weight(<T as Config>::WeightInfo::partial_fill_order()
+            .saturating_add(T::Oracle::get_price_weight())
+            .saturating_add(T::Oracle::last_update_weight()))]
         pub fn partial_fill_order(origin: OriginFor<T>, order_id: OrderId, amount_in: Balance) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
-                let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
-
-                ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
-
-                let amount_out_calculation = U256::from(order.amount_out)
-                    .checked_mul(U256::from(amount_in))
-                    .and_then(|v| v.checked_div(U256::from(order.amount_in)))
-                    .ok_or(Error::<T>::MathError)?;
-                let amount_out = Balance::try_from(amount_out_calculation).map_err(|_| Error::<T>::MathError)?;
-
-                order.amount_in = order.amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
-                order.amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
-
-                Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
-                Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
-
-                Self::execute_order(order, &who, amount_in, amount_out)?;
-
-                Self::deposit_event(Event::PartiallyFilled {
-                    order_id,
-                    who,
-                    amount_in,
-                    amount_out,
-                });
-                Ok(())
-            })
+            Self::do_partial_fill_order(order_id, &who, amount_in)
         }
 
         /// Fill an OTC order (completely)
         ///  
+        /// A fee of `Fee` is deducted from the received amount of `asset_out` and transferred to `FeeReceiver`,
+        /// unless it would leave `FeeReceiver` with a balance below the existential deposit of the asset.
+        ///
         /// Parameters:
         /// - `order_id`: ID of the order
         ///
+        /// Validations:
+        /// - order must not be expired
+        /// - caller must be an allowed taker, if the order is restricted
+        /// - for pegged orders, the oracle price must be available, the oracle at least MinOracleAge old and
+        ///   updated within MaxOracleStaleness blocks
+        ///
         /// Events:
         /// `Filled` event when successful.
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::fill_order())]
+        #[pallet::weight(<T as Config>::WeightInfo::fill_order()
+            .saturating_add(T::Oracle::get_price_weight())
+            .saturating_add(T::Oracle::last_update_weight()))]
         pub fn fill_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
-
-            Self::execute_order(&order, &who, order.amount_in, order.amount_out)?;
-            <Orders<T>>::remove(order_id);
-
-            Self::deposit_event(Event::Filled {
-                order_id,
-                who,
-                amount_in: order.amount_in,
-                amount_out: order.amount_out,
-            });
-            Ok(())
+            Self::do_fill_order(order_id, &who)
         }
 
         /// Cancel an open OTC order
@@ -322,24 +503,521 @@ pub mod pallet {
         #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
         pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            <Orders<T>>::try_mutate_exists(order_id, |maybe_order| -> DispatchResult {
+            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+            ensure!(order.owner == who, Error::<T>::Forbidden);
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            Self::remove_order(order_id, &order);
+
+            Self::deposit_event(Event::Cancelled { order_id });
+            Ok(())
+        }
+
+        /// Remove an expired OTC order and release its reserved amount back to the owner
+        ///
+        /// Can be called by anyone.
+        ///
+        /// Parameters:
+        /// - `order_id`: ID of the order
+        ///
+        /// Validations:
+        /// - order must be expired
+        ///
+        /// Emits `Expired` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_expired())]
+        pub fn cancel_expired(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+            ensure_signed(origin)?;
+            let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+            ensure!(
+                order.is_expired(&<frame_system::Pallet<T>>::block_number()),
+                Error::<T>::OrderNotExpired
+            );
+
+            Self::remove_expired_order(order_id, &order)
+        }
+
+        /// Update an open OTC order
+        ///
+        /// Only the difference between the new and the current `amount_out` is reserved or unreserved.
+        ///
+        /// Parameters:
+        /// - `order_id`: ID of the order
+        /// - `amount_in`: New amount that the order is seeking to buy
+        /// - `amount_out`: New amount that the order is selling
+        /// - `partially_fillable`: New flag indicating whether users can fill the order partially
+        ///
+        /// Validations:
+        /// - caller is order owner
+        /// - order must not be pegged
+        /// - order must not be expired
+        /// - amount_in must be higher than the existential deposit of asset_in multiplied by
+        ///   ExistentialDepositMultiplier
+        /// - amount_out must be higher than the existential deposit of asset_out multiplied by
+        ///   ExistentialDepositMultiplier
+        ///
+        /// Emits `Updated` event when successful.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_order())]
+        pub fn update_order(
+            origin: OriginFor<T>,
+            order_id: OrderId,
+            amount_in: Balance,
+            amount_out: Balance,
+            partially_fillable: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
 
                 ensure!(order.owner == who, Error::<T>::Forbidden);
+                ensure!(order.peg.is_none(), Error::<T>::OrderIsPegged);
+                Self::ensure_not_expired(order)?;
+
+                Self::ensure_min_order_amount(order.asset_in, amount_in)?;
+                Self::ensure_min_order_amount(order.asset_out, amount_out)?;
 
-                let remaining_to_unreserve =
-                    T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
-                ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
-                *maybe_order = None;
+                if amount_out > order.amount_out {
+                    T::Currency::reserve_named(
+                        &NAMED_RESERVE_ID,
+                        order.asset_out,
+                        &order.owner,
+                        amount_out.saturating_sub(order.amount_out),
+                    )?;
+                } else if amount_out < order.amount_out {
+                    let remaining_to_unreserve = T::Currency::unreserve_named(
+                        &NAMED_RESERVE_ID,
+                        order.asset_out,
+                        &order.owner,
+                        order.amount_out.saturating_sub(amount_out),
+                    );
+                    ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+                }
 
-                Self::deposit_event(Event::Cancelled { order_id });
+                Self::remove_from_order_book(order_id, order);
+                order.amount_in = amount_in;
+                order.amount_out = amount_out;
+                order.partially_fillable = partially_fillable;
+                Self::insert_into_order_book(order_id, order)?;
+
+                Self::deposit_event(Event::Updated {
+                    order_id,
+                    amount_in,
+                    amount_out,
+                    partially_fillable,
+                });
                 Ok(())
             })
         }
+
+        /// Fill the best priced OTC orders of a pair of assets
+        ///
+        /// Orders are filled from the highest to the lowest price until `amount` is spent. An order which is larger
+        /// than the remaining amount is filled partially if it is partially fillable, otherwise it is skipped.
+        /// Orders which cannot be filled by the caller, e.g. because they are expired or restricted to other takers,
+        /// are skipped as well. Pegged orders are not part of the order book and are never filled by this call.
+        ///
+        /// A fee of `Fee` is deducted from the received amount of `asset_out` and transferred to `FeeReceiver`,
+        /// unless it would leave `FeeReceiver` with a balance below the existential deposit of the asset.
+        ///
+        /// Parameters:
+        /// - `asset_pair`: Pair of assets of the orders to fill - the caller pays `asset_in` and receives `asset_out`
+        /// - `amount`: Maximum amount of `asset_in` to spend
+        /// - `limit_price`: Lowest acceptable price, as amount of `asset_out` received per unit of `asset_in`
+        ///
+        /// Validations:
+        /// - at least one order must be filled
+        ///
+        /// Events:
+        /// - `Filled` or `PartiallyFilled` event for each filled order.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::fill_best(T::MaxOrdersPerPair::get()))]
+        pub fn fill_best(
+            origin: OriginFor<T>,
+            asset_pair: AssetPair<T::AssetId>,
+            amount: Balance,
+            limit_price: Price,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut remaining = amount;
+            let mut walked: u32 = 0;
+            for (price, order_id) in Self::order_book((asset_pair.asset_in, asset_pair.asset_out)) {
+                if remaining.is_zero() || price < limit_price {
+                    break;
+                }
+                walked.saturating_inc();
+
+                let order_amount_in = match <Orders<T>>::get(order_id) {
+                    Some(order) => order.amount_in,
+                    None => continue,
+                };
+
+                let (result, amount_in) = if remaining >= order_amount_in {
+                    (
+                        Self::with_rollback_on_error(|| Self::do_fill_order(order_id, &who)),
+                        order_amount_in,
+                    )
+                } else {
+                    (
+                        Self::with_rollback_on_error(|| Self::do_partial_fill_order(order_id, &who, remaining)),
+                        remaining,
+                    )
+                };
+
+                if result.is_ok() {
+                    remaining = remaining.saturating_sub(amount_in);
+                }
+            }
+
+            ensure!(remaining < amount, Error::<T>::NoOrderFilled);
+
+            Ok(Some(<T as Config>::WeightInfo::fill_best(walked)).into())
+        }
+
+        /// Settle two crossing OTC orders against each other
+        ///
+        /// The owner of `order_a` sells `asset_out` of `order_a` to the owner of `order_b`. The smaller of the two
+        /// orders is filled completely, the other one is filled partially. Both makers trade at the price of their
+        /// own order: the owner of `order_a` receives the minimum amount required by `order_a` and the owner of
+        /// `order_b` pays the maximum amount allowed by `order_b`. The difference is paid to the caller.
+        ///
+        /// Parameters:
+        /// - `order_a`: ID of the first order
+        /// - `order_b`: ID of the second order
+        ///
+        /// Validations:
+        /// - orders must not be pegged
+        /// - asset_in of each order must be the asset_out of the other one
+        /// - orders must not be expired
+        /// - owner of each order must be an allowed taker of the other order, if it is restricted
+        /// - order which is filled partially must be partially_fillable and the remaining amounts must be higher
+        ///   than the existential deposit multiplied by ExistentialDepositMultiplier
+        /// - price of order_b must be at least as good as the price of order_a
+        ///
+        /// Emits `Matched` event when successful.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::match_orders())]
+        pub fn match_orders(origin: OriginFor<T>, order_a: OrderId, order_b: OrderId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(order_a != order_b, Error::<T>::OrdersNotMatching);
+            let a = <Orders<T>>::get(order_a).ok_or(Error::<T>::OrderNotFound)?;
+            let b = <Orders<T>>::get(order_b).ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(a.peg.is_none() && b.peg.is_none(), Error::<T>::OrderIsPegged);
+
+            ensure!(
+                a.asset_in == b.asset_out && a.asset_out == b.asset_in,
+                Error::<T>::OrdersNotMatching
+            );
+            Self::ensure_not_expired(&a)?;
+            Self::ensure_not_expired(&b)?;
+            Self::ensure_allowed_taker(order_a, &b.owner)?;
+            Self::ensure_allowed_taker(order_b, &a.owner)?;
+
+            // amount of a.asset_out traded
+            let amount_a = a.amount_out.min(b.amount_in);
+            // amount of a.asset_in received by the owner of order_a, rounded up in favour of the maker
+            let received_a = Self::calculate_amount(a.amount_in, amount_a, a.amount_out, true)?;
+            // amount of b.asset_out paid by the owner of order_b, rounded down in favour of the maker
+            let paid_b = Self::calculate_amount(b.amount_out, amount_a, b.amount_in, false)?;
+            let surplus = paid_b.checked_sub(received_a).ok_or(Error::<T>::PricesNotCrossing)?;
+
+            let remaining_to_unreserve =
+                T::Currency::unreserve_named(&NAMED_RESERVE_ID, a.asset_out, &a.owner, amount_a);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+            let remaining_to_unreserve = T::Currency::unreserve_named(&NAMED_RESERVE_ID, b.asset_out, &b.owner, paid_b);
+            ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+
+            T::Currency::transfer(a.asset_out, &a.owner, &b.owner, amount_a)?;
+            T::Currency::transfer(b.asset_out, &b.owner, &a.owner, received_a)?;
+            if !surplus.is_zero() {
+                T::Currency::transfer(b.asset_out, &b.owner, &who, surplus)?;
+            }
+
+            Self::reduce_order(order_a, a, received_a, amount_a)?;
+            Self::reduce_order(order_b, b, amount_a, paid_b)?;
+
+            Self::deposit_event(Event::Matched {
+                order_a,
+                order_b,
+                who,
+                amount_a,
+                amount_b: received_a,
+                surplus,
+            });
+            Ok(())
+        }
+
+        /// Create a new OTC order priced by the oracle
+        ///
+        /// The amount of `asset_in` is not fixed. It is calculated when the order is filled, from the oracle price
+        /// of `asset_out` in `asset_in` for `period`, adjusted by `adjustment`. Fills are rejected if the oracle was
+        /// last updated more than `MaxOracleStaleness` blocks ago or has been tracked for fewer than `MinOracleAge`
+        /// blocks. Pegged orders are indexed in `PeggedOrders` instead of the order book, so they are not filled by
+        /// `fill_best` and cannot be updated or matched.
+        ///
+        /// Parameters:
+        /// - `asset_in`: Asset which is being bought
+        /// - `asset_out`: Asset which is being sold
+        /// - `amount_out`: Amount that the order is selling
+        /// - `period`: Oracle period used to price the order
+        /// - `adjustment`: Premium or discount applied to the oracle price
+        /// - `partially_fillable`: Flag indicating whether users can fill the order partially
+        /// - `expires_at`: Optional block starting from which the order can no longer be filled
+        /// - `allowed_takers`: Optional list of accounts which are allowed to fill the order
+        ///
+        /// Validations:
+        /// - asset_in must be registered
+        /// - amount_out must be higher than the existential deposit of asset_out multiplied by
+        ///   ExistentialDepositMultiplier
+        /// - expires_at, if set, must be higher than the current block number
+        /// - allowed_takers, if set, must not be empty
+        /// - owner must have less than MaxOrdersPerAccount open orders
+        ///
+        /// Events:
+        /// - `Placed` event when successful.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::place_pegged_order())]
+        pub fn place_pegged_order(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_out: Balance,
+            period: OraclePeriod,
+            adjustment: PriceAdjustment,
+            partially_fillable: bool,
+            expires_at: Option<T::BlockNumber>,
+            allowed_takers: Option<BoundedVec<T::AccountId, T::MaxAllowedTakers>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let order = Order {
+                owner,
+                asset_in,
+                asset_out,
+                amount_in: Zero::zero(),
+                amount_out,
+                partially_fillable,
+                expires_at,
+                peg: Some(OraclePeg { period, adjustment }),
+            };
+
+            Self::do_place_order(order, allowed_takers)
+        }
     }
 }
 
 impl<T: Config> Pallet<T> {
+    fn do_place_order(
+        order: OrderOf<T>,
+        allowed_takers: Option<BoundedVec<T::AccountId, T::MaxAllowedTakers>>,
+    ) -> DispatchResult {
+        ensure!(T::AssetRegistry::exists(order.asset_in), Error::<T>::AssetNotRegistered);
+        if order.peg.is_none() {
+            Self::ensure_min_order_amount(order.asset_in, order.amount_in)?;
+        }
+        Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
+
+        if let Some(expires_at) = order.expires_at {
+            ensure!(
+                expires_at > <frame_system::Pallet<T>>::block_number(),
+                Error::<T>::InvalidExpiry
+            );
+        }
+
+        if let Some(takers) = &allowed_takers {
+            ensure!(!takers.is_empty(), Error::<T>::NoAllowedTakers);
+        }
+
+        <NextOrderId<T>>::try_mutate(|next_id| -> DispatchResult {
+            let order_id = *next_id;
+
+            <OrderCount<T>>::try_mutate(&order.owner, |count| -> DispatchResult {
+                ensure!(
+                    *count < T::MaxOrdersPerAccount::get(),
+                    Error::<T>::MaxOrdersPerAccountReached
+                );
+                *count = count.checked_add(1).ok_or(Error::<T>::MathError)?;
+                Ok(())
+            })?;
+            <OrdersByOwner<T>>::insert(&order.owner, order_id, ());
+
+            T::Currency::reserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out)?;
+            <Orders<T>>::insert(order_id, &order);
+            Self::insert_into_order_book(order_id, &order)?;
+
+            if let Some(expires_at) = order.expires_at {
+                <OrderExpiries<T>>::insert(expires_at, order_id, ());
+                <ExpiryCursor<T>>::mutate(|cursor| {
+                    if cursor.is_zero() || expires_at < *cursor {
+                        *cursor = expires_at;
+                    }
+                });
+            }
+
+            if let Some(takers) = &allowed_takers {
+                <AllowedTakers<T>>::insert(order_id, takers);
+            }
+
+            Self::deposit_event(Event::Placed {
+                order_id,
+                asset_in: order.asset_in,
+                asset_out: order.asset_out,
+                amount_in: order.amount_in,
+                amount_out: order.amount_out,
+                partially_fillable: order.partially_fillable,
+                expires_at: order.expires_at,
+                allowed_takers,
+                peg: order.peg,
+            });
+
+            *next_id = next_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOutOfBound)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the amount of `asset_in` required to fill the whole order. For pegged orders, the amount is
+    /// calculated from the oracle price.
+    fn order_amount_in(order: &OrderOf<T>) -> Result<Balance, DispatchError> {
+        let peg = match order.peg {
+            Some(peg) => peg,
+            None => return Ok(order.amount_in),
+        };
+
+        let (price, oracle_age) =
+            T::Oracle::get_price(order.asset_in, order.asset_out, peg.period, T::OracleSource::get())
+                .map_err(|_| Error::<T>::OraclePriceNotAvailable)?;
+        ensure!(oracle_age >= T::MinOracleAge::get(), Error::<T>::OracleNotMature);
+
+        let last_update = T::Oracle::last_update(order.asset_in, order.asset_out, T::OracleSource::get())
+            .ok_or(Error::<T>::OraclePriceNotAvailable)?;
+        let staleness = <frame_system::Pallet<T>>::block_number().saturating_sub(last_update);
+        ensure!(staleness <= T::MaxOracleStaleness::get(), Error::<T>::OracleStale);
+
+        let amount_in = price.checked_mul_int(order.amount_out).ok_or(Error::<T>::MathError)?;
+        let amount_in = match peg.adjustment {
+            PriceAdjustment::Premium(premium) => amount_in.checked_add(premium.mul_ceil(amount_in)),
+            PriceAdjustment::Discount(discount) => amount_in.checked_sub(discount.mul_floor(amount_in)),
+        }
+        .ok_or(Error::<T>::MathError)?;
+
+        ensure!(!amount_in.is_zero(), Error::<T>::OraclePriceNotAvailable);
+
+        Ok(amount_in)
+    }
+
+    #[require_transactional]
+    fn do_partial_fill_order(order_id: OrderId, who: &T::AccountId, amount_in: Balance) -> DispatchResult {
+        <Orders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
+            let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+
+            Self::ensure_not_expired(order)?;
+            Self::ensure_allowed_taker(order_id, who)?;
+            ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+
+            let order_amount_in = Self::order_amount_in(order)?;
+            let amount_out_calculation = U256::from(order.amount_out)
+                .checked_mul(U256::from(amount_in))
+                .and_then(|v| v.checked_div(U256::from(order_amount_in)))
+                .ok_or(Error::<T>::MathError)?;
+            let amount_out = Balance::try_from(amount_out_calculation).map_err(|_| Error::<T>::MathError)?;
+
+            let remaining_amount_in = order_amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
+            Self::remove_from_order_book(order_id, order);
+            if order.peg.is_none() {
+                order.amount_in = remaining_amount_in;
+            }
+            order.amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+
+            Self::ensure_min_order_amount(order.asset_out, order.amount_out)?;
+            Self::ensure_min_order_amount(order.asset_in, remaining_amount_in)?;
+
+            let fee = Self::execute_order(order, who, amount_in, amount_out)?;
+            Self::insert_into_order_book(order_id, order)?;
+
+            Self::deposit_event(Event::PartiallyFilled {
+                order_id,
+                who: who.clone(),
+                amount_in,
+                amount_out,
+                fee,
+            });
+            Ok(())
+        })
+    }
+
+    #[require_transactional]
+    fn do_fill_order(order_id: OrderId, who: &T::AccountId) -> DispatchResult {
+        let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+        Self::ensure_not_expired(&order)?;
+        Self::ensure_allowed_taker(order_id, who)?;
+
+        let amount_in = Self::order_amount_in(&order)?;
+        let fee = Self::execute_order(&order, who, amount_in, order.amount_out)?;
+        Self::remove_order(order_id, &order);
+
+        Self::deposit_event(Event::Filled {
+            order_id,
+            who: who.clone(),
+            amount_in,
+            amount_out: order.amount_out,
+            fee,
+        });
+        Ok(())
+    }
+
+    /// Executes `f` in a new storage layer which is rolled back if `f` fails.
+    fn with_rollback_on_error(f: impl FnOnce() -> DispatchResult) -> DispatchResult {
+        with_transaction(|| match f() {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(e) => TransactionOutcome::Rollback(Err(e)),
+        })
+    }
+
+    fn order_price(order: &OrderOf<T>) -> Result<Price, DispatchError> {
+        Price::checked_from_rational(order.amount_out, order.amount_in).ok_or_else(|| Error::<T>::MathError.into())
+    }
+
+    /// Adds the order to the order book of its pair. Fails if the book is full. Pegged orders are indexed in
+    /// `PeggedOrders` instead, as their price is not fixed.
+    pub(crate) fn insert_into_order_book(order_id: OrderId, order: &OrderOf<T>) -> DispatchResult {
+        if order.peg.is_some() {
+            <PeggedOrders<T>>::insert((order.asset_in, order.asset_out), order_id, ());
+            return Ok(());
+        }
+
+        let price = Self::order_price(order)?;
+        <OrderBook<T>>::try_mutate((order.asset_in, order.asset_out), |book| -> DispatchResult {
+            // sorted by price descending, then by order id ascending
+            let index = book
+                .binary_search_by(|(p, id)| price.cmp(p).then(id.cmp(&order_id)))
+                .unwrap_or_else(|index| index);
+            book.try_insert(index, (price, order_id))
+                .map_err(|_| Error::<T>::OrderBookFull.into())
+        })
+    }
+
+    fn remove_from_order_book(order_id: OrderId, order: &OrderOf<T>) {
+        if order.peg.is_some() {
+            <PeggedOrders<T>>::remove((order.asset_in, order.asset_out), order_id);
+            return;
+        }
+
+        <OrderBook<T>>::mutate_exists((order.asset_in, order.asset_out), |maybe_book| {
+            if let Some(book) = maybe_book {
+                book.retain(|(_, id)| *id != order_id);
+                if book.is_empty() {
+                    *maybe_book = None;
+                }
+            }
+        });
+    }
+
     fn ensure_min_order_amount(asset: T::AssetId, amount: Balance) -> DispatchResult {
         let min_amount = T::ExistentialDeposits::get(&asset)
             .checked_mul(T::ExistentialDepositMultiplier::get().into())
@@ -350,19 +1028,255 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    fn ensure_not_expired(order: &OrderOf<T>) -> DispatchResult {
+        ensure!(
+            !order.is_expired(&<frame_system::Pallet<T>>::block_number()),
+            Error::<T>::OrderExpired
+        );
+
+        Ok(())
+    }
+
+    fn ensure_allowed_taker(order_id: OrderId, who: &T::AccountId) -> DispatchResult {
+        if let Some(takers) = <AllowedTakers<T>>::get(order_id) {
+            ensure!(takers.contains(who), Error::<T>::NotAllowedTaker);
+        }
+
+        Ok(())
+    }
+
+    /// Calculates `amount * numerator / denominator`, rounded up if `round_up` is set, otherwise rounded down.
+    fn calculate_amount(
+        amount: Balance,
+        numerator: Balance,
+        denominator: Balance,
+        round_up: bool,
+    ) -> Result<Balance, DispatchError> {
+        let product = U256::from(amount)
+            .checked_mul(U256::from(numerator))
+            .ok_or(Error::<T>::MathError)?;
+        let denominator = U256::from(denominator);
+        let mut result = product.checked_div(denominator).ok_or(Error::<T>::MathError)?;
+        if round_up && !(product % denominator).is_zero() {
+            result = result.checked_add(U256::one()).ok_or(Error::<T>::MathError)?;
+        }
+
+        Balance::try_from(result).map_err(|_| Error::<T>::MathError.into())
+    }
+
+    /// Reduces the order by the filled amounts. The order is removed if it has been filled completely, otherwise
+    /// the remaining amounts are validated and the order is updated.
+    fn reduce_order(
+        order_id: OrderId,
+        mut order: OrderOf<T>,
+        amount_in: Balance,
+        amount_out: Balance,
+    ) -> DispatchResult {
+        let remaining_in = order.amount_in.checked_sub(amount_in).ok_or(Error::<T>::MathError)?;
+        let remaining_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+
+        if remaining_in.is_zero() && remaining_out.is_zero() {
+            Self::remove_order(order_id, &order);
+            return Ok(());
+        }
+
+        ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+        Self::ensure_min_order_amount(order.asset_out, remaining_out)?;
+        Self::ensure_min_order_amount(order.asset_in, remaining_in)?;
+
+        Self::remove_from_order_book(order_id, &order);
+        order.amount_in = remaining_in;
+        order.amount_out = remaining_out;
+        Self::insert_into_order_book(order_id, &order)?;
+        <Orders<T>>::insert(order_id, order);
+
+        Ok(())
+    }
+
+    fn remove_order(order_id: OrderId, order: &OrderOf<T>) {
+        <Orders<T>>::remove(order_id);
+        <OrdersByOwner<T>>::remove(&order.owner, order_id);
+        <OrderCount<T>>::mutate_exists(&order.owner, |maybe_count| {
+            *maybe_count = maybe_count
+                .and_then(|count| count.checked_sub(1))
+                .filter(|count| !count.is_zero());
+        });
+        Self::remove_from_order_book(order_id, order);
+        <AllowedTakers<T>>::remove(order_id);
+        if let Some(expires_at) = order.expires_at {
+            <OrderExpiries<T>>::remove(expires_at, order_id);
+        }
+    }
+
+    #[require_transactional]
+    fn remove_expired_order(order_id: OrderId, order: &OrderOf<T>) -> DispatchResult {
+        let remaining_to_unreserve =
+            T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, order.amount_out);
+        ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
+
+        Self::remove_order(order_id, order);
+
+        Self::deposit_event(Event::Expired { order_id });
+        Ok(())
+    }
+
+    /// Removes orders which expired at or before block `now`, starting at the block stored in `ExpiryCursor`.
+    /// Stops as soon as the next step would not fit into `remaining_weight`.
+    fn remove_expired_orders(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let expire_order_weight = T::WeightInfo::cancel_expired();
+
+        // cursor read and write
+        let mut used_weight = db_weight.reads_writes(1, 1);
+        if remaining_weight.any_lt(used_weight) {
+            return Weight::zero();
+        }
+
+        let mut cursor = Self::expiry_cursor();
+        if cursor.is_zero() {
+            return db_weight.reads(1);
+        }
+        let initial_cursor = cursor;
+
+        while cursor <= now {
+            // reading the prefix of the current block
+            let next_weight = used_weight.saturating_add(db_weight.reads(1));
+            if remaining_weight.any_lt(next_weight) {
+                break;
+            }
+            used_weight = next_weight;
+
+            let mut order_ids = Vec::new();
+            let mut exhausted = true;
+            for order_id in <OrderExpiries<T>>::iter_key_prefix(cursor) {
+                let next_weight = used_weight.saturating_add(expire_order_weight);
+                if remaining_weight.any_lt(next_weight) {
+                    exhausted = false;
+                    break;
+                }
+                used_weight = next_weight;
+                order_ids.push(order_id);
+            }
+
+            for order_id in order_ids {
+                // The index entry is removed even if the order cannot be, so that it does not block the sweep.
+                // Such an order can still be cancelled by its owner.
+                <OrderExpiries<T>>::remove(cursor, order_id);
+                if let Some(order) = <Orders<T>>::get(order_id) {
+                    let _ = Self::with_rollback_on_error(|| Self::remove_expired_order(order_id, &order));
+                }
+            }
+
+            if !exhausted {
+                break;
+            }
+            cursor.saturating_inc();
+        }
+
+        if cursor != initial_cursor {
+            <ExpiryCursor<T>>::put(cursor);
+        }
+
+        used_weight
+    }
+
+    /// Calculates the fee for `amount` of `asset`. Returns zero if `FeeReceiver` would end up with a balance
+    /// below the existential deposit of `asset`.
+    fn calculate_fee(asset: T::AssetId, amount: Balance) -> Balance {
+        let fee = T::Fee::get().mul_floor(amount);
+        let receiver_balance = T::Currency::total_balance(asset, &T::FeeReceiver::get());
+
+        if receiver_balance.saturating_add(fee) < T::ExistentialDeposits::get(&asset) {
+            Zero::zero()
+        } else {
+            fee
+        }
+    }
+
+    /// Transfers `amount_in` from the taker to the order owner and `amount_out` from the reserve of the order
+    /// owner to the taker, reduced by the fee. Returns the fee.
     #[require_transactional]
     fn execute_order(
-        order: &Order<T::AccountId, T::AssetId>,
+        order: &OrderOf<T>,
         who: &T::AccountId,
         amount_in: Balance,
         amount_out: Balance,
-    ) -> DispatchResult {
+    ) -> Result<Balance, DispatchError> {
+        let fee = Self::calculate_fee(order.asset_out, amount_out);
+
         T::Currency::transfer(order.asset_in, who, &order.owner, amount_in)?;
         let remaining_to_unreserve =
             T::Currency::unreserve_named(&NAMED_RESERVE_ID, order.asset_out, &order.owner, amount_out);
         ensure!(remaining_to_unreserve.is_zero(), Error::<T>::InsufficientReservedAmount);
-        T::Currency::transfer(order.asset_out, &order.owner, who, amount_out)?;
+        T::Currency::transfer(
+            order.asset_out,
+            &order.owner,
+            who,
+            amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?,
+        )?;
+        if !fee.is_zero() {
+            T::Currency::transfer(order.asset_out, &order.owner, &T::FeeReceiver::get(), fee)?;
+        }
 
-        Ok(())
+        Ok(fee)
+    }
+}
+
+// Queries used by the runtime API.
+impl<T: Config> Pallet<T> {
+    /// Returns the open orders of `owner`.
+    pub fn orders_by_owner(owner: &T::AccountId) -> Vec<(OrderId, OrderOf<T>)> {
+        <OrdersByOwner<T>>::iter_key_prefix(owner)
+            .filter_map(|order_id| <Orders<T>>::get(order_id).map(|order| (order_id, order)))
+            .collect()
+    }
+
+    /// Returns the open orders of the pair of assets, sorted from the best to the worst price for a taker,
+    /// followed by the pegged orders of the pair sorted by their ID.
+    pub fn orders_by_pair(asset_in: T::AssetId, asset_out: T::AssetId) -> Vec<(OrderId, OrderOf<T>)> {
+        let mut orders: Vec<(OrderId, OrderOf<T>)> = Self::order_book((asset_in, asset_out))
+            .into_iter()
+            .filter_map(|(_, order_id)| <Orders<T>>::get(order_id).map(|order| (order_id, order)))
+            .collect();
+
+        let mut pegged_order_ids: Vec<OrderId> = <PeggedOrders<T>>::iter_key_prefix((asset_in, asset_out)).collect();
+        pegged_order_ids.sort();
+        orders.extend(
+            pegged_order_ids
+                .into_iter()
+                .filter_map(|order_id| <Orders<T>>::get(order_id).map(|order| (order_id, order))),
+        );
+
+        orders
+    }
+
+    /// Returns the amounts exchanged if the order was filled with `amount_in` by a taker allowed to fill it.
+    /// If `amount_in` is higher than the amount required to fill the whole order, the order is quoted as filled
+    /// completely.
+    pub fn quote_fill(order_id: OrderId, amount_in: Balance) -> Result<FillQuote, DispatchError> {
+        let order = <Orders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+        Self::ensure_not_expired(&order)?;
+
+        let order_amount_in = Self::order_amount_in(&order)?;
+        let (amount_in, amount_out) = if amount_in >= order_amount_in {
+            (order_amount_in, order.amount_out)
+        } else {
+            ensure!(order.partially_fillable, Error::<T>::OrderNotPartiallyFillable);
+            let amount_out = Self::calculate_amount(order.amount_out, amount_in, order_amount_in, false)?;
+
+            let remaining_amount_out = order.amount_out.checked_sub(amount_out).ok_or(Error::<T>::MathError)?;
+            Self::ensure_min_order_amount(order.asset_out, remaining_amount_out)?;
+            Self::ensure_min_order_amount(order.asset_in, order_amount_in.saturating_sub(amount_in))?;
+
+            (amount_in, amount_out)
+        };
+
+        let fee = Self::calculate_fee(order.asset_out, amount_out);
+
+        Ok(FillQuote {
+            amount_in,
+            amount_out: amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?,
+            fee,
+        })
     }
 }
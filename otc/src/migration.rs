@@ -0,0 +1,144 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Balance, Config, Order, OrderCount, OrderId, Orders, OrdersByOwner, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+    log,
+    sp_runtime::RuntimeDebug,
+    traits::{Get, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Order before the expiry and the oracle peg were added in storage version 1.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct OldOrder<AccountId, AssetId> {
+    pub owner: AccountId,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    pub amount_in: Balance,
+    pub amount_out: Balance,
+    pub partially_fillable: bool,
+}
+
+impl<AccountId, AssetId, BlockNumber> From<OldOrder<AccountId, AssetId>> for Order<AccountId, AssetId, BlockNumber> {
+    fn from(order: OldOrder<AccountId, AssetId>) -> Self {
+        Self {
+            owner: order.owner,
+            asset_in: order.asset_in,
+            asset_out: order.asset_out,
+            amount_in: order.amount_in,
+            amount_out: order.amount_out,
+            partially_fillable: order.partially_fillable,
+            expires_at: None,
+            peg: None,
+        }
+    }
+}
+
+/// Orders have an optional expiry and oracle peg and are indexed by their pair of assets and by their owner.
+pub mod v1 {
+    use super::*;
+
+    pub fn pre_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Storage version too high.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: PRE checks successful!"
+        );
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        log::info!(
+            target: "runtime::otc",
+            "Running migration to v1 for OTC"
+        );
+
+        let mut i: u64 = 0;
+        Orders::<T>::translate(|_order_id: OrderId, order: OldOrder<T::AccountId, T::AssetId>| {
+            i += 1;
+            Some(order.into())
+        });
+
+        // Orders placed before the order book and the owner index were added are indexed now. Accounts may end up
+        // with more than `MaxOrdersPerAccount` orders, in which case they cannot place new ones until some are closed.
+        for (order_id, order) in Orders::<T>::iter() {
+            OrdersByOwner::<T>::insert(&order.owner, order_id, ());
+            OrderCount::<T>::mutate(&order.owner, |count| *count = count.saturating_add(1));
+            if Pallet::<T>::insert_into_order_book(order_id, &order).is_err() {
+                log::warn!(
+                    target: "runtime::otc",
+                    "OTC migration: order {:?} could not be added to the full order book, it can only be filled directly",
+                    order_id
+                );
+            }
+        }
+
+        StorageVersion::new(1).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(i.saturating_mul(4), i.saturating_mul(4).saturating_add(1))
+    }
+
+    pub fn post_migrate<T: Config>() {
+        assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Unexpected storage version.");
+
+        log::info!(
+            target: "runtime::otc",
+            "OTC migration: POST checks successful!"
+        );
+    }
+
+    /// Runs the migration to v1 if the pallet is still at storage version 0.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                log::info!(
+                    target: "runtime::otc",
+                    "OTC migration to v1 skipped - storage version already upgraded"
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>();
+
+            let count = Orders::<T>::iter_keys().count() as u32;
+            Ok(count.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>();
+
+            let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre upgrade state.")?;
+            if Orders::<T>::iter().count() as u32 != count {
+                return Err("Not all orders were migrated.");
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{AllowedTakers, Error, Event};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pretty_assertions::assert_eq;
+
+fn takers(accounts: Vec<AccountId>) -> BoundedVec<AccountId, MaxAllowedTakers> {
+    accounts.try_into().unwrap()
+}
+
+#[test]
+fn place_order_should_store_allowed_takers() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![BOB, CHARLIE]))
+        ));
+
+        // Assert
+        assert_eq!(OTC::allowed_takers(0), Some(takers(vec![BOB, CHARLIE])));
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: None,
+            allowed_takers: Some(takers(vec![BOB, CHARLIE])),
+            peg: None,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_allowed_takers_are_empty() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                Some(takers(vec![]))
+            ),
+            Error::<Test>::NoAllowedTakers
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_work_when_caller_is_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![CHARLIE]))
+        ));
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(CHARLIE), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(!AllowedTakers::<Test>::contains_key(0));
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_caller_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![CHARLIE]))
+        ));
+
+        // Act
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::NotAllowedTaker
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_work_when_caller_is_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![BOB, CHARLIE]))
+        ));
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(CHARLIE), 0, 10 * ONE));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 10 * ONE);
+        assert_eq!(OTC::allowed_takers(0), Some(takers(vec![BOB, CHARLIE])));
+    });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_caller_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![CHARLIE]))
+        ));
+
+        // Act
+        assert_noop!(
+            OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE),
+            Error::<Test>::NotAllowedTaker
+        );
+    });
+}
+
+#[test]
+fn cancel_order_should_remove_allowed_takers() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(takers(vec![CHARLIE]))
+        ));
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!AllowedTakers::<Test>::contains_key(0));
+    });
+}
@@ -30,7 +30,9 @@ fn cancel_order_should_work() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Act
@@ -67,7 +69,9 @@ fn cancel_order_should_throw_error_when_called_by_non_owner() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Act
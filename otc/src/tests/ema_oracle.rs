@@ -0,0 +1,74 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::ema_oracle_mock::*;
+use crate::Error;
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn fill_order_should_work_when_oracle_is_older_than_max_staleness_but_updated_recently() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_pegged_order();
+        for block in 1..=20 {
+            trade(block, 1_000 * ONE, 5_000 * ONE);
+        }
+        System::set_block_number(21);
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 20 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), 10_100 * ONE);
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_is_stale() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_pegged_order();
+        for block in 1..=10 {
+            trade(block, 1_000 * ONE, 5_000 * ONE);
+        }
+        System::set_block_number(10 + MaxOracleStaleness::get() + 1);
+
+        // Act
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OracleStale
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_is_not_mature() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_pegged_order();
+        trade(1, 1_000 * ONE, 5_000 * ONE);
+        System::set_block_number(2);
+
+        // Act
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OracleNotMature
+        );
+    });
+}
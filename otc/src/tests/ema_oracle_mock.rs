@@ -0,0 +1,259 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment pricing pegged orders with the EMA oracle.
+
+use crate as otc;
+use crate::tests::mock::{DummyRegistry, REGISTERED_ASSETS};
+use crate::{Config, OracleLastUpdate, Price};
+use frame_support::{
+    assert_ok, bounded_vec, parameter_types,
+    traits::{ConstU32, ConstU64, Everything, GenesisBuild, Get, Hooks, Nothing},
+    weights::Weight,
+};
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OraclePeriod, Source},
+    OnTradeHandler,
+};
+use orml_tokens::AccountData;
+use orml_traits::parameter_type_with_key;
+use pallet_ema_oracle::{ordered_pair, OnActivityHandler, OracleError, LAST_BLOCK_PERIOD_LENGTH};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    FixedPointNumber, Permill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Amount = i128;
+pub type AssetId = u32;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+pub type NamedReserveIdentifier = [u8; 8];
+
+pub const HDX: AssetId = 0;
+pub const DAI: AssetId = 2;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const TREASURY: AccountId = 5;
+
+pub const SOURCE: Source = *b"dummysrc";
+
+frame_support::construct_runtime!(
+    pub enum Test where
+     Block = Block,
+     NodeBlock = Block,
+     UncheckedExtrinsic = UncheckedExtrinsic,
+     {
+         System: frame_system,
+         OTC: otc,
+         Tokens: orml_tokens,
+         EmaOracle: pallet_ema_oracle,
+     }
+);
+
+parameter_types! {
+    pub ExistentialDepositMultiplier: u8 = 5;
+    pub const FeeReceiver: AccountId = TREASURY;
+    pub const OracleSource: Source = SOURCE;
+    pub const MinOracleAge: BlockNumber = 5;
+    pub const MaxOracleStaleness: BlockNumber = 10;
+    pub const MaxReserves: u32 = 50;
+}
+
+parameter_type_with_key! {
+    pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+        ONE
+    };
+}
+
+impl Config for Test {
+    type AssetId = AssetId;
+    type AssetRegistry = DummyRegistry<Test>;
+    type Currency = Tokens;
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposits = ExistentialDeposits;
+    type ExistentialDepositMultiplier = ExistentialDepositMultiplier;
+    type MaxAllowedTakers = ConstU32<3>;
+    type MaxOrdersPerPair = ConstU32<5>;
+    type MaxOrdersPerAccount = ConstU32<10>;
+    type Fee = ();
+    type FeeReceiver = FeeReceiver;
+    type Oracle = EmaPriceOracle;
+    type OracleSource = OracleSource;
+    type MinOracleAge = MinOracleAge;
+    type MaxOracleStaleness = MaxOracleStaleness;
+    type WeightInfo = ();
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl orml_tokens::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type Amount = Amount;
+    type CurrencyId = AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type MaxLocks = ();
+    type DustRemovalWhitelist = Nothing;
+    type ReserveIdentifier = NamedReserveIdentifier;
+    type MaxReserves = MaxReserves;
+    type CurrencyHooks = ();
+}
+
+pallet_ema_oracle::impl_test_config!(Test, bounded_vec![OraclePeriod::LastBlock, OraclePeriod::Short]);
+
+/// Adapter providing the prices of the EMA oracle as `Price` together with the block of their last update.
+pub struct EmaPriceOracle;
+
+impl AggregatedPriceOracle<AssetId, BlockNumber, Price> for EmaPriceOracle {
+    type Error = OracleError;
+
+    fn get_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(Price, BlockNumber), Self::Error> {
+        let (price, oracle_age) = EmaOracle::get_price(asset_a, asset_b, period, source)?;
+        let (n, d): (Balance, Balance) = price.into();
+        let price = Price::checked_from_rational(n, d).ok_or(OracleError::NotPresent)?;
+        Ok((price, oracle_age))
+    }
+
+    fn get_price_weight() -> Weight {
+        <EmaOracle as AggregatedPriceOracle<AssetId, BlockNumber, pallet_ema_oracle::Price>>::get_price_weight()
+    }
+}
+
+impl OracleLastUpdate<AssetId, BlockNumber> for EmaPriceOracle {
+    fn last_update(asset_a: AssetId, asset_b: AssetId, source: Source) -> Option<BlockNumber> {
+        EmaOracle::oracle((source, ordered_pair(asset_a, asset_b), LAST_BLOCK_PERIOD_LENGTH))
+            .map(|(entry, _)| entry.timestamp)
+    }
+
+    fn last_update_weight() -> Weight {
+        <Test as frame_system::Config>::DbWeight::get().reads(1)
+    }
+}
+
+/// Record a trade of `DAI/HDX` with the given liquidity and finalize the block.
+pub fn trade(block: BlockNumber, liquidity_dai: Balance, liquidity_hdx: Balance) {
+    System::set_block_number(block);
+    assert_ok!(OnActivityHandler::<Test>::on_trade(
+        SOURCE,
+        DAI,
+        HDX,
+        ONE,
+        ONE,
+        liquidity_dai,
+        liquidity_hdx
+    ));
+    EmaOracle::on_finalize(block);
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        REGISTERED_ASSETS.with(|v| {
+            v.borrow_mut().clear();
+        });
+
+        Self {
+            endowed_accounts: vec![
+                (ALICE, HDX, 10_000 * ONE),
+                (BOB, HDX, 10_000 * ONE),
+                (ALICE, DAI, 100 * ONE),
+                (BOB, DAI, 100 * ONE),
+            ],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        REGISTERED_ASSETS.with(|v| {
+            v.borrow_mut().insert(HDX, HDX);
+            v.borrow_mut().insert(DAI, DAI);
+        });
+
+        orml_tokens::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut r: sp_io::TestExternalities = t.into();
+
+        r.execute_with(|| {
+            System::set_block_number(1);
+        });
+
+        r
+    }
+}
+
+/// Place a pegged order of `ALICE` selling 100 HDX for DAI at the `LastBlock` oracle price.
+pub fn place_alice_pegged_order() {
+    assert_ok!(OTC::place_pegged_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        100 * ONE,
+        OraclePeriod::LastBlock,
+        otc::PriceAdjustment::Premium(Permill::zero()),
+        true,
+        None,
+        None
+    ));
+}
@@ -0,0 +1,414 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as otc;
+use crate::tests::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, ExpiryCursor, OrderExpiries};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn place_order_should_store_expiry_when_expires_at_is_set() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.expires_at, Some(10));
+        assert!(OrderExpiries::<Test>::contains_key(10, 0));
+        assert_eq!(OTC::expiry_cursor(), 10);
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: Some(10),
+            allowed_takers: None,
+            peg: None,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn place_order_should_move_expiry_cursor_to_earliest_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+
+        // Assert
+        assert_eq!(OTC::expiry_cursor(), 5);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_expires_at_is_not_in_future() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                Some(1),
+                None
+            ),
+            Error::<Test>::InvalidExpiry
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_work_before_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        System::set_block_number(9);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(!OrderExpiries::<Test>::contains_key(10, 0));
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_order_is_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        System::set_block_number(10);
+
+        // Act
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_throw_error_when_order_is_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        System::set_block_number(11);
+
+        // Act
+        assert_noop!(
+            OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn cancel_order_should_remove_expiry_entry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!OrderExpiries::<Test>::contains_key(10, 0));
+    });
+}
+
+#[test]
+fn cancel_expired_should_work_when_called_by_anyone() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
+        System::set_block_number(10);
+
+        // Act
+        assert_ok!(OTC::cancel_expired(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(!OrderExpiries::<Test>::contains_key(10, 0));
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(
+            Tokens::free_balance(HDX, &ALICE),
+            alice_free_hdx_balance_before + 100 * ONE
+        );
+
+        expect_events(vec![Event::Expired { order_id: 0 }.into()]);
+    });
+}
+
+#[test]
+fn cancel_expired_should_throw_error_when_order_is_not_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        System::set_block_number(9);
+
+        // Act
+        assert_noop!(
+            OTC::cancel_expired(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderNotExpired
+        );
+    });
+}
+
+#[test]
+fn cancel_expired_should_throw_error_when_order_has_no_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            None
+        ));
+        System::set_block_number(1_000);
+
+        // Act
+        assert_noop!(
+            OTC::cancel_expired(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OrderNotExpired
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_orders() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            10 * ONE,
+            50 * ONE,
+            true,
+            Some(10),
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(BOB),
+            HDX,
+            DAI,
+            50 * ONE,
+            10 * ONE,
+            true,
+            Some(6),
+            None
+        ));
+        System::set_block_number(8);
+
+        // Act
+        OTC::on_idle(8, Weight::MAX);
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_some());
+        assert!(OTC::orders(2).is_none());
+        assert_eq!(OTC::expiry_cursor(), 9);
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            50 * ONE
+        );
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB), 0);
+
+        expect_events(vec![
+            Event::Expired { order_id: 0 }.into(),
+            Event::Expired { order_id: 2 }.into(),
+        ]);
+    });
+}
+
+#[test]
+fn on_idle_should_not_remove_orders_when_there_is_not_enough_weight() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        System::set_block_number(8);
+
+        // Act
+        let used_weight = OTC::on_idle(8, Weight::zero());
+
+        // Assert
+        assert_eq!(used_weight, Weight::zero());
+        assert!(OTC::orders(0).is_some());
+        assert_eq!(OTC::expiry_cursor(), 5);
+    });
+}
+
+#[test]
+fn on_idle_should_continue_where_it_stopped_when_weight_was_exhausted() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            10 * ONE,
+            50 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        System::set_block_number(8);
+
+        // Act - enough weight for a single order only
+        OTC::on_idle(8, <Test as otc::Config>::WeightInfo::cancel_expired());
+
+        // Assert
+        assert_eq!(OrderExpiries::<Test>::iter_prefix(5).count(), 1);
+        assert_eq!(ExpiryCursor::<Test>::get(), 5);
+
+        // Act
+        System::set_block_number(9);
+        OTC::on_idle(9, Weight::MAX);
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_none());
+        assert_eq!(ExpiryCursor::<Test>::get(), 10);
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+    });
+}
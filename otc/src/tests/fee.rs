@@ -0,0 +1,168 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::Event;
+use frame_support::assert_ok;
+use hydradx_traits::AssetPair;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::{FixedPointNumber, Permill};
+
+fn place_alice_order() {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn fill_order_should_transfer_fee_to_fee_receiver() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_order();
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 99 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &TREASURY), ONE);
+
+            expect_events(vec![Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                fee: ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn partial_fill_order_should_transfer_fee_to_fee_receiver() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_order();
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 49 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &TREASURY), ONE);
+
+            expect_events(vec![Event::PartiallyFilled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 50 * ONE,
+                fee: ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn fee_should_not_be_charged_when_fee_receiver_would_stay_below_existential_deposit() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_order();
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 50 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &TREASURY), 0);
+
+            expect_events(vec![Event::PartiallyFilled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 50 * ONE,
+                fee: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn fee_below_existential_deposit_should_be_charged_when_fee_receiver_has_balance() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_order();
+            place_alice_order();
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 1, 10 * ONE));
+
+            // Assert
+            assert_eq!(
+                Tokens::free_balance(HDX, &BOB),
+                bob_hdx_balance_before + 50 * ONE - ONE / 2
+            );
+            assert_eq!(Tokens::free_balance(HDX, &TREASURY), ONE + ONE / 2);
+        });
+}
+
+#[test]
+fn fill_best_should_charge_fee_for_each_fill() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(1))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_order();
+            place_alice_order();
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::fill_best(
+                RuntimeOrigin::signed(BOB),
+                AssetPair::new(DAI, HDX),
+                40 * ONE,
+                crate::Price::saturating_from_integer(1u128)
+            ));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 198 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &TREASURY), 2 * ONE);
+        });
+}
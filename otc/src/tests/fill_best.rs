@@ -0,0 +1,288 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::weights::WeightInfo;
+use crate::{Error, Event, OrderBook, Price};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AssetPair;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedPointNumber;
+
+fn price(n: u128) -> Price {
+    Price::saturating_from_integer(n)
+}
+
+fn place_orders() {
+    // price 5
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        true,
+        None,
+        None
+    ));
+    // price 10
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        10 * ONE,
+        100 * ONE,
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn fill_best_should_fill_order_with_best_price_first() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_best(
+            RuntimeOrigin::signed(BOB),
+            AssetPair::new(DAI, HDX),
+            10 * ONE,
+            price(1)
+        ));
+
+        // Assert
+        assert!(OTC::orders(1).is_none());
+        assert_eq!(OTC::orders(0).unwrap().amount_in, 20 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 100 * ONE);
+        assert_eq!(OrderBook::<Test>::get((DAI, HDX)).to_vec(), vec![(price(5), 0)]);
+
+        expect_events(vec![Event::Filled {
+            order_id: 1,
+            who: BOB,
+            amount_in: 10 * ONE,
+            amount_out: 100 * ONE,
+            fee: 0,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn fill_best_should_partially_fill_last_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_best(
+            RuntimeOrigin::signed(BOB),
+            AssetPair::new(DAI, HDX),
+            20 * ONE,
+            price(1)
+        ));
+
+        // Assert
+        assert!(OTC::orders(1).is_none());
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 10 * ONE);
+        assert_eq!(order.amount_out, 50 * ONE);
+
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 20 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 150 * ONE);
+
+        expect_events(vec![
+            Event::Filled {
+                order_id: 1,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 100 * ONE,
+                fee: 0,
+            }
+            .into(),
+            Event::PartiallyFilled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 50 * ONE,
+                fee: 0,
+            }
+            .into(),
+        ]);
+    });
+}
+
+#[test]
+fn fill_best_should_stop_at_limit_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+        let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+
+        // Act
+        assert_ok!(OTC::fill_best(
+            RuntimeOrigin::signed(BOB),
+            AssetPair::new(DAI, HDX),
+            20 * ONE,
+            price(6)
+        ));
+
+        // Assert
+        assert!(OTC::orders(1).is_none());
+        assert_eq!(OTC::orders(0).unwrap().amount_in, 20 * ONE);
+        assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 10 * ONE);
+    });
+}
+
+#[test]
+fn fill_best_should_skip_order_when_it_is_not_partially_fillable_and_amount_is_lower() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            10 * ONE,
+            100 * ONE,
+            false,
+            None,
+            None
+        ));
+
+        // Act
+        assert_ok!(OTC::fill_best(
+            RuntimeOrigin::signed(BOB),
+            AssetPair::new(DAI, HDX),
+            5 * ONE,
+            price(1)
+        ));
+
+        // Assert
+        assert_eq!(OTC::orders(1).unwrap().amount_in, 10 * ONE);
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 15 * ONE);
+        assert_eq!(order.amount_out, 75 * ONE);
+    });
+}
+
+#[test]
+fn fill_best_should_skip_order_when_caller_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            None
+        ));
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            10 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(vec![CHARLIE].try_into().unwrap())
+        ));
+
+        // Act
+        assert_ok!(OTC::fill_best(
+            RuntimeOrigin::signed(BOB),
+            AssetPair::new(DAI, HDX),
+            10 * ONE,
+            price(1)
+        ));
+
+        // Assert
+        assert_eq!(OTC::orders(1).unwrap().amount_in, 10 * ONE);
+        assert_eq!(OTC::orders(0).unwrap().amount_in, 10 * ONE);
+    });
+}
+
+#[test]
+fn fill_best_should_throw_error_when_no_order_is_filled() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+
+        // Act
+        assert_noop!(
+            OTC::fill_best(
+                RuntimeOrigin::signed(BOB),
+                AssetPair::new(DAI, HDX),
+                10 * ONE,
+                price(11)
+            ),
+            Error::<Test>::NoOrderFilled
+        );
+    });
+}
+
+#[test]
+fn fill_best_should_throw_error_when_there_are_no_orders_for_pair() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+
+        // Act
+        assert_noop!(
+            OTC::fill_best(
+                RuntimeOrigin::signed(ALICE),
+                AssetPair::new(HDX, DAI),
+                10 * ONE,
+                price(0)
+            ),
+            Error::<Test>::NoOrderFilled
+        );
+    });
+}
+
+#[test]
+fn fill_best_should_charge_weight_of_walked_orders_only() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_orders();
+
+        // Act
+        let post_info = OTC::fill_best(RuntimeOrigin::signed(BOB), AssetPair::new(DAI, HDX), 10 * ONE, price(1))
+            .expect("fill_best should succeed");
+
+        // Assert
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<Test as crate::Config>::WeightInfo::fill_best(1))
+        );
+    });
+}
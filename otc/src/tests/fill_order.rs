@@ -30,7 +30,9 @@ fn complete_fill_order_should_work() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
@@ -67,6 +69,7 @@ fn complete_fill_order_should_work() {
             who: BOB,
             amount_in: 20 * ONE,
             amount_out: 100 * ONE,
+            fee: 0,
         }
         .into()]);
     });
@@ -82,7 +85,9 @@ fn complete_fill_order_should_work_when_order_is_not_partially_fillable() {
             HDX,
             20 * ONE,
             100 * ONE,
-            false
+            false,
+            None,
+            None
         ));
 
         let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
@@ -119,6 +124,7 @@ fn complete_fill_order_should_work_when_order_is_not_partially_fillable() {
             who: BOB,
             amount_in: 20 * ONE,
             amount_out: 100 * ONE,
+            fee: 0,
         }
         .into()]);
     });
@@ -134,7 +140,9 @@ fn complete_fill_order_should_work_when_there_are_multiple_orders() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         assert_ok!(OTC::place_order(
@@ -143,7 +151,9 @@ fn complete_fill_order_should_work_when_there_are_multiple_orders() {
             HDX,
             10 * ONE,
             50 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
@@ -183,6 +193,7 @@ fn complete_fill_order_should_work_when_there_are_multiple_orders() {
             who: BOB,
             amount_in: 20 * ONE,
             amount_out: 100 * ONE,
+            fee: 0,
         }
         .into()]);
     });
@@ -198,7 +209,9 @@ fn fill_order_should_throw_error_when_insufficient_balance() {
             HDX,
             200 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         let alice_free_hdx_balance_before = Tokens::free_balance(HDX, &ALICE);
@@ -99,7 +99,9 @@ proptest! {
                 HDX,
                 initial_amount_in,
                 initial_amount_out,
-                true
+                true,
+                None,
+                None
             ).unwrap();
 
             let initial_price = FixedU128::from_rational(initial_amount_out, initial_amount_in);
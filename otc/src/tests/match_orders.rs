@@ -0,0 +1,256 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+
+// Alice sells 100 HDX for 20 DAI
+fn place_alice_order(partially_fillable: bool) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        20 * ONE,
+        100 * ONE,
+        partially_fillable,
+        None,
+        None
+    ));
+}
+
+fn place_bob_order(amount_in: Balance, amount_out: Balance) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(BOB),
+        HDX,
+        DAI,
+        amount_in,
+        amount_out,
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn match_orders_should_work_when_both_orders_are_filled_completely() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(false);
+        // Bob sells 25 DAI for 100 HDX
+        place_bob_order(100 * ONE, 25 * ONE);
+
+        let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+        let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+        let charlie_dai_balance_before = Tokens::free_balance(DAI, &CHARLIE);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_none());
+
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+        assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, DAI, &BOB), 0);
+
+        assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 20 * ONE);
+        assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 100 * ONE);
+        assert_eq!(
+            Tokens::free_balance(DAI, &CHARLIE),
+            charlie_dai_balance_before + 5 * ONE
+        );
+
+        expect_events(vec![Event::Matched {
+            order_a: 0,
+            order_b: 1,
+            who: CHARLIE,
+            amount_a: 100 * ONE,
+            amount_b: 20 * ONE,
+            surplus: 5 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn match_orders_should_partially_fill_larger_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(true);
+        // Bob sells 15 DAI for 50 HDX
+        place_bob_order(50 * ONE, 15 * ONE);
+
+        let charlie_dai_balance_before = Tokens::free_balance(DAI, &CHARLIE);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.amount_in, 10 * ONE);
+        assert_eq!(order.amount_out, 50 * ONE);
+        assert!(OTC::orders(1).is_none());
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            50 * ONE
+        );
+        assert_eq!(
+            Tokens::free_balance(DAI, &CHARLIE),
+            charlie_dai_balance_before + 5 * ONE
+        );
+
+        expect_events(vec![Event::Matched {
+            order_a: 0,
+            order_b: 1,
+            who: CHARLIE,
+            amount_a: 50 * ONE,
+            amount_b: 10 * ONE,
+            surplus: 5 * ONE,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn match_orders_should_work_when_prices_are_equal() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(false);
+        place_bob_order(100 * ONE, 20 * ONE);
+
+        let charlie_dai_balance_before = Tokens::free_balance(DAI, &CHARLIE);
+
+        // Act
+        assert_ok!(OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1));
+
+        // Assert
+        assert!(OTC::orders(0).is_none());
+        assert!(OTC::orders(1).is_none());
+        assert_eq!(Tokens::free_balance(DAI, &CHARLIE), charlie_dai_balance_before);
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_prices_do_not_cross() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(true);
+        // Bob sells 15 DAI for 100 HDX
+        place_bob_order(100 * ONE, 15 * ONE);
+
+        // Act
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::PricesNotCrossing
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_partially_filled_order_is_not_partially_fillable() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(false);
+        place_bob_order(50 * ONE, 15 * ONE);
+
+        // Act
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderNotPartiallyFillable
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_assets_do_not_match() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(true);
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(BOB),
+            REGISTERED_ASSET,
+            DAI,
+            50 * ONE,
+            15 * ONE,
+            true,
+            None,
+            None
+        ));
+
+        // Act
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrdersNotMatching
+        );
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 0),
+            Error::<Test>::OrdersNotMatching
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_order_is_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_order(true);
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(BOB),
+            HDX,
+            DAI,
+            50 * ONE,
+            15 * ONE,
+            true,
+            Some(5),
+            None
+        ));
+        System::set_block_number(5);
+
+        // Act
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::OrderExpired
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_counterparty_is_not_allowed_taker() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            true,
+            None,
+            Some(vec![CHARLIE].try_into().unwrap())
+        ));
+        place_bob_order(50 * ONE, 15 * ONE);
+
+        // Act
+        assert_noop!(
+            OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+            Error::<Test>::NotAllowedTaker
+        );
+    });
+}
@@ -0,0 +1,121 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::migration::{v1, OldOrder};
+use crate::tests::mock::*;
+use crate::{Order, OrderBook, OrderId, Orders, OrdersByOwner, Price};
+use frame_support::{
+    storage::unhashed,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedPointNumber;
+
+fn insert_old_order(order_id: OrderId, order: OldOrder<AccountId, AssetId>) {
+    unhashed::put(&Orders::<Test>::hashed_key_for(order_id), &order);
+}
+
+fn old_order(owner: AccountId, asset_in: AssetId, asset_out: AssetId) -> OldOrder<AccountId, AssetId> {
+    OldOrder {
+        owner,
+        asset_in,
+        asset_out,
+        amount_in: 20 * ONE,
+        amount_out: 100 * ONE,
+        partially_fillable: true,
+    }
+}
+
+#[test]
+fn migration_to_v1_should_translate_orders() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        StorageVersion::new(0).put::<OTC>();
+        insert_old_order(0, old_order(ALICE, DAI, HDX));
+        insert_old_order(1, old_order(BOB, HDX, DAI));
+
+        // Act
+        v1::pre_migrate::<Test>();
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        v1::post_migrate::<Test>();
+
+        // Assert
+        assert_eq!(OTC::on_chain_storage_version(), 1);
+        assert!(OrdersByOwner::<Test>::contains_key(ALICE, 0));
+        assert!(OrdersByOwner::<Test>::contains_key(BOB, 1));
+        assert_eq!(OTC::order_count(ALICE), 1);
+        assert_eq!(OTC::order_count(BOB), 1);
+        assert_eq!(
+            OrderBook::<Test>::get((DAI, HDX)).to_vec(),
+            vec![(Price::saturating_from_integer(5), 0)]
+        );
+        assert_eq!(
+            OrderBook::<Test>::get((HDX, DAI)).to_vec(),
+            vec![(Price::saturating_from_integer(5), 1)]
+        );
+        assert_eq!(
+            OTC::orders(0),
+            Some(Order {
+                owner: ALICE,
+                asset_in: DAI,
+                asset_out: HDX,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                partially_fillable: true,
+                expires_at: None,
+                peg: None,
+            })
+        );
+        assert_eq!(
+            OTC::orders(1),
+            Some(Order {
+                owner: BOB,
+                asset_in: HDX,
+                asset_out: DAI,
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                partially_fillable: true,
+                expires_at: None,
+                peg: None,
+            })
+        );
+    });
+}
+
+#[test]
+fn migration_to_v1_should_be_skipped_when_storage_version_is_current() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        StorageVersion::new(1).put::<OTC>();
+        let order = Order {
+            owner: ALICE,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 20 * ONE,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: Some(10),
+            peg: None,
+        };
+        Orders::<Test>::insert(0, order.clone());
+
+        // Act
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        // Assert
+        assert_eq!(OTC::on_chain_storage_version(), 1);
+        assert_eq!(OTC::orders(0), Some(order));
+    });
+}
@@ -14,20 +14,24 @@
 // limitations under the License.
 
 use crate as otc;
-use crate::Config;
+use crate::{Config, OracleLastUpdate, Price};
 use frame_support::{
     parameter_types,
     traits::{Everything, GenesisBuild, Nothing},
+    weights::Weight,
 };
 use frame_system as system;
-use hydradx_traits::Registry;
+use hydradx_traits::{
+    oracle::{AggregatedPriceOracle, OraclePeriod, Source},
+    Registry,
+};
 use orml_tokens::AccountData;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
-    DispatchError,
+    DispatchError, Permill,
 };
 use std::{cell::RefCell, collections::HashMap};
 
@@ -49,6 +53,8 @@ pub const ONE: Balance = 1_000_000_000_000;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const TREASURY: AccountId = 5;
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -66,11 +72,22 @@ thread_local! {
     pub static REGISTERED_ASSETS: RefCell<HashMap<AssetId, u32>> = RefCell::new(HashMap::default());
     pub static EXISTENTIAL_DEPOSIT: RefCell<HashMap<AssetId, u128>>= RefCell::new(HashMap::default());
     pub static PRECISIONS: RefCell<HashMap<AssetId, u32>>= RefCell::new(HashMap::default());
+    pub static FEE: RefCell<Permill> = RefCell::new(Permill::zero());
+    pub static ORACLE_PRICES: RefCell<HashMap<(AssetId, AssetId), (Price, u64)>> = RefCell::new(HashMap::default());
+    pub static ORACLE_LAST_UPDATES: RefCell<HashMap<(AssetId, AssetId), u64>> = RefCell::new(HashMap::default());
 }
 
 parameter_types! {
     pub NativeCurrencyId: AssetId = HDX;
     pub ExistentialDepositMultiplier: u8 = 5;
+    pub const MaxAllowedTakers: u32 = 3;
+    pub const MaxOrdersPerPair: u32 = 5;
+    pub const MaxOrdersPerAccount: u32 = 10;
+    pub Fee: Permill = FEE.with(|v| *v.borrow());
+    pub const FeeReceiver: AccountId = TREASURY;
+    pub const OracleSource: Source = *b"testsrc_";
+    pub const MinOracleAge: u64 = 10;
+    pub const MaxOracleStaleness: u64 = 10;
 }
 
 parameter_type_with_key! {
@@ -86,6 +103,15 @@ impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ExistentialDeposits = ExistentialDeposits;
     type ExistentialDepositMultiplier = ExistentialDepositMultiplier;
+    type MaxAllowedTakers = MaxAllowedTakers;
+    type MaxOrdersPerPair = MaxOrdersPerPair;
+    type MaxOrdersPerAccount = MaxOrdersPerAccount;
+    type Fee = Fee;
+    type FeeReceiver = FeeReceiver;
+    type Oracle = DummyOracle;
+    type OracleSource = OracleSource;
+    type MinOracleAge = MinOracleAge;
+    type MaxOracleStaleness = MaxOracleStaleness;
     type WeightInfo = ();
 }
 
@@ -136,6 +162,35 @@ impl orml_tokens::Config for Test {
     type CurrencyHooks = ();
 }
 
+pub struct DummyOracle;
+
+impl AggregatedPriceOracle<AssetId, u64, Price> for DummyOracle {
+    type Error = ();
+
+    fn get_price(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        _period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(Price, u64), Self::Error> {
+        ORACLE_PRICES.with(|v| v.borrow().get(&(asset_a, asset_b)).copied().ok_or(()))
+    }
+
+    fn get_price_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl OracleLastUpdate<AssetId, u64> for DummyOracle {
+    fn last_update(asset_a: AssetId, asset_b: AssetId, _source: Source) -> Option<u64> {
+        ORACLE_LAST_UPDATES.with(|v| v.borrow().get(&(asset_a, asset_b)).copied())
+    }
+
+    fn last_update_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 pub struct DummyRegistry<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> Registry<AssetId, Vec<u8>, Balance, DispatchError> for DummyRegistry<T> {
@@ -174,6 +229,15 @@ impl Default for ExtBuilder {
         EXISTENTIAL_DEPOSIT.with(|v| {
             v.borrow_mut().clear();
         });
+        FEE.with(|v| {
+            *v.borrow_mut() = Permill::zero();
+        });
+        ORACLE_PRICES.with(|v| {
+            v.borrow_mut().clear();
+        });
+        ORACLE_LAST_UPDATES.with(|v| {
+            v.borrow_mut().clear();
+        });
 
         Self {
             endowed_accounts: vec![
@@ -181,6 +245,7 @@ impl Default for ExtBuilder {
                 (BOB, HDX, 10_000),
                 (ALICE, DAI, 100),
                 (BOB, DAI, 100),
+                (CHARLIE, DAI, 100),
             ],
             registered_assets: vec![HDX, DAI],
         }
@@ -188,6 +253,25 @@ impl Default for ExtBuilder {
 }
 
 impl ExtBuilder {
+    pub fn with_oracle_price(self, asset_a: AssetId, asset_b: AssetId, price: Price, age: u64) -> Self {
+        ORACLE_PRICES.with(|v| {
+            v.borrow_mut().insert((asset_a, asset_b), (price, age));
+        });
+        ORACLE_LAST_UPDATES.with(|v| {
+            v.borrow_mut().insert((asset_a, asset_b), 1);
+        });
+
+        self
+    }
+
+    pub fn with_fee(self, fee: Permill) -> Self {
+        FEE.with(|v| {
+            *v.borrow_mut() = fee;
+        });
+
+        self
+    }
+
     pub fn with_existential_deposit(self, asset_id: AssetId, precision: u32) -> Self {
         EXISTENTIAL_DEPOSIT.with(|v| {
             v.borrow_mut().insert(asset_id, 10u128.pow(precision));
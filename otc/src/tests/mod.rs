@@ -13,10 +13,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod ema_oracle_mock;
 pub mod mock;
 
+pub mod allowed_takers;
 pub mod cancel_order;
+pub mod ema_oracle;
+pub mod expiry;
+pub mod fee;
+pub mod fill_best;
 pub mod fill_order;
 pub mod invariants;
+pub mod match_orders;
+pub mod migration;
+pub mod order_book;
+pub mod owner_index;
 pub mod partial_fill_order;
+pub mod pegged_order;
 pub mod place_order;
+pub mod queries;
+pub mod update_order;
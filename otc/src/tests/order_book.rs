@@ -0,0 +1,153 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{Error, OrderBook, Price};
+use frame_support::{assert_noop, assert_ok};
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedPointNumber;
+
+fn place_order(amount_in: Balance, amount_out: Balance) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        amount_in,
+        amount_out,
+        true,
+        None,
+        None
+    ));
+}
+
+fn price(n: u128) -> Price {
+    Price::saturating_from_integer(n)
+}
+
+#[test]
+fn place_order_should_insert_orders_into_order_book_sorted_by_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_order(20 * ONE, 100 * ONE);
+        place_order(10 * ONE, 100 * ONE);
+        place_order(20 * ONE, 40 * ONE);
+        place_order(10 * ONE, 50 * ONE);
+
+        // Assert
+        assert_eq!(
+            OrderBook::<Test>::get((DAI, HDX)).to_vec(),
+            vec![(price(10), 1), (price(5), 0), (price(5), 3), (price(2), 2)]
+        );
+        assert!(OrderBook::<Test>::get((HDX, DAI)).is_empty());
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_order_book_is_full() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        for _ in 0..MaxOrdersPerPair::get() {
+            place_order(20 * ONE, 100 * ONE);
+        }
+
+        // Act
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                10 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
+            Error::<Test>::OrderBookFull
+        );
+    });
+}
+
+#[test]
+fn place_order_should_work_when_order_book_has_space_again() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        for _ in 0..MaxOrdersPerPair::get() {
+            place_order(20 * ONE, 100 * ONE);
+        }
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Act
+        place_order(10 * ONE, 100 * ONE);
+
+        // Assert
+        let book = OrderBook::<Test>::get((DAI, HDX));
+        assert_eq!(book.len() as u32, MaxOrdersPerPair::get());
+        assert_eq!(book.first(), Some(&(price(10), 5)));
+    });
+}
+
+#[test]
+fn order_should_be_removed_from_order_book_when_filled_or_cancelled() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(20 * ONE, 100 * ONE);
+        place_order(10 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 1));
+
+        // Assert
+        assert!(!OrderBook::<Test>::contains_key((DAI, HDX)));
+    });
+}
+
+#[test]
+fn update_order_should_move_order_in_order_book() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(20 * ONE, 100 * ONE);
+        place_order(10 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::update_order(
+            RuntimeOrigin::signed(ALICE),
+            0,
+            10 * ONE,
+            200 * ONE,
+            true
+        ));
+
+        // Assert
+        assert_eq!(
+            OrderBook::<Test>::get((DAI, HDX)).to_vec(),
+            vec![(price(20), 0), (price(10), 1)]
+        );
+    });
+}
+
+#[test]
+fn partial_fill_order_should_keep_order_in_order_book() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+        // Assert
+        assert_eq!(OrderBook::<Test>::get((DAI, HDX)).to_vec(), vec![(price(5), 0)]);
+    });
+}
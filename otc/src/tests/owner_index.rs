@@ -0,0 +1,140 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::tests::mock::*;
+use crate::{Error, OrdersByOwner};
+use frame_support::{assert_noop, assert_ok};
+use pretty_assertions::assert_eq;
+
+fn place_order(owner: AccountId, asset_in: AssetId, asset_out: AssetId, amount_in: Balance, amount_out: Balance) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(owner),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        true,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn place_order_should_index_order_by_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(BOB, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(ALICE, HDX, DAI, 100 * ONE, 20 * ONE);
+
+        // Assert
+        assert!(OrdersByOwner::<Test>::contains_key(ALICE, 0));
+        assert!(OrdersByOwner::<Test>::contains_key(BOB, 1));
+        assert!(OrdersByOwner::<Test>::contains_key(ALICE, 2));
+        assert_eq!(OTC::order_count(ALICE), 2);
+        assert_eq!(OTC::order_count(BOB), 1);
+    });
+}
+
+#[test]
+fn partial_fill_order_should_keep_order_indexed_by_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+        // Assert
+        assert!(OrdersByOwner::<Test>::contains_key(ALICE, 0));
+        assert_eq!(OTC::order_count(ALICE), 1);
+    });
+}
+
+#[test]
+fn fill_order_should_remove_order_from_owner_index() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+        // Assert
+        assert!(!OrdersByOwner::<Test>::contains_key(ALICE, 0));
+        assert_eq!(OTC::order_count(ALICE), 0);
+    });
+}
+
+#[test]
+fn cancel_order_should_remove_order_from_owner_index() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(ALICE, DAI, HDX, 10 * ONE, 50 * ONE);
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!OrdersByOwner::<Test>::contains_key(ALICE, 0));
+        assert!(OrdersByOwner::<Test>::contains_key(ALICE, 1));
+        assert_eq!(OTC::order_count(ALICE), 1);
+    });
+}
+
+#[test]
+fn place_order_should_throw_error_when_max_orders_per_account_is_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        for _ in 0..MaxOrdersPerPair::get() {
+            place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+            place_order(ALICE, HDX, DAI, 100 * ONE, 10 * ONE);
+        }
+        assert_eq!(OTC::order_count(ALICE), MaxOrdersPerAccount::get());
+
+        // Act
+        assert_noop!(
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                REGISTERED_ASSET,
+                DAI,
+                50 * ONE,
+                10 * ONE,
+                true,
+                None,
+                None
+            ),
+            Error::<Test>::MaxOrdersPerAccountReached
+        );
+    });
+}
+
+#[test]
+fn place_order_should_work_when_order_of_account_at_limit_is_cancelled() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        for _ in 0..MaxOrdersPerPair::get() {
+            place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+            place_order(ALICE, HDX, DAI, 100 * ONE, 10 * ONE);
+        }
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Act
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Assert
+        assert_eq!(OTC::order_count(ALICE), MaxOrdersPerAccount::get());
+    });
+}
@@ -0,0 +1,306 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate as otc;
+use crate::tests::mock::*;
+use crate::{Error, Event, OraclePeg, Price, PriceAdjustment};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::oracle::OraclePeriod;
+use orml_traits::{MultiCurrency, NamedMultiReservableCurrency};
+use pretty_assertions::assert_eq;
+use sp_runtime::{FixedPointNumber, Permill};
+
+fn place_alice_pegged_order(adjustment: PriceAdjustment, partially_fillable: bool) {
+    assert_ok!(OTC::place_pegged_order(
+        RuntimeOrigin::signed(ALICE),
+        DAI,
+        HDX,
+        100 * ONE,
+        OraclePeriod::Short,
+        adjustment,
+        partially_fillable,
+        None,
+        None
+    ));
+}
+
+fn dai_per_hdx() -> Price {
+    Price::saturating_from_rational(1, 5)
+}
+
+#[test]
+fn place_pegged_order_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        place_alice_pegged_order(PriceAdjustment::Premium(Permill::from_percent(10)), true);
+
+        // Assert
+        let peg = OraclePeg {
+            period: OraclePeriod::Short,
+            adjustment: PriceAdjustment::Premium(Permill::from_percent(10)),
+        };
+        let order = OTC::orders(0).unwrap();
+        assert_eq!(order.owner, ALICE);
+        assert_eq!(order.amount_in, 0);
+        assert_eq!(order.amount_out, 100 * ONE);
+        assert_eq!(order.peg, Some(peg));
+
+        expect_events(vec![Event::Placed {
+            order_id: 0,
+            asset_in: DAI,
+            asset_out: HDX,
+            amount_in: 0,
+            amount_out: 100 * ONE,
+            partially_fillable: true,
+            expires_at: None,
+            allowed_takers: None,
+            peg: Some(peg),
+        }
+        .into()]);
+
+        assert_eq!(
+            Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+            100 * ONE
+        );
+        assert!(OTC::order_book((DAI, HDX)).is_empty());
+    });
+}
+
+#[test]
+fn place_pegged_order_should_throw_error_when_amount_out_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_noop!(
+            OTC::place_pegged_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                4 * ONE,
+                OraclePeriod::Short,
+                PriceAdjustment::Premium(Permill::zero()),
+                true,
+                None,
+                None
+            ),
+            Error::<Test>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_use_oracle_price_with_premium() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::from_percent(10)), false);
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+            let bob_dai_balance_before = Tokens::free_balance(DAI, &BOB);
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 22 * ONE);
+            assert_eq!(Tokens::free_balance(DAI, &BOB), bob_dai_balance_before - 22 * ONE);
+            assert_eq!(Tokens::free_balance(HDX, &BOB), bob_hdx_balance_before + 100 * ONE);
+            assert_eq!(Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE), 0);
+            assert!(OTC::orders(0).is_none());
+
+            expect_events(vec![Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 22 * ONE,
+                amount_out: 100 * ONE,
+                fee: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn fill_order_should_use_oracle_price_with_discount() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Discount(Permill::from_percent(10)), false);
+
+            let alice_dai_balance_before = Tokens::free_balance(DAI, &ALICE);
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+
+            // Assert
+            assert_eq!(Tokens::free_balance(DAI, &ALICE), alice_dai_balance_before + 18 * ONE);
+
+            expect_events(vec![Event::Filled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 18 * ONE,
+                amount_out: 100 * ONE,
+                fee: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn partial_fill_order_should_use_oracle_price() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+
+            // Act
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+
+            // Assert
+            let order = OTC::orders(0).unwrap();
+            assert_eq!(order.amount_in, 0);
+            assert_eq!(order.amount_out, 50 * ONE);
+            assert_eq!(
+                Tokens::reserved_balance_named(&otc::NAMED_RESERVE_ID, HDX, &ALICE),
+                50 * ONE
+            );
+
+            expect_events(vec![Event::PartiallyFilled {
+                order_id: 0,
+                who: BOB,
+                amount_in: 10 * ONE,
+                amount_out: 50 * ONE,
+                fee: 0,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_price_is_not_available() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), false);
+
+        // Act
+        assert_noop!(
+            OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::OraclePriceNotAvailable
+        );
+    });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_is_not_mature() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get() - 1)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+
+            // Act
+            assert_noop!(
+                OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+                Error::<Test>::OracleNotMature
+            );
+            assert_noop!(
+                OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE),
+                Error::<Test>::OracleNotMature
+            );
+        });
+}
+
+#[test]
+fn fill_order_should_throw_error_when_oracle_is_stale() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+            System::set_block_number(1 + MaxOracleStaleness::get() + 1);
+
+            // Act
+            assert_noop!(
+                OTC::fill_order(RuntimeOrigin::signed(BOB), 0),
+                Error::<Test>::OracleStale
+            );
+            assert_noop!(
+                OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE),
+                Error::<Test>::OracleStale
+            );
+        });
+}
+
+#[test]
+fn fill_order_should_work_when_oracle_was_updated_within_max_staleness() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+            System::set_block_number(1 + MaxOracleStaleness::get());
+
+            // Act
+            assert_ok!(OTC::fill_order(RuntimeOrigin::signed(BOB), 0));
+        });
+}
+
+#[test]
+fn update_order_should_throw_error_when_order_is_pegged() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+
+        // Act
+        assert_noop!(
+            OTC::update_order(RuntimeOrigin::signed(ALICE), 0, 20 * ONE, 100 * ONE, true),
+            Error::<Test>::OrderIsPegged
+        );
+    });
+}
+
+#[test]
+fn match_orders_should_throw_error_when_order_is_pegged() {
+    ExtBuilder::default()
+        .with_oracle_price(DAI, HDX, dai_per_hdx(), MinOracleAge::get())
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_alice_pegged_order(PriceAdjustment::Premium(Permill::zero()), true);
+            assert_ok!(OTC::place_order(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                100 * ONE,
+                20 * ONE,
+                true,
+                None,
+                None
+            ));
+
+            // Act
+            assert_noop!(
+                OTC::match_orders(RuntimeOrigin::signed(CHARLIE), 0, 1),
+                Error::<Test>::OrderIsPegged
+            );
+        });
+}
@@ -30,7 +30,9 @@ fn place_order_should_work() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Assert
@@ -41,6 +43,7 @@ fn place_order_should_work() {
         assert_eq!(order.amount_in, 20 * ONE);
         assert_eq!(order.amount_out, 100 * ONE);
         assert_eq!(order.partially_fillable, true);
+        assert_eq!(order.expires_at, None);
 
         expect_events(vec![Event::Placed {
             order_id: 0,
@@ -49,6 +52,9 @@ fn place_order_should_work() {
             amount_in: order.amount_in,
             amount_out: 100 * ONE,
             partially_fillable: true,
+            expires_at: None,
+            allowed_takers: None,
+            peg: None,
         }
         .into()]);
 
@@ -72,7 +78,9 @@ fn place_order_should_work_when_user_has_multiple_orders() {
             HDX,
             20 * ONE,
             100 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         assert_ok!(OTC::place_order(
@@ -81,7 +89,9 @@ fn place_order_should_work_when_user_has_multiple_orders() {
             HDX,
             10 * ONE,
             50 * ONE,
-            true
+            true,
+            None,
+            None
         ));
 
         // Assert
@@ -97,7 +107,16 @@ fn place_order_should_throw_error_when_amount_is_higher_than_balance() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 20 * ONE, 100_000 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                100_000 * ONE,
+                true,
+                None,
+                None
+            ),
             BalanceTooLow::<Test>
         );
     });
@@ -108,7 +127,16 @@ fn place_order_should_throw_error_when_asset_out_is_not_registered() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, DOGE, 20 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                DOGE,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             BalanceTooLow::<Test>
         );
     });
@@ -119,7 +147,16 @@ fn place_order_should_throw_error_when_asset_in_is_not_registered() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DOGE, HDX, 20 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DOGE,
+                HDX,
+                20 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::AssetNotRegistered
         );
     });
@@ -130,7 +167,16 @@ fn place_order_should_throw_error_when_amount_in_is_too_low() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 4 * ONE, 100 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                4 * ONE,
+                100 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::OrderAmountTooSmall
         );
     });
@@ -141,7 +187,16 @@ fn place_order_should_throw_error_when_amount_out_is_too_low() {
     ExtBuilder::default().build().execute_with(|| {
         // Act
         assert_noop!(
-            OTC::place_order(RuntimeOrigin::signed(ALICE), DAI, HDX, 20 * ONE, 4 * ONE, true),
+            OTC::place_order(
+                RuntimeOrigin::signed(ALICE),
+                DAI,
+                HDX,
+                20 * ONE,
+                4 * ONE,
+                true,
+                None,
+                None
+            ),
             Error::<Test>::OrderAmountTooSmall
         );
     });
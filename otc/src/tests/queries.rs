@@ -0,0 +1,192 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::tests::mock::*;
+use crate::{Error, FillQuote, OrderId, PeggedOrders, PriceAdjustment};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::oracle::OraclePeriod;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn place_order(owner: AccountId, asset_in: AssetId, asset_out: AssetId, amount_in: Balance, amount_out: Balance) {
+    assert_ok!(OTC::place_order(
+        RuntimeOrigin::signed(owner),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        true,
+        None,
+        None
+    ));
+}
+
+fn place_pegged_order(owner: AccountId, asset_in: AssetId, asset_out: AssetId, amount_out: Balance) {
+    assert_ok!(OTC::place_pegged_order(
+        RuntimeOrigin::signed(owner),
+        asset_in,
+        asset_out,
+        amount_out,
+        OraclePeriod::Short,
+        PriceAdjustment::Premium(Permill::zero()),
+        true,
+        None,
+        None
+    ));
+}
+
+fn order_ids(orders: Vec<(OrderId, crate::OrderOf<Test>)>) -> Vec<OrderId> {
+    orders.into_iter().map(|(order_id, _)| order_id).collect()
+}
+
+#[test]
+fn orders_by_owner_should_return_open_orders_of_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+        place_order(BOB, HDX, DAI, 100 * ONE, 10 * ONE);
+        place_pegged_order(ALICE, DAI, HDX, 100 * ONE);
+        place_order(ALICE, HDX, DAI, 100 * ONE, 10 * ONE);
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 3));
+
+        // Act
+        let mut alice_orders = order_ids(OTC::orders_by_owner(&ALICE));
+        alice_orders.sort();
+
+        // Assert
+        assert_eq!(alice_orders, vec![0, 2]);
+        assert_eq!(order_ids(OTC::orders_by_owner(&BOB)), vec![1]);
+        assert_eq!(order_ids(OTC::orders_by_owner(&CHARLIE)), vec![]);
+    });
+}
+
+#[test]
+fn orders_by_pair_should_return_orders_sorted_by_price_followed_by_pegged_orders() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(ALICE, DAI, HDX, 100 * ONE);
+        place_order(ALICE, DAI, HDX, 25 * ONE, 100 * ONE);
+        place_order(BOB, HDX, DAI, 100 * ONE, 10 * ONE);
+        place_order(BOB, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        let orders = OTC::orders_by_pair(DAI, HDX);
+
+        // Assert
+        assert_eq!(order_ids(orders), vec![3, 1, 0]);
+        assert_eq!(order_ids(OTC::orders_by_pair(HDX, DAI)), vec![2]);
+    });
+}
+
+#[test]
+fn pegged_orders_should_be_indexed_by_pair_until_removed() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_pegged_order(ALICE, DAI, HDX, 100 * ONE);
+        place_pegged_order(BOB, DAI, HDX, 100 * ONE);
+        assert!(PeggedOrders::<Test>::contains_key((DAI, HDX), 0));
+        assert!(PeggedOrders::<Test>::contains_key((DAI, HDX), 1));
+
+        // Act
+        assert_ok!(OTC::cancel_order(RuntimeOrigin::signed(ALICE), 0));
+
+        // Assert
+        assert!(!PeggedOrders::<Test>::contains_key((DAI, HDX), 0));
+        assert_eq!(order_ids(OTC::orders_by_pair(DAI, HDX)), vec![1]);
+    });
+}
+
+#[test]
+fn quote_fill_should_return_amounts_of_partial_fill() {
+    ExtBuilder::default()
+        .with_fee(Permill::from_percent(2))
+        .build()
+        .execute_with(|| {
+            // Arrange
+            place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+            let bob_hdx_balance_before = Tokens::free_balance(HDX, &BOB);
+
+            // Act
+            let quote = OTC::quote_fill(0, 10 * ONE).unwrap();
+
+            // Assert
+            assert_eq!(
+                quote,
+                FillQuote {
+                    amount_in: 10 * ONE,
+                    amount_out: 49 * ONE,
+                    fee: ONE,
+                }
+            );
+
+            assert_ok!(OTC::partial_fill_order(RuntimeOrigin::signed(BOB), 0, 10 * ONE));
+            assert_eq!(
+                Tokens::free_balance(HDX, &BOB),
+                bob_hdx_balance_before + quote.amount_out
+            );
+        });
+}
+
+#[test]
+fn quote_fill_should_return_amounts_of_whole_order_when_amount_in_is_higher_than_order_amount() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        let quote = OTC::quote_fill(0, 30 * ONE).unwrap();
+
+        // Assert
+        assert_eq!(
+            quote,
+            FillQuote {
+                amount_in: 20 * ONE,
+                amount_out: 100 * ONE,
+                fee: 0,
+            }
+        );
+    });
+}
+
+#[test]
+fn quote_fill_should_throw_error_when_order_is_not_partially_fillable() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        assert_ok!(OTC::place_order(
+            RuntimeOrigin::signed(ALICE),
+            DAI,
+            HDX,
+            20 * ONE,
+            100 * ONE,
+            false,
+            None,
+            None
+        ));
+
+        // Act
+        assert_noop!(OTC::quote_fill(0, 10 * ONE), Error::<Test>::OrderNotPartiallyFillable);
+    });
+}
+
+#[test]
+fn quote_fill_should_throw_error_when_remaining_amount_is_too_low() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        place_order(ALICE, DAI, HDX, 20 * ONE, 100 * ONE);
+
+        // Act
+        assert_noop!(OTC::quote_fill(0, 19 * ONE), Error::<Test>::OrderAmountTooSmall);
+    });
+}
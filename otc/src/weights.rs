@@ -15,26 +15,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_otc
+//! Weights for pallet_otc
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-03-17, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! PLACEHOLDER WEIGHTS - NOT GENERATED BY THE BENCHMARK CLI.
+//! The weights were derived by hand from the benchmark run of 2023-03-17 (STEPS: 5, REPEAT: 20, CHAIN: Some("dev"))
+//! and the storage accesses added since then. Regenerate them with the `pallet-otc` benchmarks before release:
+//!
+//! target/release/hydradx benchmark pallet --chain=dev --steps=5 --repeat=20 --execution=wasm
+//! --wasm-execution=compiled --heap-pages=4096 --template=.maintain/pallet-weight-template.hbs --pallet=pallet-otc
+//! --output=otc.rs --extrinsic=*
 
-// Executed Command:
-// target/release/hydradx
-// benchmark
-// pallet
-// --chain=dev
-// --steps=5
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --template=.maintain/pallet-weight-template.hbs
-// --pallet=pallet-otc
-// --output=otc.rs
-// --extrinsic=*
 #![allow(unused_parens)]
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
@@ -51,6 +41,11 @@ pub trait WeightInfo {
     fn partial_fill_order() -> Weight;
     fn fill_order() -> Weight;
     fn cancel_order() -> Weight;
+    fn cancel_expired() -> Weight;
+    fn update_order() -> Weight;
+    fn fill_best(n: u32) -> Weight;
+    fn match_orders() -> Weight;
+    fn place_pegged_order() -> Weight;
 }
 
 /// Weights for pallet_otc using the hydraDX node and recommended hardware.
@@ -58,47 +53,101 @@ pub struct HydraWeight<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
     fn place_order() -> Weight {
-        Weight::from_ref_time(69_013_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(5 as u64))
-            .saturating_add(T::DbWeight::get().writes(4 as u64))
+        Weight::from_ref_time(72_436_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(8 as u64))
+            .saturating_add(T::DbWeight::get().writes(10 as u64))
     }
     fn partial_fill_order() -> Weight {
         Weight::from_ref_time(125_008_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(8 as u64))
-            .saturating_add(T::DbWeight::get().writes(6 as u64))
+            .saturating_add(T::DbWeight::get().reads(11 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
     }
     fn fill_order() -> Weight {
-        Weight::from_ref_time(120_345_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(7 as u64))
-            .saturating_add(T::DbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(121_904_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(11 as u64))
+            .saturating_add(T::DbWeight::get().writes(12 as u64))
     }
     fn cancel_order() -> Weight {
-        Weight::from_ref_time(55_731_000 as u64)
-            .saturating_add(T::DbWeight::get().reads(3 as u64))
-            .saturating_add(T::DbWeight::get().writes(3 as u64))
+        Weight::from_ref_time(56_958_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
+    }
+    fn cancel_expired() -> Weight {
+        Weight::from_ref_time(57_120_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(8 as u64))
+    }
+    fn update_order() -> Weight {
+        Weight::from_ref_time(58_203_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(5 as u64))
+            .saturating_add(T::DbWeight::get().writes(5 as u64))
+    }
+    fn fill_best(n: u32) -> Weight {
+        Weight::from_ref_time(31_245_000 as u64)
+            .saturating_add(Weight::from_ref_time(126_380_000 as u64).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+    }
+    fn match_orders() -> Weight {
+        Weight::from_ref_time(167_532_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(12 as u64))
+            .saturating_add(T::DbWeight::get().writes(14 as u64))
+    }
+    fn place_pegged_order() -> Weight {
+        Weight::from_ref_time(70_915_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(7 as u64))
+            .saturating_add(T::DbWeight::get().writes(10 as u64))
     }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn place_order() -> Weight {
-        Weight::from_ref_time(69_013_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(5 as u64))
-            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+        Weight::from_ref_time(72_436_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(8 as u64))
+            .saturating_add(RocksDbWeight::get().writes(10 as u64))
     }
     fn partial_fill_order() -> Weight {
         Weight::from_ref_time(125_008_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(8 as u64))
-            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+            .saturating_add(RocksDbWeight::get().reads(11 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
     }
     fn fill_order() -> Weight {
-        Weight::from_ref_time(120_345_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(7 as u64))
-            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+        Weight::from_ref_time(121_904_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(11 as u64))
+            .saturating_add(RocksDbWeight::get().writes(12 as u64))
     }
     fn cancel_order() -> Weight {
-        Weight::from_ref_time(55_731_000 as u64)
-            .saturating_add(RocksDbWeight::get().reads(3 as u64))
-            .saturating_add(RocksDbWeight::get().writes(3 as u64))
+        Weight::from_ref_time(56_958_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
+    }
+    fn cancel_expired() -> Weight {
+        Weight::from_ref_time(57_120_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(8 as u64))
+    }
+    fn update_order() -> Weight {
+        Weight::from_ref_time(58_203_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(5 as u64))
+            .saturating_add(RocksDbWeight::get().writes(5 as u64))
+    }
+    fn fill_best(n: u32) -> Weight {
+        Weight::from_ref_time(31_245_000 as u64)
+            .saturating_add(Weight::from_ref_time(126_380_000 as u64).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().reads((11 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+    }
+    fn match_orders() -> Weight {
+        Weight::from_ref_time(167_532_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(12 as u64))
+            .saturating_add(RocksDbWeight::get().writes(14 as u64))
+    }
+    fn place_pegged_order() -> Weight {
+        Weight::from_ref_time(70_915_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(7 as u64))
+            .saturating_add(RocksDbWeight::get().writes(10 as u64))
     }
 }
@@ -28,12 +28,13 @@ pub use registry::*;
 pub mod oracle;
 pub use oracle::*;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::dispatch::{self, DispatchError};
 use frame_support::sp_runtime::traits::Zero;
 use frame_support::sp_runtime::RuntimeDebug;
 use frame_support::traits::LockIdentifier;
 use frame_support::weights::Weight;
+use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_std::vec::Vec;
@@ -52,6 +53,40 @@ pub struct AMMTransfer<AccountId, AssetId, AssetPair, Balance> {
     pub fee: (AssetId, Balance),
 }
 
+/// Asset pair of a trade.
+/// `asset_in`: asset sold to the pool
+/// `asset_out`: asset bought from the pool
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct AssetPair<AssetId> {
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+}
+
+impl<AssetId: Ord + Copy> AssetPair<AssetId> {
+    pub fn new(asset_in: AssetId, asset_out: AssetId) -> Self {
+        Self { asset_in, asset_out }
+    }
+
+    /// Return ordered asset tuple (A,B) where A < B.
+    /// Used as pool identifier in storage.
+    pub fn ordered_pair(&self) -> (AssetId, AssetId) {
+        if self.asset_in <= self.asset_out {
+            (self.asset_in, self.asset_out)
+        } else {
+            (self.asset_out, self.asset_in)
+        }
+    }
+
+    /// Return the same pair with `asset_in` and `asset_out` swapped.
+    pub fn reversed(&self) -> Self {
+        Self {
+            asset_in: self.asset_out,
+            asset_out: self.asset_in,
+        }
+    }
+}
+
 /// Traits for handling AMM Pool trades.
 pub trait AMM<AccountId, AssetId, AssetPair, Amount: Zero> {
     /// Check if both assets exist in a pool.
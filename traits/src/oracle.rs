@@ -56,6 +56,8 @@ pub enum OraclePeriod {
     Day,
     /// The oracle data was aggregated over the blocks of the last week.
     Week,
+    /// The oracle data was aggregated over the given number of blocks.
+    Custom(u32),
 }
 
 impl OraclePeriod {
@@ -321,3 +323,46 @@ where
         Weight::zero()
     }
 }
+
+/// An oracle returning the volatility of the price aggregated over `period` with the associated
+/// oracle age (to allow judging whether the oracle had a chance to settle yet).
+pub trait VolatilityOracle<AssetId, BlockNumber, Volatility> {
+    type Error;
+    fn get_volatility(
+        asset_a: AssetId,
+        asset_b: AssetId,
+        period: OraclePeriod,
+        source: Source,
+    ) -> Result<(Volatility, BlockNumber), Self::Error>;
+
+    fn get_volatility_weight() -> Weight;
+}
+
+/// Default implementation of the oracle trait that always returns `Err`.
+impl<AssetId, BlockNumber, Volatility> VolatilityOracle<AssetId, BlockNumber, Volatility> for () {
+    type Error = ();
+
+    fn get_volatility(
+        _asset_a: AssetId,
+        _asset_b: AssetId,
+        _period: OraclePeriod,
+        _source: Source,
+    ) -> Result<(Volatility, BlockNumber), Self::Error> {
+        Err(())
+    }
+
+    fn get_volatility_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Handler used by oracles to perform some tasks when the oracle of a pair is removed.
+pub trait OnOracleRemovedHandler<AssetId> {
+    /// Called after all entries of the oracle for `asset_a` and `asset_b` from `source` were removed.
+    /// A new oracle for the pair is initialised by the next trade or liquidity change.
+    fn on_oracle_removed(source: Source, asset_a: AssetId, asset_b: AssetId);
+}
+
+impl<AssetId> OnOracleRemovedHandler<AssetId> for () {
+    fn on_oracle_removed(_source: Source, _asset_a: AssetId, _asset_b: AssetId) {}
+}
@@ -0,0 +1,113 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::account;
+use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
+use orml_traits::MultiCurrencyExtended;
+
+use hydradx_traits::Registry;
+
+const SEED: u32 = 1;
+const ONE: Balance = 1_000_000_000_000;
+
+fn create_account_with_balances<T: Config>(name: &'static str, index: u32, assets: &[T::AssetId]) -> T::AccountId
+where
+    T::Currency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+{
+    let account_id: T::AccountId = account(name, index, SEED);
+
+    for asset in assets {
+        T::Currency::update_balance(*asset, &account_id, (1_000_000 * ONE) as i128).unwrap();
+    }
+
+    account_id
+}
+
+fn register_assets<T: Config>() -> Result<(T::AssetId, T::AssetId), DispatchError> {
+    let asset_a = T::AssetRegistry::create_asset(&b"TKNA".to_vec(), 1u128)?;
+    let asset_b = T::AssetRegistry::create_asset(&b"TKNB".to_vec(), 1u128)?;
+
+    Ok((asset_a, asset_b))
+}
+
+benchmarks! {
+    where_clause { where
+        T::Currency: MultiCurrencyExtended<T::AccountId, Amount = i128>,
+    }
+
+    create_pool {
+        let (asset_a, asset_b) = register_assets::<T>()?;
+        let caller = create_account_with_balances::<T>("caller", 0, &[asset_a, asset_b]);
+    }: _(RawOrigin::Signed(caller.clone()), asset_a, 10 * ONE, asset_b, 20 * ONE)
+    verify {
+        assert!(<Pallet<T> as AMM<_, _, _, _>>::exists(AssetPair::new(asset_a, asset_b)));
+    }
+
+    add_liquidity {
+        let (asset_a, asset_b) = register_assets::<T>()?;
+        let caller = create_account_with_balances::<T>("caller", 0, &[asset_a, asset_b]);
+        let lp = create_account_with_balances::<T>("lp", 1, &[asset_a, asset_b]);
+
+        crate::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, 100 * ONE, asset_b, 200 * ONE)?;
+    }: _(RawOrigin::Signed(lp.clone()), asset_a, asset_b, 10 * ONE, 21 * ONE)
+    verify {
+        let share_token = <Pallet<T> as AMM<_, _, _, _>>::get_share_token(AssetPair::new(asset_a, asset_b));
+        assert!(T::Currency::free_balance(share_token, &lp) > 0u128);
+    }
+
+    remove_liquidity {
+        let (asset_a, asset_b) = register_assets::<T>()?;
+        let caller = create_account_with_balances::<T>("caller", 0, &[asset_a, asset_b]);
+        let lp = create_account_with_balances::<T>("lp", 1, &[asset_a, asset_b]);
+
+        crate::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, 100 * ONE, asset_b, 200 * ONE)?;
+        crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp.clone()).into(), asset_a, asset_b, 10 * ONE, 21 * ONE)?;
+
+        let share_token = <Pallet<T> as AMM<_, _, _, _>>::get_share_token(AssetPair::new(asset_a, asset_b));
+        let shares = T::Currency::free_balance(share_token, &lp);
+    }: _(RawOrigin::Signed(lp.clone()), asset_a, asset_b, shares)
+    verify {
+        assert_eq!(T::Currency::free_balance(share_token, &lp), 0u128);
+    }
+
+    sell {
+        let (asset_a, asset_b) = register_assets::<T>()?;
+        let caller = create_account_with_balances::<T>("caller", 0, &[asset_a, asset_b]);
+        let trader = create_account_with_balances::<T>("trader", 1, &[asset_a]);
+
+        crate::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, 100 * ONE, asset_b, 200 * ONE)?;
+    }: _(RawOrigin::Signed(trader.clone()), asset_a, asset_b, 10 * ONE, 0u128, false)
+    verify {
+        assert!(T::Currency::free_balance(asset_b, &trader) > 0u128);
+    }
+
+    buy {
+        let (asset_a, asset_b) = register_assets::<T>()?;
+        let caller = create_account_with_balances::<T>("caller", 0, &[asset_a, asset_b]);
+        let trader = create_account_with_balances::<T>("trader", 1, &[asset_a]);
+
+        crate::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, 100 * ONE, asset_b, 200 * ONE)?;
+    }: _(RawOrigin::Signed(trader.clone()), asset_b, asset_a, 10 * ONE, 100 * ONE, false)
+    verify {
+        assert_eq!(T::Currency::free_balance(asset_b, &trader), 10 * ONE);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::mock::ExtBuilder::default().build(), crate::tests::mock::Test);
+}
@@ -0,0 +1,913 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # XYK pallet
+//!
+//! Constant product (`x * y = k`) AMM implementation.
+//!
+//! ### Terminology
+//!
+//! * **LP** - liquidity provider
+//! * **Share Token** - a token representing share asset of specific pool. Each pool has its own share token.
+//! * **Pool account** - account holding the reserves of a pool. Derived from the ordered asset pair.
+//!
+//! ## Assumptions
+//!
+//! A pool consists of exactly 2 assets. Anyone can create a pool if it is allowed by `CanCreatePool`.
+//!
+//! The pool creator provides initial liquidity of both assets, which determines the initial price.
+//!
+//! Share token is registered via `ShareTokenRegistry` when a pool is created. LP is given certain amount of
+//! shares by minting the pool's share token.
+//!
+//! Trade fee is determined by `get_fee` and is paid in the asset leaving the pool on sell and in the asset
+//! entering the pool on buy.
+//!
+//! When last LP withdraws all the liquidity, the pool is destroyed.
+//!
+//! Pool creation, trades and liquidity changes are reported to `AMMHandler` (e.g. an oracle).
+//!
+//! ## Dispatchable functions
+//! * `create_pool` - create a new pool and provide initial liquidity.
+//! * `add_liquidity` - add liquidity to a pool.
+//! * `remove_liquidity` - remove liquidity from a pool.
+//! * `sell` - sell an asset to a pool.
+//! * `buy` - buy an asset from a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet_prelude::{DispatchResult, Get};
+use frame_support::{ensure, transactional};
+use hydradx_traits::{
+    AMMTransfer, AccountIdFor, CanCreatePool, OnCreatePoolHandler, OnLiquidityChangedHandler, OnTradeHandler,
+    ShareTokenRegistry, Source, AMM,
+};
+use orml_traits::MultiCurrency;
+use sp_core::U256;
+use sp_runtime::traits::Zero;
+use sp_runtime::{ArithmeticError, DispatchError};
+use sp_std::prelude::*;
+use sp_std::vec;
+
+pub use pallet::*;
+
+mod trade_execution;
+pub mod types;
+pub mod weights;
+
+pub use trade_execution::*;
+
+use crate::types::{AssetPair, Balance};
+use weights::WeightInfo;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarks;
+
+/// XYK share token and account id identifier.
+/// Used as identifier to create share token unique names and account ids.
+pub const POOL_IDENTIFIER: &[u8] = b"xyk";
+
+/// Oracle source identifier of XYK pools.
+pub const SOURCE: Source = *b"snek/xyk";
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use codec::HasCompact;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Identifier for the class of asset.
+        type AssetId: Member
+            + Parameter
+            + Ord
+            + Default
+            + Copy
+            + HasCompact
+            + MaybeSerializeDeserialize
+            + MaxEncodedLen
+            + TypeInfo;
+
+        /// Multi currency mechanism
+        type Currency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+
+        /// Account ID constructor - pool account and share token name are derived from ordered asset pair
+        type ShareAccountId: AccountIdFor<(Self::AssetId, Self::AssetId), AccountId = Self::AccountId>;
+
+        /// Share token registry mechanism
+        type AssetRegistry: ShareTokenRegistry<Self::AssetId, Vec<u8>, Balance, DispatchError>;
+
+        /// Determines whether a pool of given assets can be created
+        type CanCreatePool: CanCreatePool<Self::AssetId>;
+
+        /// Handler notified about pool creation, trades and liquidity changes
+        type AMMHandler: OnCreatePoolHandler<Self::AssetId>
+            + OnTradeHandler<Self::AssetId, Balance>
+            + OnLiquidityChangedHandler<Self::AssetId, Balance>;
+
+        /// Trading fee rate as (numerator, denominator)
+        #[pallet::constant]
+        type GetExchangeFee: Get<(u32, u32)>;
+
+        /// Minimum trading amount
+        #[pallet::constant]
+        type MinTradingLimit: Get<Balance>;
+
+        /// Minimum pool liquidity
+        #[pallet::constant]
+        type MinPoolLiquidity: Get<Balance>;
+
+        /// Max fraction of pool reserve to sell in single transaction
+        #[pallet::constant]
+        type MaxInRatio: Get<u128>;
+
+        /// Max fraction of pool reserve to buy in single transaction
+        #[pallet::constant]
+        type MaxOutRatio: Get<u128>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Share token of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn share_token)]
+    pub type ShareToken<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AssetId, ValueQuery>;
+
+    /// Total issuance of share token of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn total_liquidity)]
+    pub type TotalLiquidity<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+    /// Ordered asset pair of a pool.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_assets)]
+    pub type PoolAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (T::AssetId, T::AssetId), OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A pool was created.
+        PoolCreated {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            initial_shares_amount: Balance,
+            share_token: T::AssetId,
+            pool: T::AccountId,
+        },
+        /// A pool was destroyed as all the liquidity was removed.
+        PoolDestroyed {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            share_token: T::AssetId,
+            pool: T::AccountId,
+        },
+        /// Liquidity was added to a pool.
+        LiquidityAdded {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+            shares: Balance,
+        },
+        /// Liquidity was removed from a pool.
+        LiquidityRemoved {
+            who: T::AccountId,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b: Balance,
+            shares: Balance,
+        },
+        /// Sell trade executed. Trade fee paid in asset leaving the pool (already subtracted from amount_out).
+        SellExecuted {
+            who: T::AccountId,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount_in: Balance,
+            amount_out: Balance,
+            fee_asset: T::AssetId,
+            fee_amount: Balance,
+            pool: T::AccountId,
+        },
+        /// Buy trade executed. Trade fee paid in asset entering the pool (not included in amount_in).
+        BuyExecuted {
+            who: T::AccountId,
+            asset_out: T::AssetId,
+            asset_in: T::AssetId,
+            amount_out: Balance,
+            amount_in: Balance,
+            fee_asset: T::AssetId,
+            fee_amount: Balance,
+            pool: T::AccountId,
+        },
+    }
+
+    #[pallet::error]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
+    pub enum Error<T> {
+        /// Creating a pool with same assets is not allowed.
+        CannotCreatePoolWithSameAssets,
+
+        /// Creating a pool of given assets is not allowed.
+        CannotCreatePool,
+
+        /// A pool with given assets does not exist.
+        TokenPoolNotFound,
+
+        /// A pool with given assets already exists.
+        TokenPoolAlreadyExists,
+
+        /// Liquidity has not reached the required minimum.
+        InsufficientLiquidity,
+
+        /// Amount is less than the minimum trading amount configured.
+        InsufficientTradingAmount,
+
+        /// Liquidity is zero.
+        ZeroLiquidity,
+
+        /// Account balance is not sufficient.
+        InsufficientAssetBalance,
+
+        /// Pool balance of an asset is not sufficient to perform a trade.
+        InsufficientPoolAssetBalance,
+
+        /// Invalid amount of asset to add as liquidity.
+        AddAssetAmountInvalid,
+
+        /// Invalid amount of asset to remove as liquidity.
+        RemoveAssetAmountInvalid,
+
+        /// Invalid amount of asset to sell.
+        SellAssetAmountInvalid,
+
+        /// Invalid amount of asset to buy.
+        BuyAssetAmountInvalid,
+
+        /// Fee calculation failed.
+        FeeAmountInvalid,
+
+        /// Discounted trades are not supported.
+        CannotApplyDiscount,
+
+        /// Amount to sell exceeds the allowed fraction of pool reserve.
+        MaxInRatioExceeded,
+
+        /// Amount to buy exceeds the allowed fraction of pool reserve.
+        MaxOutRatioExceeded,
+
+        /// Maximum limit has been exceeded.
+        AssetAmountExceededLimit,
+
+        /// Minimum limit has not been reached.
+        AssetAmountNotReachedLimit,
+
+        /// Amount of shares to mint is zero.
+        InvalidMintedLiquidity,
+
+        /// Total liquidity overflow or underflow.
+        InvalidLiquidityAmount,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(!T::MaxInRatio::get().is_zero(), "MaxInRatio is 0.");
+            assert!(!T::MaxOutRatio::get().is_zero(), "MaxOutRatio is 0.");
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new pool for given asset pair and provide initial liquidity.
+        ///
+        /// Initial price of the pool is determined by the ratio of provided amounts.
+        ///
+        /// Share token is registered in `T::AssetRegistry` (or retrieved if already registered).
+        ///
+        /// Parameters:
+        /// - `origin`: pool creator and first LP
+        /// - `asset_a`: first asset id
+        /// - `amount_a`: initial liquidity of `asset_a`
+        /// - `asset_b`: second asset id
+        /// - `amount_b`: initial liquidity of `asset_b`
+        ///
+        /// Emits `PoolCreated` event if successful.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_pool()
+            .saturating_add(<T as Config>::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            amount_a: Balance,
+            asset_b: T::AssetId,
+            amount_b: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+            ensure!(
+                T::CanCreatePool::can_create(asset_a, asset_b),
+                Error::<T>::CannotCreatePool
+            );
+            ensure!(
+                amount_a >= T::MinPoolLiquidity::get() && amount_b >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(!Self::exists(asset_pair), Error::<T>::TokenPoolAlreadyExists);
+
+            ensure!(
+                T::Currency::free_balance(asset_a, &who) >= amount_a,
+                Error::<T>::InsufficientAssetBalance
+            );
+            ensure!(
+                T::Currency::free_balance(asset_b, &who) >= amount_b,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            // Initial shares are equal to the initial liquidity of the first asset of the ordered pair.
+            let shares_added = if asset_a < asset_b { amount_a } else { amount_b };
+
+            let pool = Self::get_pair_id(asset_pair);
+            let ordered_pair = asset_pair.ordered_pair();
+            let share_token = T::AssetRegistry::get_or_create_shared_asset(
+                T::ShareAccountId::name(&ordered_pair, Some(POOL_IDENTIFIER)),
+                vec![ordered_pair.0, ordered_pair.1],
+                T::MinPoolLiquidity::get(),
+            )?;
+
+            T::AMMHandler::on_create_pool(ordered_pair.0, ordered_pair.1)?;
+
+            T::Currency::transfer(asset_a, &who, &pool, amount_a)?;
+            T::Currency::transfer(asset_b, &who, &pool, amount_b)?;
+            T::Currency::deposit(share_token, &who, shares_added)?;
+
+            <ShareToken<T>>::insert(&pool, share_token);
+            <TotalLiquidity<T>>::insert(&pool, shares_added);
+            <PoolAssets<T>>::insert(&pool, ordered_pair);
+
+            T::AMMHandler::on_liquidity_changed(SOURCE, asset_a, asset_b, amount_a, amount_b, amount_a, amount_b)
+                .map_err(|(_w, e)| e)?;
+
+            Self::deposit_event(Event::PoolCreated {
+                who,
+                asset_a,
+                asset_b,
+                initial_shares_amount: shares_added,
+                share_token,
+                pool,
+            });
+
+            Ok(())
+        }
+
+        /// Add liquidity to a pool.
+        ///
+        /// Amount of `asset_b` is calculated so the price of the pool stays the same.
+        ///
+        /// Origin is given corresponding amount of shares.
+        ///
+        /// Parameters:
+        /// - `origin`: liquidity provider
+        /// - `asset_a`: asset id of the exact amount provided
+        /// - `asset_b`: asset id of the calculated amount provided
+        /// - `amount_a`: amount of `asset_a` to provide
+        /// - `amount_b_max_limit`: maximum amount of `asset_b` to provide
+        ///
+        /// Emits `LiquidityAdded` event when successful.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_liquidity()
+            .saturating_add(<T as Config>::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            amount_a: Balance,
+            amount_b_max_limit: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+            ensure!(
+                amount_a >= T::MinTradingLimit::get(),
+                Error::<T>::InsufficientTradingAmount
+            );
+            ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
+            ensure!(
+                T::Currency::free_balance(asset_a, &who) >= amount_a,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            let pool = Self::get_pair_id(asset_pair);
+            let share_token = Self::share_token(&pool);
+            let asset_a_reserve = T::Currency::free_balance(asset_a, &pool);
+            let asset_b_reserve = T::Currency::free_balance(asset_b, &pool);
+            let total_liquidity = Self::total_liquidity(&pool);
+
+            let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+                .map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
+
+            ensure!(amount_b <= amount_b_max_limit, Error::<T>::AssetAmountExceededLimit);
+            ensure!(
+                T::Currency::free_balance(asset_b, &who) >= amount_b,
+                Error::<T>::InsufficientAssetBalance
+            );
+
+            let shares_calculation = U256::from(amount_a)
+                .checked_mul(U256::from(total_liquidity))
+                .and_then(|v| v.checked_div(U256::from(asset_a_reserve)))
+                .ok_or(ArithmeticError::Overflow)?;
+            let shares = Balance::try_from(shares_calculation).map_err(|_| ArithmeticError::Overflow)?;
+
+            ensure!(!shares.is_zero(), Error::<T>::InvalidMintedLiquidity);
+            ensure!(
+                T::Currency::free_balance(share_token, &who).saturating_add(shares) >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let liquidity_amount = total_liquidity
+                .checked_add(shares)
+                .ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+            T::Currency::transfer(asset_a, &who, &pool, amount_a)?;
+            T::Currency::transfer(asset_b, &who, &pool, amount_b)?;
+            T::Currency::deposit(share_token, &who, shares)?;
+
+            <TotalLiquidity<T>>::insert(&pool, liquidity_amount);
+
+            T::AMMHandler::on_liquidity_changed(
+                SOURCE,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                asset_a_reserve.saturating_add(amount_a),
+                asset_b_reserve.saturating_add(amount_b),
+            )
+            .map_err(|(_w, e)| e)?;
+
+            Self::deposit_event(Event::LiquidityAdded {
+                who,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                shares,
+            });
+
+            Ok(())
+        }
+
+        /// Remove liquidity from a pool.
+        ///
+        /// Shares are burned and LP receives corresponding amount of both pool assets.
+        ///
+        /// If all liquidity is removed, the pool is destroyed.
+        ///
+        /// Parameters:
+        /// - `origin`: liquidity provider
+        /// - `asset_a`: first asset id of the pool
+        /// - `asset_b`: second asset id of the pool
+        /// - `liquidity_amount`: amount of shares to withdraw
+        ///
+        /// Emits `LiquidityRemoved` event when successful and `PoolDestroyed` event when the pool is destroyed.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_liquidity()
+            .saturating_add(<T as Config>::AMMHandler::on_liquidity_changed_weight()))]
+        #[transactional]
+        pub fn remove_liquidity(
+            origin: OriginFor<T>,
+            asset_a: T::AssetId,
+            asset_b: T::AssetId,
+            liquidity_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
+
+            let asset_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+            let pool = Self::get_pair_id(asset_pair);
+            let share_token = Self::share_token(&pool);
+            let total_shares = Self::total_liquidity(&pool);
+            let account_shares = T::Currency::free_balance(share_token, &who);
+
+            ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
+
+            // Remaining share balance must be either zero or at least the minimum pool liquidity.
+            ensure!(
+                account_shares == liquidity_amount
+                    || account_shares.saturating_sub(liquidity_amount) >= T::MinPoolLiquidity::get(),
+                Error::<T>::InsufficientLiquidity
+            );
+
+            let asset_a_reserve = T::Currency::free_balance(asset_a, &pool);
+            let asset_b_reserve = T::Currency::free_balance(asset_b, &pool);
+
+            let (amount_a, amount_b) = hydra_dx_math::xyk::calculate_liquidity_out(
+                asset_a_reserve,
+                asset_b_reserve,
+                liquidity_amount,
+                total_shares,
+            )
+            .map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
+
+            let liquidity_left = total_shares
+                .checked_sub(liquidity_amount)
+                .ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+            T::Currency::transfer(asset_a, &pool, &who, amount_a)?;
+            T::Currency::transfer(asset_b, &pool, &who, amount_b)?;
+            T::Currency::withdraw(share_token, &who, liquidity_amount)?;
+
+            <TotalLiquidity<T>>::insert(&pool, liquidity_left);
+
+            T::AMMHandler::on_liquidity_changed(
+                SOURCE,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                asset_a_reserve.saturating_sub(amount_a),
+                asset_b_reserve.saturating_sub(amount_b),
+            )
+            .map_err(|(_w, e)| e)?;
+
+            Self::deposit_event(Event::LiquidityRemoved {
+                who: who.clone(),
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                shares: liquidity_amount,
+            });
+
+            if liquidity_left.is_zero() {
+                <ShareToken<T>>::remove(&pool);
+                <TotalLiquidity<T>>::remove(&pool);
+                <PoolAssets<T>>::remove(&pool);
+
+                Self::deposit_event(Event::PoolDestroyed {
+                    who,
+                    asset_a,
+                    asset_b,
+                    share_token,
+                    pool,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Execute a swap of `asset_in` for `asset_out` by specifying how much to put in.
+        ///
+        /// Trade fee is subtracted from the amount received.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `amount`: Amount of asset to be sold to the pool
+        /// - `min_bought`: Minimum amount required to receive
+        /// - `discount`: Discounted trade (not supported)
+        ///
+        /// Emits `SellExecuted` event when successful.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::sell()
+            .saturating_add(<T as Config>::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn sell(
+            origin: OriginFor<T>,
+            asset_in: T::AssetId,
+            asset_out: T::AssetId,
+            amount: Balance,
+            min_bought: Balance,
+            discount: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <Self as AMM<_, _, _, _>>::sell(&who, AssetPair::new(asset_in, asset_out), amount, min_bought, discount)
+        }
+
+        /// Execute a swap of `asset_in` for `asset_out` by specifying how much to get out.
+        ///
+        /// Trade fee is added to the amount paid.
+        ///
+        /// Parameters:
+        /// - `origin`: origin of the caller
+        /// - `asset_out`: ID of asset bought from the pool
+        /// - `asset_in`: ID of asset sold to the pool
+        /// - `amount`: Amount of asset to receive from the pool
+        /// - `max_limit`: Maximum amount allowed to be paid, including the fee
+        /// - `discount`: Discounted trade (not supported)
+        ///
+        /// Emits `BuyExecuted` event when successful.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::buy()
+            .saturating_add(<T as Config>::AMMHandler::on_trade_weight()))]
+        #[transactional]
+        pub fn buy(
+            origin: OriginFor<T>,
+            asset_out: T::AssetId,
+            asset_in: T::AssetId,
+            amount: Balance,
+            max_limit: Balance,
+            discount: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <Self as AMM<_, _, _, _>>::buy(&who, AssetPair::new(asset_in, asset_out), amount, max_limit, discount)
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Calculate amount of `asset_out` received for `amount_in` of `asset_in`.
+    ///
+    /// Returns `(amount_out, fee)` where `fee` is already subtracted from `amount_out`.
+    fn calculate_out_amount(
+        assets: AssetPair<T::AssetId>,
+        amount_in: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
+
+        let pool = Self::get_pair_id(assets);
+        let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pool);
+        let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pool);
+
+        ensure!(
+            amount_in <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+
+        let amount_out = hydra_dx_math::xyk::calculate_out_given_in(asset_in_reserve, asset_out_reserve, amount_in)
+            .map_err(|_| Error::<T>::SellAssetAmountInvalid)?;
+
+        ensure!(asset_out_reserve > amount_out, Error::<T>::InsufficientPoolAssetBalance);
+
+        let fee = Self::calculate_fee(&pool, amount_out)?;
+        let amount_out_without_fee = amount_out.checked_sub(fee).ok_or(Error::<T>::SellAssetAmountInvalid)?;
+
+        ensure!(
+            amount_out_without_fee <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        Ok((amount_out_without_fee, fee))
+    }
+
+    /// Calculate amount of `asset_in` required to receive `amount_out` of `asset_out`.
+    ///
+    /// Returns `(amount_in, fee)` where `fee` is NOT included in `amount_in`.
+    fn calculate_in_amount(
+        assets: AssetPair<T::AssetId>,
+        amount_out: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
+
+        let pool = Self::get_pair_id(assets);
+        let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pool);
+        let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pool);
+
+        ensure!(asset_out_reserve > amount_out, Error::<T>::InsufficientPoolAssetBalance);
+        ensure!(
+            amount_out <= asset_out_reserve / T::MaxOutRatio::get(),
+            Error::<T>::MaxOutRatioExceeded
+        );
+
+        let amount_in = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount_out)
+            .map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
+
+        ensure!(
+            amount_in <= asset_in_reserve / T::MaxInRatio::get(),
+            Error::<T>::MaxInRatioExceeded
+        );
+
+        let fee = Self::calculate_fee(&pool, amount_in)?;
+
+        Ok((amount_in, fee))
+    }
+
+    fn calculate_fee(pool: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+        hydra_dx_math::fee::calculate_pool_trade_fee(amount, Self::get_fee(pool))
+            .ok_or_else(|| Error::<T>::FeeAmountInvalid.into())
+    }
+}
+
+impl<T: Config> AMM<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance> for Pallet<T> {
+    fn exists(assets: AssetPair<T::AssetId>) -> bool {
+        <PoolAssets<T>>::contains_key(Self::get_pair_id(assets))
+    }
+
+    fn get_pair_id(assets: AssetPair<T::AssetId>) -> T::AccountId {
+        T::ShareAccountId::from_assets(&assets.ordered_pair(), Some(POOL_IDENTIFIER))
+    }
+
+    fn get_share_token(assets: AssetPair<T::AssetId>) -> T::AssetId {
+        Self::share_token(Self::get_pair_id(assets))
+    }
+
+    fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<T::AssetId>> {
+        Self::pool_assets(pool_account_id).map(|(asset_a, asset_b)| vec![asset_a, asset_b])
+    }
+
+    fn get_spot_price_unchecked(asset_a: T::AssetId, asset_b: T::AssetId, amount: Balance) -> Balance {
+        let pool = Self::get_pair_id(AssetPair::new(asset_a, asset_b));
+
+        let asset_a_reserve = T::Currency::free_balance(asset_a, &pool);
+        let asset_b_reserve = T::Currency::free_balance(asset_b, &pool);
+
+        hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
+            .unwrap_or_else(|_| Balance::zero())
+    }
+
+    fn validate_sell(
+        origin: &T::AccountId,
+        assets: AssetPair<T::AssetId>,
+        amount: Balance,
+        min_bought: Balance,
+        discount: bool,
+    ) -> Result<AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>, DispatchError> {
+        ensure!(!discount, Error::<T>::CannotApplyDiscount);
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+        ensure!(
+            T::Currency::free_balance(assets.asset_in, origin) >= amount,
+            Error::<T>::InsufficientAssetBalance
+        );
+
+        let (amount_out, fee) = Self::calculate_out_amount(assets, amount)?;
+
+        ensure!(amount_out >= min_bought, Error::<T>::AssetAmountNotReachedLimit);
+
+        Ok(AMMTransfer {
+            origin: origin.clone(),
+            assets,
+            amount,
+            amount_b: amount_out,
+            discount: false,
+            discount_amount: Balance::zero(),
+            fee: (assets.asset_out, fee),
+        })
+    }
+
+    fn execute_sell(
+        transfer: &AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>,
+    ) -> DispatchResult {
+        let pool = Self::get_pair_id(transfer.assets);
+        let (asset_in, asset_out) = (transfer.assets.asset_in, transfer.assets.asset_out);
+
+        T::Currency::transfer(asset_in, &transfer.origin, &pool, transfer.amount)?;
+        T::Currency::transfer(asset_out, &pool, &transfer.origin, transfer.amount_b)?;
+
+        T::AMMHandler::on_trade(
+            SOURCE,
+            asset_in,
+            asset_out,
+            transfer.amount,
+            transfer.amount_b,
+            T::Currency::free_balance(asset_in, &pool),
+            T::Currency::free_balance(asset_out, &pool),
+        )
+        .map_err(|(_w, e)| e)?;
+
+        Self::deposit_event(Event::SellExecuted {
+            who: transfer.origin.clone(),
+            asset_in,
+            asset_out,
+            amount_in: transfer.amount,
+            amount_out: transfer.amount_b,
+            fee_asset: transfer.fee.0,
+            fee_amount: transfer.fee.1,
+            pool,
+        });
+
+        Ok(())
+    }
+
+    fn validate_buy(
+        origin: &T::AccountId,
+        assets: AssetPair<T::AssetId>,
+        amount: Balance,
+        max_limit: Balance,
+        discount: bool,
+    ) -> Result<AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>, DispatchError> {
+        ensure!(!discount, Error::<T>::CannotApplyDiscount);
+        ensure!(
+            amount >= T::MinTradingLimit::get(),
+            Error::<T>::InsufficientTradingAmount
+        );
+
+        let (amount_in, fee) = Self::calculate_in_amount(assets, amount)?;
+        let amount_in_with_fee = amount_in.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+
+        ensure!(amount_in_with_fee <= max_limit, Error::<T>::AssetAmountExceededLimit);
+        ensure!(
+            T::Currency::free_balance(assets.asset_in, origin) >= amount_in_with_fee,
+            Error::<T>::InsufficientAssetBalance
+        );
+
+        Ok(AMMTransfer {
+            origin: origin.clone(),
+            assets,
+            amount,
+            amount_b: amount_in,
+            discount: false,
+            discount_amount: Balance::zero(),
+            fee: (assets.asset_in, fee),
+        })
+    }
+
+    fn execute_buy(transfer: &AMMTransfer<T::AccountId, T::AssetId, AssetPair<T::AssetId>, Balance>) -> DispatchResult {
+        let pool = Self::get_pair_id(transfer.assets);
+        let (asset_in, asset_out) = (transfer.assets.asset_in, transfer.assets.asset_out);
+        let amount_in = transfer
+            .amount_b
+            .checked_add(transfer.fee.1)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        T::Currency::transfer(asset_out, &pool, &transfer.origin, transfer.amount)?;
+        T::Currency::transfer(asset_in, &transfer.origin, &pool, amount_in)?;
+
+        T::AMMHandler::on_trade(
+            SOURCE,
+            asset_in,
+            asset_out,
+            amount_in,
+            transfer.amount,
+            T::Currency::free_balance(asset_in, &pool),
+            T::Currency::free_balance(asset_out, &pool),
+        )
+        .map_err(|(_w, e)| e)?;
+
+        Self::deposit_event(Event::BuyExecuted {
+            who: transfer.origin.clone(),
+            asset_out,
+            asset_in,
+            amount_out: transfer.amount,
+            amount_in: transfer.amount_b,
+            fee_asset: transfer.fee.0,
+            fee_amount: transfer.fee.1,
+            pool,
+        });
+
+        Ok(())
+    }
+
+    fn get_min_trading_limit() -> Balance {
+        T::MinTradingLimit::get()
+    }
+
+    fn get_min_pool_liquidity() -> Balance {
+        T::MinPoolLiquidity::get()
+    }
+
+    fn get_max_in_ratio() -> u128 {
+        T::MaxInRatio::get()
+    }
+
+    fn get_max_out_ratio() -> u128 {
+        T::MaxOutRatio::get()
+    }
+
+    fn get_fee(_pool_account_id: &T::AccountId) -> (u32, u32) {
+        T::GetExchangeFee::get()
+    }
+}
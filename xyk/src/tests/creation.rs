@@ -0,0 +1,173 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AMM;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use test_utils::assert_balance;
+
+#[test]
+fn create_pool_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Arrange
+        let pool = pool_account(HDX, DOT);
+
+        // Act
+        assert_ok!(XYK::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            HDX,
+            100 * ONE,
+            DOT,
+            200 * ONE
+        ));
+
+        // Assert
+        let share_token = SHARE_TOKEN_START;
+        assert_eq!(XYK::share_token(pool), share_token);
+        assert_eq!(XYK::total_liquidity(pool), 100 * ONE);
+        assert_eq!(XYK::pool_assets(pool), Some((HDX, DOT)));
+        assert!(XYK::exists(AssetPair::new(DOT, HDX)));
+
+        assert_balance!(pool, HDX, 100 * ONE);
+        assert_balance!(pool, DOT, 200 * ONE);
+        assert_balance!(ALICE, HDX, 900 * ONE);
+        assert_balance!(ALICE, DOT, 800 * ONE);
+        assert_balance!(ALICE, share_token, 100 * ONE);
+
+        assert_eq!(
+            amm_activity(),
+            vec![
+                AmmActivity::PoolCreated(HDX, DOT),
+                AmmActivity::LiquidityChanged(crate::SOURCE, HDX, DOT, 100 * ONE, 200 * ONE, 100 * ONE, 200 * ONE),
+            ]
+        );
+
+        test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::PoolCreated {
+            who: ALICE,
+            asset_a: HDX,
+            asset_b: DOT,
+            initial_shares_amount: 100 * ONE,
+            share_token,
+            pool,
+        }
+        .into()]);
+    });
+}
+
+#[test]
+fn create_pool_should_mint_shares_of_lower_asset_amount_when_assets_are_reversed() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Act
+        assert_ok!(XYK::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            200 * ONE,
+            HDX,
+            100 * ONE
+        ));
+
+        // Assert
+        let pool = pool_account(HDX, DOT);
+        assert_eq!(XYK::total_liquidity(pool), 100 * ONE);
+        assert_eq!(XYK::pool_assets(pool), Some((HDX, DOT)));
+        assert_balance!(ALICE, SHARE_TOKEN_START, 100 * ONE);
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_assets_are_same() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 100 * ONE, HDX, 100 * ONE),
+            Error::<Test>::CannotCreatePoolWithSameAssets
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_creation_is_not_allowed() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 100 * ONE, FORBIDDEN, 100 * ONE),
+            Error::<Test>::CannotCreatePool
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_already_exists() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::create_pool(RuntimeOrigin::signed(BOB), DOT, 100 * ONE, HDX, 100 * ONE),
+                Error::<Test>::TokenPoolAlreadyExists
+            );
+        });
+}
+
+#[test]
+fn create_pool_should_fail_when_initial_liquidity_is_below_minimum() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 999, DOT, 100 * ONE),
+            Error::<Test>::InsufficientLiquidity
+        );
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 100 * ONE, DOT, 999),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::create_pool(RuntimeOrigin::signed(ALICE), HDX, 1_001 * ONE, DOT, 100 * ONE),
+            Error::<Test>::InsufficientAssetBalance
+        );
+    });
+}
+
+#[test]
+fn create_pool_should_reuse_share_token_when_pool_is_recreated() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            assert_ok!(XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DOT, 100 * ONE));
+            assert!(!XYK::exists(AssetPair::new(HDX, DOT)));
+
+            // Act
+            assert_ok!(XYK::create_pool(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                10 * ONE,
+                DOT,
+                10 * ONE
+            ));
+
+            // Assert
+            assert_eq!(XYK::share_token(pool_account(HDX, DOT)), SHARE_TOKEN_START);
+            assert_balance!(BOB, SHARE_TOKEN_START, 10 * ONE);
+        });
+}
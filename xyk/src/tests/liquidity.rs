@@ -0,0 +1,253 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AMM;
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use test_utils::{assert_balance, assert_balance_approx};
+
+#[test]
+fn add_liquidity_should_work() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DOT);
+
+            // Act
+            assert_ok!(XYK::add_liquidity(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DOT,
+                10 * ONE,
+                21 * ONE
+            ));
+
+            // Assert
+            assert_balance!(BOB, SHARE_TOKEN_START, 10 * ONE);
+            assert_eq!(XYK::total_liquidity(pool), 110 * ONE);
+
+            assert_balance!(BOB, HDX, 990 * ONE);
+            assert_balance_approx!(BOB, DOT, 980 * ONE, 1);
+            assert_balance!(pool, HDX, 110 * ONE);
+            assert_balance_approx!(pool, DOT, 220 * ONE, 1);
+
+            let amount_b = Tokens::free_balance(DOT, &pool) - 200 * ONE;
+            assert_eq!(
+                amm_activity(),
+                vec![AmmActivity::LiquidityChanged(
+                    crate::SOURCE,
+                    HDX,
+                    DOT,
+                    10 * ONE,
+                    amount_b,
+                    110 * ONE,
+                    200 * ONE + amount_b
+                )]
+            );
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::LiquidityAdded {
+                who: BOB,
+                asset_a: HDX,
+                asset_b: DOT,
+                amount_a: 10 * ONE,
+                amount_b,
+                shares: 10 * ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_limit_is_exceeded() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DOT, 10 * ONE, 19 * ONE),
+                Error::<Test>::AssetAmountExceededLimit
+            );
+        });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DOT, 10 * ONE, 20 * ONE),
+            Error::<Test>::TokenPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_amount_is_below_trading_limit() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DOT, 999, 20 * ONE),
+                Error::<Test>::InsufficientTradingAmount
+            );
+        });
+}
+
+#[test]
+fn add_liquidity_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::add_liquidity(RuntimeOrigin::signed(BOB), HDX, DOT, 600 * ONE, 2_000 * ONE),
+                Error::<Test>::InsufficientAssetBalance
+            );
+        });
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DOT);
+
+            // Act
+            assert_ok!(XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DOT, 50 * ONE));
+
+            // Assert
+            assert_balance!(ALICE, SHARE_TOKEN_START, 50 * ONE);
+            assert_eq!(XYK::total_liquidity(pool), 50 * ONE);
+
+            assert_balance!(ALICE, HDX, 950 * ONE);
+            assert_balance!(ALICE, DOT, 900 * ONE);
+            assert_balance!(pool, HDX, 50 * ONE);
+            assert_balance!(pool, DOT, 100 * ONE);
+
+            assert_eq!(
+                amm_activity(),
+                vec![AmmActivity::LiquidityChanged(
+                    crate::SOURCE,
+                    HDX,
+                    DOT,
+                    50 * ONE,
+                    100 * ONE,
+                    50 * ONE,
+                    100 * ONE
+                )]
+            );
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::LiquidityRemoved {
+                who: ALICE,
+                asset_a: HDX,
+                asset_b: DOT,
+                amount_a: 50 * ONE,
+                amount_b: 100 * ONE,
+                shares: 50 * ONE,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn remove_liquidity_should_destroy_pool_when_all_liquidity_is_removed() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DOT);
+
+            // Act
+            assert_ok!(XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DOT, 100 * ONE));
+
+            // Assert
+            assert!(!XYK::exists(AssetPair::new(HDX, DOT)));
+            assert_eq!(XYK::total_liquidity(pool), 0);
+            assert_eq!(XYK::pool_assets(pool), None);
+
+            assert_balance!(ALICE, SHARE_TOKEN_START, 0);
+            assert_balance!(ALICE, HDX, 1_000 * ONE);
+            assert_balance!(ALICE, DOT, 1_000 * ONE);
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![
+                Event::LiquidityRemoved {
+                    who: ALICE,
+                    asset_a: HDX,
+                    asset_b: DOT,
+                    amount_a: 100 * ONE,
+                    amount_b: 200 * ONE,
+                    shares: 100 * ONE,
+                }
+                .into(),
+                Event::PoolDestroyed {
+                    who: ALICE,
+                    asset_a: HDX,
+                    asset_b: DOT,
+                    share_token: SHARE_TOKEN_START,
+                    pool,
+                }
+                .into(),
+            ]);
+        });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_remaining_shares_are_below_minimum() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DOT, 100 * ONE - 999),
+                Error::<Test>::InsufficientLiquidity
+            );
+        });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_shares_are_insufficient() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::remove_liquidity(RuntimeOrigin::signed(BOB), HDX, DOT, ONE),
+                Error::<Test>::InsufficientAssetBalance
+            );
+        });
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_amount_is_zero() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DOT, 200 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::remove_liquidity(RuntimeOrigin::signed(ALICE), HDX, DOT, 0),
+                Error::<Test>::ZeroLiquidity
+            );
+        });
+}
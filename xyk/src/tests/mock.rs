@@ -0,0 +1,391 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as pallet_xyk;
+use crate::Config;
+use frame_support::assert_ok;
+use frame_support::dispatch::DispatchResult;
+use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::weights::Weight;
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{ConstU32, ConstU64},
+};
+use hydradx_traits::{
+    AccountIdFor, CanCreatePool, OnCreatePoolHandler, OnLiquidityChangedHandler, OnTradeHandler, Registry,
+    ShareTokenRegistry, Source,
+};
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type Balance = u128;
+pub type AssetId = u32;
+pub type AccountId = u64;
+
+pub const HDX: AssetId = 0;
+pub const DAI: AssetId = 1;
+pub const DOT: AssetId = 2;
+pub const FORBIDDEN: AssetId = 99;
+
+/// First asset id assigned to a share token.
+pub const SHARE_TOKEN_START: AssetId = 1000;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AmmActivity {
+    PoolCreated(AssetId, AssetId),
+    Trade(Source, AssetId, AssetId, Balance, Balance, Balance, Balance),
+    LiquidityChanged(Source, AssetId, AssetId, Balance, Balance, Balance, Balance),
+}
+
+thread_local! {
+    pub static ASSET_IDENTS: RefCell<HashMap<Vec<u8>, AssetId>> = RefCell::new(HashMap::default());
+    pub static NEXT_ASSET_ID: RefCell<AssetId> = RefCell::new(SHARE_TOKEN_START);
+    pub static AMM_ACTIVITY: RefCell<Vec<AmmActivity>> = RefCell::new(Vec::new());
+}
+
+construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Tokens: orml_tokens::{Pallet, Event<T>},
+        XYK: pallet_xyk::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_type_with_key! {
+    pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+        0
+    };
+}
+
+impl orml_tokens::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type Amount = i128;
+    type CurrencyId = AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type CurrencyHooks = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+    type DustRemovalWhitelist = Everything;
+}
+
+parameter_types! {
+    pub const ExchangeFee: (u32, u32) = (2, 1_000);
+    pub const MinTradingLimit: Balance = 1_000;
+    pub const MinPoolLiquidity: Balance = 1_000;
+    pub const MaxInRatio: u128 = 3;
+    pub const MaxOutRatio: u128 = 3;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AssetId = AssetId;
+    type Currency = Tokens;
+    type ShareAccountId = AccountIdConstructor;
+    type AssetRegistry = DummyRegistry;
+    type CanCreatePool = AllowPools;
+    type AMMHandler = AmmActivityRecorder;
+    type GetExchangeFee = ExchangeFee;
+    type MinTradingLimit = MinTradingLimit;
+    type MinPoolLiquidity = MinPoolLiquidity;
+    type MaxInRatio = MaxInRatio;
+    type MaxOutRatio = MaxOutRatio;
+    type WeightInfo = ();
+}
+
+pub struct DummyRegistry;
+
+impl Registry<AssetId, Vec<u8>, Balance, DispatchError> for DummyRegistry {
+    fn exists(asset_id: AssetId) -> bool {
+        ASSET_IDENTS.with(|v| v.borrow().values().any(|id| *id == asset_id))
+    }
+
+    fn retrieve_asset(name: &Vec<u8>) -> Result<AssetId, DispatchError> {
+        ASSET_IDENTS
+            .with(|v| v.borrow().get(name).copied())
+            .ok_or_else(|| DispatchError::Other("AssetNotFound"))
+    }
+
+    fn create_asset(name: &Vec<u8>, _existential_deposit: Balance) -> Result<AssetId, DispatchError> {
+        let assigned = NEXT_ASSET_ID.with(|v| {
+            let id = *v.borrow();
+            *v.borrow_mut() = id + 1;
+            id
+        });
+
+        ASSET_IDENTS.with(|v| v.borrow_mut().insert(name.clone(), assigned));
+
+        Ok(assigned)
+    }
+}
+
+impl ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError> for DummyRegistry {
+    fn retrieve_shared_asset(name: &Vec<u8>, _assets: &[AssetId]) -> Result<AssetId, DispatchError> {
+        Self::retrieve_asset(name)
+    }
+
+    fn create_shared_asset(
+        name: &Vec<u8>,
+        _assets: &[AssetId],
+        existential_deposit: Balance,
+    ) -> Result<AssetId, DispatchError> {
+        Self::get_or_create_asset(name.clone(), existential_deposit)
+    }
+}
+
+pub struct AccountIdConstructor;
+
+impl AccountIdFor<(AssetId, AssetId)> for AccountIdConstructor {
+    type AccountId = AccountId;
+
+    fn from_assets(assets: &(AssetId, AssetId), _identifier: Option<&[u8]>) -> Self::AccountId {
+        // Offset to avoid collision with regular test accounts
+        1_000_000 + (assets.0 as u64) * 1_000 + assets.1 as u64
+    }
+
+    fn name(assets: &(AssetId, AssetId), identifier: Option<&[u8]>) -> Vec<u8> {
+        let mut buf: Vec<u8> = if let Some(ident) = identifier {
+            ident.to_vec()
+        } else {
+            vec![]
+        };
+        buf.extend_from_slice(&(assets.0).to_le_bytes());
+        buf.extend_from_slice(&(assets.1).to_le_bytes());
+
+        buf
+    }
+}
+
+pub struct AllowPools;
+
+impl CanCreatePool<AssetId> for AllowPools {
+    fn can_create(asset_a: AssetId, asset_b: AssetId) -> bool {
+        asset_a != FORBIDDEN && asset_b != FORBIDDEN
+    }
+}
+
+pub struct AmmActivityRecorder;
+
+impl OnCreatePoolHandler<AssetId> for AmmActivityRecorder {
+    fn on_create_pool(asset_a: AssetId, asset_b: AssetId) -> DispatchResult {
+        AMM_ACTIVITY.with(|v| v.borrow_mut().push(AmmActivity::PoolCreated(asset_a, asset_b)));
+        Ok(())
+    }
+}
+
+impl OnTradeHandler<AssetId, Balance> for AmmActivityRecorder {
+    fn on_trade(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+        amount_a: Balance,
+        amount_b: Balance,
+        liquidity_a: Balance,
+        liquidity_b: Balance,
+    ) -> Result<Weight, (Weight, DispatchError)> {
+        AMM_ACTIVITY.with(|v| {
+            v.borrow_mut().push(AmmActivity::Trade(
+                source,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                liquidity_a,
+                liquidity_b,
+            ))
+        });
+        Ok(Weight::zero())
+    }
+
+    fn on_trade_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl OnLiquidityChangedHandler<AssetId, Balance> for AmmActivityRecorder {
+    fn on_liquidity_changed(
+        source: Source,
+        asset_a: AssetId,
+        asset_b: AssetId,
+        amount_a: Balance,
+        amount_b: Balance,
+        liquidity_a: Balance,
+        liquidity_b: Balance,
+    ) -> Result<Weight, (Weight, DispatchError)> {
+        AMM_ACTIVITY.with(|v| {
+            v.borrow_mut().push(AmmActivity::LiquidityChanged(
+                source,
+                asset_a,
+                asset_b,
+                amount_a,
+                amount_b,
+                liquidity_a,
+                liquidity_b,
+            ))
+        });
+        Ok(Weight::zero())
+    }
+
+    fn on_liquidity_changed_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+pub(crate) fn amm_activity() -> Vec<AmmActivity> {
+    AMM_ACTIVITY.with(|v| v.borrow().clone())
+}
+
+pub(crate) fn pool_account(asset_a: AssetId, asset_b: AssetId) -> AccountId {
+    let pair = if asset_a <= asset_b {
+        (asset_a, asset_b)
+    } else {
+        (asset_b, asset_a)
+    };
+    AccountIdConstructor::from_assets(&pair, Some(crate::POOL_IDENTIFIER))
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+    created_pools: Vec<(AccountId, AssetId, Balance, AssetId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        // If eg. tests running on one thread only, this thread local is shared.
+        // let's make sure that it is empty for each  test case
+        // or set to original default value
+        ASSET_IDENTS.with(|v| {
+            v.borrow_mut().clear();
+        });
+        NEXT_ASSET_ID.with(|v| {
+            *v.borrow_mut() = SHARE_TOKEN_START;
+        });
+        AMM_ACTIVITY.with(|v| {
+            v.borrow_mut().clear();
+        });
+        Self {
+            endowed_accounts: vec![
+                (ALICE, HDX, 1_000 * ONE),
+                (ALICE, DAI, 1_000 * ONE),
+                (ALICE, DOT, 1_000 * ONE),
+                (ALICE, FORBIDDEN, 1_000 * ONE),
+                (BOB, HDX, 1_000 * ONE),
+                (BOB, DAI, 1_000 * ONE),
+                (BOB, DOT, 1_000 * ONE),
+            ],
+            created_pools: vec![],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn with_endowed_accounts(mut self, accounts: Vec<(AccountId, AssetId, Balance)>) -> Self {
+        self.endowed_accounts = accounts;
+        self
+    }
+
+    pub fn with_pool(
+        mut self,
+        who: AccountId,
+        asset_a: AssetId,
+        amount_a: Balance,
+        asset_b: AssetId,
+        amount_b: Balance,
+    ) -> Self {
+        self.created_pools.push((who, asset_a, amount_a, asset_b, amount_b));
+        self
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        orml_tokens::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut r: sp_io::TestExternalities = t.into();
+
+        r.execute_with(|| {
+            System::set_block_number(1);
+
+            for (who, asset_a, amount_a, asset_b, amount_b) in self.created_pools {
+                assert_ok!(XYK::create_pool(
+                    RuntimeOrigin::signed(who),
+                    asset_a,
+                    amount_a,
+                    asset_b,
+                    amount_b
+                ));
+            }
+
+            AMM_ACTIVITY.with(|v| {
+                v.borrow_mut().clear();
+            });
+        });
+
+        r
+    }
+}
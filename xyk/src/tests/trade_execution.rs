@@ -0,0 +1,106 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use frame_support::assert_ok;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use test_utils::assert_balance;
+
+#[test]
+fn calculate_sell_should_return_amount_received_without_fee() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            let result = XYK::calculate_sell(PoolType::XYK, HDX, DAI, 25 * ONE);
+
+            assert_eq!(result, Ok(20 * ONE - 40_000_000_000));
+        });
+}
+
+#[test]
+fn calculate_buy_should_return_amount_paid_including_fee() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Act
+            let expected = XYK::calculate_buy(PoolType::XYK, HDX, DAI, 20 * ONE).unwrap();
+            assert_ok!(XYK::execute_buy(
+                RuntimeOrigin::signed(BOB),
+                PoolType::XYK,
+                HDX,
+                DAI,
+                20 * ONE,
+                expected
+            ));
+
+            // Assert
+            assert_balance!(BOB, HDX, 1_000 * ONE - expected);
+            assert_balance!(BOB, DAI, 1_020 * ONE);
+        });
+}
+
+#[test]
+fn execute_sell_should_work() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let expected = XYK::calculate_sell(PoolType::XYK, HDX, DAI, 25 * ONE).unwrap();
+
+            // Act
+            assert_ok!(XYK::execute_sell(
+                RuntimeOrigin::signed(BOB),
+                PoolType::XYK,
+                HDX,
+                DAI,
+                25 * ONE,
+                expected
+            ));
+
+            // Assert
+            assert_balance!(BOB, HDX, 975 * ONE);
+            assert_balance!(BOB, DAI, 1_000 * ONE + expected);
+        });
+}
+
+#[test]
+fn trade_execution_should_not_support_other_pool_types() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_eq!(
+                XYK::calculate_sell(PoolType::LBP, HDX, DAI, ONE),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                XYK::calculate_buy(PoolType::Omnipool, HDX, DAI, ONE),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                XYK::execute_sell(RuntimeOrigin::signed(BOB), PoolType::Stableswap(HDX), HDX, DAI, ONE, 0),
+                Err(ExecutorError::NotSupported)
+            );
+            assert_eq!(
+                XYK::execute_buy(RuntimeOrigin::signed(BOB), PoolType::LBP, HDX, DAI, ONE, ONE),
+                Err(ExecutorError::NotSupported)
+            );
+        });
+}
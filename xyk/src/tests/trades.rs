@@ -0,0 +1,283 @@
+// This file is part of galacticcouncil/warehouse.
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB). SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use test_utils::{assert_balance, assert_balance_approx};
+
+#[test]
+fn sell_should_work() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DAI);
+            let expected_fee = 40_000_000_000;
+            let expected_received = 20 * ONE - expected_fee;
+
+            // Act
+            assert_ok!(XYK::sell(
+                RuntimeOrigin::signed(BOB),
+                HDX,
+                DAI,
+                25 * ONE,
+                19 * ONE,
+                false
+            ));
+
+            // Assert
+            assert_balance!(BOB, HDX, 975 * ONE);
+            assert_balance!(BOB, DAI, 1_000 * ONE + expected_received);
+            assert_balance!(pool, HDX, 125 * ONE);
+            assert_balance!(pool, DAI, 100 * ONE - expected_received);
+
+            assert_eq!(
+                amm_activity(),
+                vec![AmmActivity::Trade(
+                    crate::SOURCE,
+                    HDX,
+                    DAI,
+                    25 * ONE,
+                    expected_received,
+                    125 * ONE,
+                    100 * ONE - expected_received
+                )]
+            );
+
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::SellExecuted {
+                who: BOB,
+                asset_in: HDX,
+                asset_out: DAI,
+                amount_in: 25 * ONE,
+                amount_out: expected_received,
+                fee_asset: DAI,
+                fee_amount: expected_fee,
+                pool,
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn sell_should_fail_when_min_limit_is_not_reached() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 25 * ONE, 20 * ONE, false),
+                Error::<Test>::AssetAmountNotReachedLimit
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_max_in_ratio_is_exceeded() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 34 * ONE, 0, false),
+                Error::<Test>::MaxInRatioExceeded
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_amount_is_below_trading_limit() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 999, 0, false),
+                Error::<Test>::InsufficientTradingAmount
+            );
+        });
+}
+
+#[test]
+fn sell_should_fail_when_pool_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, 0, false),
+            Error::<Test>::TokenPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn sell_should_fail_when_discount_is_requested() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, 0, true),
+                Error::<Test>::CannotApplyDiscount
+            );
+        });
+}
+
+#[test]
+fn buy_should_work() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DAI);
+
+            // Act
+            assert_ok!(XYK::buy(
+                RuntimeOrigin::signed(BOB),
+                DAI,
+                HDX,
+                20 * ONE,
+                26 * ONE,
+                false
+            ));
+
+            // Assert
+            // 25 HDX required by the pool + 0.2% fee
+            let expected_paid = 25_050_000_000_000;
+            assert_balance!(BOB, DAI, 1_020 * ONE);
+            assert_balance_approx!(BOB, HDX, 1_000 * ONE - expected_paid, 1);
+            assert_balance!(pool, DAI, 80 * ONE);
+            assert_balance_approx!(pool, HDX, 100 * ONE + expected_paid, 1);
+
+            let paid = 1_000 * ONE - Tokens::free_balance(HDX, &BOB);
+            assert_eq!(
+                amm_activity(),
+                vec![AmmActivity::Trade(
+                    crate::SOURCE,
+                    HDX,
+                    DAI,
+                    paid,
+                    20 * ONE,
+                    100 * ONE + paid,
+                    80 * ONE
+                )]
+            );
+        });
+}
+
+#[test]
+fn buy_should_emit_event_with_fee_excluded_from_amount_in() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Act
+            assert_ok!(XYK::buy(
+                RuntimeOrigin::signed(BOB),
+                DAI,
+                HDX,
+                20 * ONE,
+                26 * ONE,
+                false
+            ));
+
+            // Assert
+            let paid = 1_000 * ONE - Tokens::free_balance(HDX, &BOB);
+            let fee = 50_000_000_000;
+            test_utils::expect_events::<RuntimeEvent, Test>(vec![Event::BuyExecuted {
+                who: BOB,
+                asset_out: DAI,
+                asset_in: HDX,
+                amount_out: 20 * ONE,
+                amount_in: paid - fee,
+                fee_asset: HDX,
+                fee_amount: fee,
+                pool: pool_account(HDX, DAI),
+            }
+            .into()]);
+        });
+}
+
+#[test]
+fn buy_should_fail_when_max_limit_is_exceeded() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 20 * ONE, 25 * ONE, false),
+                Error::<Test>::AssetAmountExceededLimit
+            );
+        });
+}
+
+#[test]
+fn buy_should_fail_when_max_out_ratio_is_exceeded() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 34 * ONE, 100 * ONE, false),
+                Error::<Test>::MaxOutRatioExceeded
+            );
+        });
+}
+
+#[test]
+fn buy_should_fail_when_balance_is_insufficient() {
+    ExtBuilder::default()
+        .with_endowed_accounts(vec![
+            (ALICE, HDX, 1_000 * ONE),
+            (ALICE, DAI, 1_000 * ONE),
+            (BOB, HDX, 20 * ONE),
+        ])
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XYK::buy(RuntimeOrigin::signed(BOB), DAI, HDX, 20 * ONE, 26 * ONE, false),
+                Error::<Test>::InsufficientAssetBalance
+            );
+        });
+}
+
+#[test]
+fn sell_and_buy_should_keep_invariant_non_decreasing() {
+    ExtBuilder::default()
+        .with_pool(ALICE, HDX, 100 * ONE, DAI, 100 * ONE)
+        .build()
+        .execute_with(|| {
+            // Arrange
+            let pool = pool_account(HDX, DAI);
+            let invariant = |pool| {
+                sp_core::U256::from(Tokens::free_balance(HDX, &pool))
+                    * sp_core::U256::from(Tokens::free_balance(DAI, &pool))
+            };
+            let initial = invariant(pool);
+
+            // Act
+            assert_ok!(XYK::sell(RuntimeOrigin::signed(BOB), HDX, DAI, 10 * ONE, 0, false));
+            let after_sell = invariant(pool);
+            assert_ok!(XYK::buy(RuntimeOrigin::signed(BOB), HDX, DAI, 5 * ONE, 10 * ONE, false));
+            let after_buy = invariant(pool);
+
+            // Assert
+            assert!(after_sell >= initial);
+            assert!(after_buy >= after_sell);
+        });
+}
@@ -0,0 +1,76 @@
+use crate::types::{AssetPair, Balance};
+use crate::{Config, Pallet};
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use sp_runtime::{ArithmeticError, DispatchError};
+
+impl<T: Config> TradeExecution<T::RuntimeOrigin, T::AccountId, T::AssetId, Balance> for Pallet<T> {
+    type Error = DispatchError;
+
+    fn calculate_sell(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::XYK => {
+                let (amount_out, _) = Self::calculate_out_amount(AssetPair::new(asset_in, asset_out), amount_in)
+                    .map_err(ExecutorError::Error)?;
+
+                Ok(amount_out)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn calculate_buy(
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+    ) -> Result<Balance, ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::XYK => {
+                let (amount_in, fee) = Self::calculate_in_amount(AssetPair::new(asset_in, asset_out), amount_out)
+                    .map_err(ExecutorError::Error)?;
+
+                amount_in
+                    .checked_add(fee)
+                    .ok_or_else(|| ExecutorError::Error(ArithmeticError::Overflow.into()))
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_sell(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_in: Balance,
+        min_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::XYK => {
+                Self::sell(who, asset_in, asset_out, amount_in, min_limit, false).map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+
+    fn execute_buy(
+        who: T::RuntimeOrigin,
+        pool_type: PoolType<T::AssetId>,
+        asset_in: T::AssetId,
+        asset_out: T::AssetId,
+        amount_out: Balance,
+        max_limit: Balance,
+    ) -> Result<(), ExecutorError<Self::Error>> {
+        match pool_type {
+            PoolType::XYK => {
+                Self::buy(who, asset_out, asset_in, amount_out, max_limit, false).map_err(ExecutorError::Error)
+            }
+            _ => Err(ExecutorError::NotSupported),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_xyk
+//!
+//! PLACEHOLDER WEIGHTS - NOT GENERATED BY THE BENCHMARK CLI.
+//! The weights are estimates derived by hand from the storage accesses of each call, not the output of the
+//! `pallet-xyk` benchmarks. Regenerate them with the benchmarks before release:
+//!
+//! target/release/hydradx benchmark pallet --chain=dev --steps=5 --repeat=20 --execution=wasm
+//! --wasm-execution=compiled --heap-pages=4096 --template=.maintain/pallet-weight-template.hbs --pallet=pallet-xyk
+//! --output=xyk.rs --extrinsic=*
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_xyk.
+pub trait WeightInfo {
+    fn create_pool() -> Weight;
+    fn add_liquidity() -> Weight;
+    fn remove_liquidity() -> Weight;
+    fn sell() -> Weight;
+    fn buy() -> Weight;
+}
+
+pub struct BasiliskWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(86_452_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(12 as u64))
+            .saturating_add(T::DbWeight::get().writes(12 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(78_313_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(11 as u64))
+            .saturating_add(T::DbWeight::get().writes(6 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(74_617_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(10 as u64))
+            .saturating_add(T::DbWeight::get().writes(7 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(59_843_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(8 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(60_216_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(8 as u64))
+            .saturating_add(T::DbWeight::get().writes(4 as u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_pool() -> Weight {
+        Weight::from_ref_time(86_452_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(12 as u64))
+            .saturating_add(RocksDbWeight::get().writes(12 as u64))
+    }
+    fn add_liquidity() -> Weight {
+        Weight::from_ref_time(78_313_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(11 as u64))
+            .saturating_add(RocksDbWeight::get().writes(6 as u64))
+    }
+    fn remove_liquidity() -> Weight {
+        Weight::from_ref_time(74_617_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(10 as u64))
+            .saturating_add(RocksDbWeight::get().writes(7 as u64))
+    }
+    fn sell() -> Weight {
+        Weight::from_ref_time(59_843_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(8 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+    fn buy() -> Weight {
+        Weight::from_ref_time(60_216_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(8 as u64))
+            .saturating_add(RocksDbWeight::get().writes(4 as u64))
+    }
+}